@@ -0,0 +1,127 @@
+use syn::{Attribute, Ident, LitStr, Result};
+
+/// How an enum is laid out in JSON, chosen with `#[json(...)]` on the enum.
+pub enum Tagging {
+    /// `{"Variant": content}`, or `"Variant"` for unit variants (the default)
+    External,
+    /// `#[json(tag = "type")]`: `{"type": "Variant", ...fields}`
+    Internal(String),
+    /// `#[json(tag = "t", content = "c")]`: `{"t": "Variant", "c": content}`
+    Adjacent(String, String),
+    /// `#[json(untagged)]`: content only, variants tried in order when reading
+    Untagged,
+}
+
+pub struct ContainerAttrs {
+    pub tagging: Tagging,
+}
+
+#[derive(Default)]
+pub struct FieldAttrs {
+    pub rename: Option<String>,
+    pub default: bool,
+    pub skip: bool,
+    pub flatten: bool,
+}
+
+#[derive(Default)]
+pub struct VariantAttrs {
+    pub rename: Option<String>,
+}
+
+impl ContainerAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut tag = None;
+        let mut content = None;
+        let mut untagged = false;
+        for attr in attrs.iter().filter(|a| a.path().is_ident("json")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tag") {
+                    tag = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("content") {
+                    content = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("untagged") {
+                    untagged = true;
+                } else {
+                    return Err(meta.error("unsupported container attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        let tagging = match (tag, content, untagged) {
+            (None, None, false) => Tagging::External,
+            (Some(tag), None, false) => Tagging::Internal(tag),
+            (Some(tag), Some(content), false) => Tagging::Adjacent(tag, content),
+            (None, None, true) => Tagging::Untagged,
+            (None, Some(_), _) => {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "`content` requires `tag`",
+                ));
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "`untagged` cannot be combined with `tag`",
+                ));
+            }
+        };
+        Ok(Self { tagging })
+    }
+}
+
+impl FieldAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut out = Self::default();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("json")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    out.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("default") {
+                    out.default = true;
+                } else if meta.path.is_ident("skip") {
+                    out.skip = true;
+                } else if meta.path.is_ident("flatten") {
+                    out.flatten = true;
+                } else {
+                    return Err(meta.error("unsupported field attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(out)
+    }
+
+    /// The JSON member name for a named field.
+    pub fn key(&self, ident: &Ident) -> String {
+        self.rename.clone().unwrap_or_else(|| unraw(ident))
+    }
+}
+
+impl VariantAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut out = Self::default();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("json")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    out.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else {
+                    return Err(meta.error("unsupported variant attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(out)
+    }
+
+    pub fn name(&self, ident: &Ident) -> String {
+        self.rename.clone().unwrap_or_else(|| unraw(ident))
+    }
+}
+
+// `r#type` becomes the JSON member `type`
+fn unraw(ident: &Ident) -> String {
+    let name = ident.to_string();
+    name.strip_prefix("r#").map(str::to_string).unwrap_or(name)
+}
@@ -0,0 +1,240 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Result, Variant};
+
+use crate::add_bounds;
+use crate::attrs::{ContainerAttrs, FieldAttrs, Tagging, VariantAttrs};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let generics = add_bounds(
+        &input.generics,
+        quote!(::parse_light::convert::from_json::FromJson),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => construct(quote!(Self), &data.fields, &quote!(value), &quote!())?,
+        Data::Enum(data) => {
+            let container = ContainerAttrs::parse(&input.attrs)?;
+            enum_body(
+                &data.variants.iter().collect::<Vec<_>>(),
+                &container.tagging,
+            )?
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "FromJson cannot be derived for unions",
+            ));
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::parse_light::convert::from_json::FromJson for #name #ty_generics #where_clause {
+            fn from_json(
+                value: &::parse_light::core::json_value::JsonValue,
+            ) -> ::std::result::Result<Self, ::parse_light::convert::from_json::FromJsonError> {
+                #body
+            }
+        }
+    })
+}
+
+// Statements ending in `Ok(#path ..)`, reading `fields` out of the JSON `value`.
+// `wrap` is appended to every fallible call before its `?`.
+fn construct(
+    path: TokenStream,
+    fields: &Fields,
+    value: &TokenStream,
+    wrap: &TokenStream,
+) -> Result<TokenStream> {
+    match fields {
+        Fields::Named(named) => {
+            let mut inits = Vec::new();
+            for field in &named.named {
+                let attrs = FieldAttrs::parse(&field.attrs)?;
+                let ident = field.ident.as_ref().expect("named field");
+                let key = attrs.key(ident);
+                let init = if attrs.skip {
+                    quote!(::std::default::Default::default())
+                } else if attrs.flatten {
+                    quote!(::parse_light::convert::from_json::FromJson::from_json(#value) #wrap ?)
+                } else if attrs.default {
+                    quote! {
+                        ::parse_light::convert::from_json::from_json_field_or_default(__object, #key) #wrap ?
+                    }
+                } else {
+                    quote!(::parse_light::convert::from_json::from_json_field(__object, #key) #wrap ?)
+                };
+                inits.push(quote!(#ident: #init));
+            }
+            Ok(quote! {
+                #[allow(unused_variables)]
+                let __object = ::parse_light::convert::from_json::from_json_object(#value) #wrap ?;
+                Ok(#path { #(#inits),* })
+            })
+        }
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => Ok(quote! {
+            Ok(#path(::parse_light::convert::from_json::FromJson::from_json(#value) #wrap ?))
+        }),
+        Fields::Unnamed(unnamed) => {
+            let count = unnamed.unnamed.len();
+            let items = (0..count).map(|i| {
+                let index = i.to_string();
+                quote! {
+                    ::parse_light::convert::from_json::FromJson::from_json(&__items[#i])
+                        .map_err(|e| e.within(#index)) #wrap ?
+                }
+            });
+            Ok(quote! {
+                let __items = ::parse_light::convert::from_json::from_json_array(#value, #count) #wrap ?;
+                Ok(#path(#(#items),*))
+            })
+        }
+        Fields::Unit => Ok(quote! {
+            <() as ::parse_light::convert::from_json::FromJson>::from_json(#value) #wrap ?;
+            Ok(#path)
+        }),
+    }
+}
+
+fn enum_body(variants: &[&Variant], tagging: &Tagging) -> Result<TokenStream> {
+    let mut arms = Vec::new();
+    for variant in variants {
+        let ident = &variant.ident;
+        let name = VariantAttrs::parse(&variant.attrs)?.name(ident);
+        arms.push((variant, name, quote!(Self::#ident)));
+    }
+    let unknown = quote! {
+        __name => Err(::parse_light::convert::from_json::FromJsonError::new(
+            format!("Unknown variant '{}'", __name),
+        )),
+    };
+
+    match tagging {
+        Tagging::External => {
+            let unit_arms = arms
+                .iter()
+                .filter(|(variant, _, _)| matches!(variant.fields, Fields::Unit))
+                .map(|(_, name, path)| quote!(#name => Ok(#path),));
+            let mut content_arms = Vec::new();
+            for (variant, name, path) in &arms {
+                let wrap = quote!(.map_err(|e| e.within(#name)));
+                let body = construct(path.clone(), &variant.fields, &quote!(__content), &wrap)?;
+                content_arms.push(quote!(#name => { #body }));
+            }
+            Ok(quote! {
+                match value {
+                    ::parse_light::core::json_value::JsonValue::String(__name) => {
+                        match __name.as_str() {
+                            #(#unit_arms)*
+                            #unknown
+                        }
+                    }
+                    ::parse_light::core::json_value::JsonValue::Object(__outer) if __outer.len() == 1 => {
+                        let (__name, __content) = __outer.iter().next().expect("single member");
+                        match __name.as_str() {
+                            #(#content_arms)*
+                            #unknown
+                        }
+                    }
+                    other => Err(::parse_light::convert::from_json::FromJsonError::expected(
+                        "string or single-member object",
+                        other,
+                    )),
+                }
+            })
+        }
+        Tagging::Internal(tag) => {
+            let mut tag_arms = Vec::new();
+            for (variant, name, path) in &arms {
+                let body = match &variant.fields {
+                    Fields::Unit => quote!(Ok(#path)),
+                    Fields::Unnamed(unnamed) if unnamed.unnamed.len() != 1 => {
+                        return Err(syn::Error::new_spanned(
+                            variant,
+                            "internally tagged enums only support unit, struct and newtype variants",
+                        ));
+                    }
+                    fields => construct(path.clone(), fields, &quote!(value), &quote!())?,
+                };
+                tag_arms.push(quote!(#name => { #body }));
+            }
+            let read_tag = read_tag(tag);
+            Ok(quote! {
+                #read_tag
+                match __tag {
+                    #(#tag_arms)*
+                    #unknown
+                }
+            })
+        }
+        Tagging::Adjacent(tag, content_key) => {
+            let mut tag_arms = Vec::new();
+            for (variant, name, path) in &arms {
+                let body = match &variant.fields {
+                    Fields::Unit => quote!(Ok(#path)),
+                    fields => {
+                        let wrap = quote!(.map_err(|e| e.within(#content_key)));
+                        let body = construct(path.clone(), fields, &quote!(__content), &wrap)?;
+                        quote! {
+                            let __content = __tagged.get(#content_key).ok_or_else(|| {
+                                ::parse_light::convert::from_json::FromJsonError::missing_field(#content_key)
+                            })?;
+                            #body
+                        }
+                    }
+                };
+                tag_arms.push(quote!(#name => { #body }));
+            }
+            let read_tag = read_tag(tag);
+            Ok(quote! {
+                #read_tag
+                match __tag {
+                    #(#tag_arms)*
+                    #unknown
+                }
+            })
+        }
+        Tagging::Untagged => {
+            let mut attempts = Vec::new();
+            for (variant, _, path) in &arms {
+                let body = construct(path.clone(), &variant.fields, &quote!(value), &quote!())?;
+                attempts.push(quote! {
+                    let __attempt = || -> ::std::result::Result<Self, ::parse_light::convert::from_json::FromJsonError> {
+                        #body
+                    };
+                    if let Ok(__variant) = __attempt() {
+                        return Ok(__variant);
+                    }
+                });
+            }
+            Ok(quote! {
+                #(#attempts)*
+                Err(::parse_light::convert::from_json::FromJsonError::new(
+                    "Data did not match any variant of untagged enum",
+                ))
+            })
+        }
+    }
+}
+
+// Binds `__tagged` to the object and `__tag` to its tag member
+fn read_tag(tag: &str) -> TokenStream {
+    quote! {
+        let __tagged = ::parse_light::convert::from_json::from_json_object(value)?;
+        let __tag = match __tagged.get(#tag) {
+            Some(::parse_light::core::json_value::JsonValue::String(__tag)) => __tag.as_str(),
+            Some(other) => {
+                return Err(
+                    ::parse_light::convert::from_json::FromJsonError::expected("string", other)
+                        .within(#tag),
+                );
+            }
+            None => {
+                return Err(::parse_light::convert::from_json::FromJsonError::missing_field(#tag));
+            }
+        };
+    }
+}
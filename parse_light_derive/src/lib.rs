@@ -0,0 +1,49 @@
+//! Derive macros for `parse_light::convert::{FromJson, ToJson}`.
+//!
+//! Enable them through the `derive` feature of `parse_light` rather than
+//! depending on this crate directly.
+mod attrs;
+mod from_json;
+mod to_json;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{DeriveInput, Generics, TypeParamBound, parse_macro_input, parse_quote};
+
+/// Derives `ToJson`.
+///
+/// Structs with named fields become objects, newtype structs are transparent,
+/// tuple structs become arrays and unit structs become `null`. Enums are
+/// externally tagged unless `#[json(tag = "..")]`, `#[json(tag = "..", content = "..")]`
+/// or `#[json(untagged)]` is given. Fields accept `rename = ".."`, `skip` and
+/// `flatten`; variants accept `rename = ".."`.
+#[proc_macro_derive(ToJson, attributes(json))]
+pub fn derive_to_json(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    to_json::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `FromJson`, reading the same layout `ToJson` writes.
+///
+/// In addition to the `ToJson` attributes, fields accept `default`, which
+/// falls back to `Default::default()` when the member is absent. Skipped
+/// fields are always filled from `Default`.
+#[proc_macro_derive(FromJson, attributes(json))]
+pub fn derive_from_json(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_json::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// Requires every type parameter to implement the derived trait
+fn add_bounds(generics: &Generics, bound: TokenStream2) -> Generics {
+    let mut generics = generics.clone();
+    let bound: TypeParamBound = parse_quote!(#bound);
+    for param in generics.type_params_mut() {
+        param.bounds.push(bound.clone());
+    }
+    generics
+}
@@ -0,0 +1,216 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, FieldsNamed, Result, Variant};
+
+use crate::add_bounds;
+use crate::attrs::{ContainerAttrs, FieldAttrs, Tagging, VariantAttrs};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let generics = add_bounds(
+        &input.generics,
+        quote!(::parse_light::convert::to_json::ToJson),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => struct_body(&data.fields)?,
+        Data::Enum(data) => {
+            let container = ContainerAttrs::parse(&input.attrs)?;
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| variant_arm(variant, &container.tagging))
+                .collect::<Result<Vec<_>>>()?;
+            if arms.is_empty() {
+                quote!(match *self {})
+            } else {
+                quote!(match self { #(#arms)* })
+            }
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "ToJson cannot be derived for unions",
+            ));
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::parse_light::convert::to_json::ToJson for #name #ty_generics #where_clause {
+            fn to_json(&self) -> ::parse_light::core::json_value::JsonValue {
+                #body
+            }
+        }
+    })
+}
+
+fn struct_body(fields: &Fields) -> Result<TokenStream> {
+    match fields {
+        Fields::Named(named) => {
+            let inserts = named_inserts(named, |ident| quote!(&self.#ident))?;
+            Ok(object(inserts))
+        }
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => Ok(quote!(
+            ::parse_light::convert::to_json::ToJson::to_json(&self.0)
+        )),
+        Fields::Unnamed(unnamed) => {
+            let items = (0..unnamed.unnamed.len()).map(syn::Index::from).map(
+                |index| quote!(::parse_light::convert::to_json::ToJson::to_json(&self.#index)),
+            );
+            Ok(quote!(::parse_light::core::json_value::JsonValue::Array(
+                vec![#(#items),*]
+            )))
+        }
+        Fields::Unit => Ok(quote!(::parse_light::core::json_value::JsonValue::Null)),
+    }
+}
+
+// Statements inserting each named field into a `__map` in scope
+fn named_inserts(
+    fields: &FieldsNamed,
+    access: impl Fn(&syn::Ident) -> TokenStream,
+) -> Result<Vec<TokenStream>> {
+    let mut inserts = Vec::new();
+    for field in &fields.named {
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+        let ident = field.ident.as_ref().expect("named field");
+        let value = access(ident);
+        if attrs.skip {
+            continue;
+        }
+        if attrs.flatten {
+            inserts.push(quote! {
+                if let ::parse_light::core::json_value::JsonValue::Object(inner) =
+                    ::parse_light::convert::to_json::ToJson::to_json(#value)
+                {
+                    __map.extend(inner);
+                }
+            });
+        } else {
+            let key = attrs.key(ident);
+            inserts.push(quote! {
+                __map.insert(
+                    #key.to_string(),
+                    ::parse_light::convert::to_json::ToJson::to_json(#value),
+                );
+            });
+        }
+    }
+    Ok(inserts)
+}
+
+fn object(inserts: Vec<TokenStream>) -> TokenStream {
+    quote! {{
+        #[allow(unused_mut)]
+        let mut __map = ::std::collections::HashMap::new();
+        #(#inserts)*
+        ::parse_light::core::json_value::JsonValue::Object(__map)
+    }}
+}
+
+fn variant_arm(variant: &Variant, tagging: &Tagging) -> Result<TokenStream> {
+    let ident = &variant.ident;
+    let name = VariantAttrs::parse(&variant.attrs)?.name(ident);
+    let string =
+        |s: &str| quote!(::parse_light::core::json_value::JsonValue::String(#s.to_string()));
+    let name_value = string(&name);
+
+    // The pattern binding the variant's fields and the JSON for its content
+    let (pattern, content) = match &variant.fields {
+        Fields::Named(named) => {
+            let mut bindings = Vec::new();
+            for field in &named.named {
+                if FieldAttrs::parse(&field.attrs)?.skip {
+                    continue;
+                }
+                let field_ident = field.ident.as_ref().expect("named field");
+                let binding = format_ident!("__{}", field_ident);
+                bindings.push(quote!(#field_ident: #binding));
+            }
+            let inserts = named_inserts(named, |field_ident| {
+                let binding = format_ident!("__{}", field_ident);
+                quote!(#binding)
+            })?;
+            (quote!(Self::#ident { #(#bindings,)* .. }), Some(inserts))
+        }
+        Fields::Unnamed(unnamed) => {
+            let bindings = (0..unnamed.unnamed.len())
+                .map(|i| format_ident!("__field{}", i))
+                .collect::<Vec<_>>();
+            (quote!(Self::#ident(#(#bindings),*)), None)
+        }
+        Fields::Unit => (quote!(Self::#ident), None),
+    };
+
+    let unnamed_content = |count: usize| {
+        let bindings = (0..count).map(|i| format_ident!("__field{}", i));
+        if count == 1 {
+            quote!(::parse_light::convert::to_json::ToJson::to_json(__field0))
+        } else {
+            quote! {
+                ::parse_light::core::json_value::JsonValue::Array(vec![
+                    #(::parse_light::convert::to_json::ToJson::to_json(#bindings)),*
+                ])
+            }
+        }
+    };
+
+    let body = match (tagging, &variant.fields) {
+        (Tagging::External, Fields::Unit) => name_value,
+        (Tagging::Untagged, Fields::Unit) => {
+            quote!(::parse_light::core::json_value::JsonValue::Null)
+        }
+        (Tagging::Internal(tag) | Tagging::Adjacent(tag, _), Fields::Unit) => {
+            object(vec![quote! {
+                __map.insert(#tag.to_string(), #name_value);
+            }])
+        }
+        (Tagging::Internal(tag), Fields::Named(_)) => {
+            let mut inserts = content.expect("named content");
+            inserts.insert(0, quote!(__map.insert(#tag.to_string(), #name_value);));
+            object(inserts)
+        }
+        (Tagging::Internal(tag), Fields::Unnamed(unnamed)) => {
+            if unnamed.unnamed.len() != 1 {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "internally tagged enums only support unit, struct and newtype variants",
+                ));
+            }
+            let message = format!(
+                "Internally tagged variant {} must serialize to an object",
+                ident
+            );
+            quote! {
+                match ::parse_light::convert::to_json::ToJson::to_json(__field0) {
+                    ::parse_light::core::json_value::JsonValue::Object(mut __map) => {
+                        __map.insert(#tag.to_string(), #name_value);
+                        ::parse_light::core::json_value::JsonValue::Object(__map)
+                    }
+                    _ => panic!(#message),
+                }
+            }
+        }
+        (_, fields) => {
+            let value = match fields {
+                Fields::Named(_) => object(content.expect("named content")),
+                Fields::Unnamed(unnamed) => unnamed_content(unnamed.unnamed.len()),
+                Fields::Unit => unreachable!(),
+            };
+            match tagging {
+                Tagging::External => object(vec![quote! {
+                    __map.insert(#name.to_string(), #value);
+                }]),
+                Tagging::Adjacent(tag, content_key) => object(vec![
+                    quote!(__map.insert(#tag.to_string(), #name_value);),
+                    quote!(__map.insert(#content_key.to_string(), #value);),
+                ]),
+                Tagging::Untagged => value,
+                Tagging::Internal(_) => unreachable!(),
+            }
+        }
+    };
+
+    Ok(quote!(#pattern => #body,))
+}
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+
+use parse_light::convert::from_json::FromJson;
+use parse_light::convert::to_json::ToJson;
+use parse_light::core::json_value::JsonValue;
+use parse_light::{parse, stringify};
+use parse_light_derive::{FromJson, ToJson};
+
+#[derive(Debug, PartialEq, Default, ToJson, FromJson)]
+struct Address {
+    city: String,
+    zip: Option<String>,
+}
+
+#[derive(Debug, PartialEq, ToJson, FromJson)]
+struct User {
+    #[json(rename = "userName")]
+    name: String,
+    age: u32,
+    #[json(default)]
+    tags: Vec<String>,
+    #[json(skip)]
+    cache: Option<u64>,
+    address: Address,
+    nickname: Option<String>,
+}
+
+#[derive(Debug, PartialEq, ToJson, FromJson)]
+struct Meters(f64);
+
+#[derive(Debug, PartialEq, ToJson, FromJson)]
+struct Point(i32, i32);
+
+#[derive(Debug, PartialEq, ToJson, FromJson)]
+struct Marker;
+
+#[derive(Debug, PartialEq, ToJson, FromJson)]
+struct Wrapper<T> {
+    items: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, ToJson, FromJson)]
+struct Located {
+    id: u32,
+    #[json(flatten)]
+    address: Address,
+}
+
+#[derive(Debug, PartialEq, ToJson, FromJson)]
+enum Shape {
+    Empty,
+    #[json(rename = "circle")]
+    Circle(f64),
+    Rect(f64, f64),
+    Named {
+        label: String,
+        sides: u8,
+    },
+}
+
+#[derive(Debug, PartialEq, ToJson, FromJson)]
+#[json(tag = "type")]
+enum Event {
+    Ping,
+    Login { user: String },
+    Moved(Address),
+}
+
+#[derive(Debug, PartialEq, ToJson, FromJson)]
+#[json(tag = "t", content = "c")]
+enum Message {
+    Quit,
+    Text(String),
+    Pos { x: i32, y: i32 },
+}
+
+#[derive(Debug, PartialEq, ToJson, FromJson)]
+#[json(untagged)]
+enum Scalar {
+    Flag(bool),
+    Number(f64),
+    Pair(String, String),
+    Nothing,
+}
+
+// The parser only accepts arrays and objects at the top level, so scalars are wrapped
+fn parse_any(json: &str) -> JsonValue {
+    parse(&format!("[{}]", json)).unwrap()[0].clone()
+}
+
+fn roundtrip<T: ToJson + FromJson + PartialEq + std::fmt::Debug>(value: T, expected: &str) {
+    let json = value.to_json();
+    assert_eq!(stringify(&json), expected);
+    assert_eq!(T::from_json(&parse_any(expected)).unwrap(), value);
+}
+
+#[test]
+fn test_derive_named_struct() {
+    let user = User {
+        name: "ann".to_string(),
+        age: 30,
+        tags: vec!["a".to_string()],
+        cache: None,
+        address: Address {
+            city: "Pune".to_string(),
+            zip: None,
+        },
+        nickname: None,
+    };
+    roundtrip(
+        user,
+        r#"{"address":{"city":"Pune","zip":null},"age":30,"nickname":null,"tags":["a"],"userName":"ann"}"#,
+    );
+}
+
+#[test]
+fn test_derive_missing_and_default_fields() {
+    let user = User::from_json(
+        &parse(r#"{"userName":"bo","age":4,"address":{"city":"X"},"cache":9}"#).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(user.tags, Vec::<String>::new());
+    assert_eq!(user.cache, None);
+    assert_eq!(user.nickname, None);
+    assert_eq!(user.address.zip, None);
+
+    let err = User::from_json(&parse(r#"{"userName":"bo","address":{"city":"X"}}"#).unwrap())
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Missing field 'age'");
+}
+
+#[test]
+fn test_derive_error_path() {
+    let err = User::from_json(
+        &parse(r#"{"userName":"bo","age":4,"tags":["a",7],"address":{"city":"X"}}"#).unwrap(),
+    )
+    .unwrap_err();
+    assert_eq!(err.path(), "/tags/1");
+    assert_eq!(err.to_string(), "Expected string, found number at /tags/1");
+
+    let err =
+        User::from_json(&parse(r#"{"userName":"bo","age":4,"address":{"city":false}}"#).unwrap())
+            .unwrap_err();
+    assert_eq!(err.path(), "/address/city");
+}
+
+#[test]
+fn test_derive_tuple_unit_and_generic_structs() {
+    roundtrip(Meters(2.5), "2.5");
+    roundtrip(Point(1, 2), "[1,2]");
+    roundtrip(Marker, "null");
+    roundtrip(
+        Wrapper {
+            items: vec![Point(0, 1)],
+        },
+        r#"{"items":[[0,1]]}"#,
+    );
+
+    let err = Point::from_json(&parse("[1]").unwrap()).unwrap_err();
+    assert_eq!(err.message(), "Expected array of length 2, found length 1");
+}
+
+#[test]
+fn test_derive_flatten() {
+    roundtrip(
+        Located {
+            id: 1,
+            address: Address {
+                city: "Oslo".to_string(),
+                zip: Some("0150".to_string()),
+            },
+        },
+        r#"{"city":"Oslo","id":1,"zip":"0150"}"#,
+    );
+}
+
+#[test]
+fn test_derive_externally_tagged_enum() {
+    roundtrip(Shape::Empty, r#""Empty""#);
+    roundtrip(Shape::Circle(1.5), r#"{"circle":1.5}"#);
+    roundtrip(Shape::Rect(1.0, 2.0), r#"{"Rect":[1,2]}"#);
+    roundtrip(
+        Shape::Named {
+            label: "tri".to_string(),
+            sides: 3,
+        },
+        r#"{"Named":{"label":"tri","sides":3}}"#,
+    );
+
+    let err =
+        Shape::from_json(&parse(r#"{"Named":{"label":"tri","sides":"3"}}"#).unwrap()).unwrap_err();
+    assert_eq!(err.path(), "/Named/sides");
+    let err = Shape::from_json(&parse_any(r#""Square""#)).unwrap_err();
+    assert_eq!(err.message(), "Unknown variant 'Square'");
+}
+
+#[test]
+fn test_derive_internally_tagged_enum() {
+    roundtrip(Event::Ping, r#"{"type":"Ping"}"#);
+    roundtrip(
+        Event::Login {
+            user: "u".to_string(),
+        },
+        r#"{"type":"Login","user":"u"}"#,
+    );
+    roundtrip(
+        Event::Moved(Address {
+            city: "Rome".to_string(),
+            zip: None,
+        }),
+        r#"{"city":"Rome","type":"Moved","zip":null}"#,
+    );
+
+    let err = Event::from_json(&parse(r#"{"user":"u"}"#).unwrap()).unwrap_err();
+    assert_eq!(err.message(), "Missing field 'type'");
+}
+
+#[test]
+fn test_derive_adjacently_tagged_enum() {
+    roundtrip(Message::Quit, r#"{"t":"Quit"}"#);
+    roundtrip(Message::Text("hi".to_string()), r#"{"c":"hi","t":"Text"}"#);
+    roundtrip(
+        Message::Pos { x: 1, y: 2 },
+        r#"{"c":{"x":1,"y":2},"t":"Pos"}"#,
+    );
+
+    let err = Message::from_json(&parse(r#"{"t":"Pos","c":{"x":1}}"#).unwrap()).unwrap_err();
+    assert_eq!(err.to_string(), "Missing field 'y' at /c");
+}
+
+#[test]
+fn test_derive_untagged_enum() {
+    roundtrip(Scalar::Flag(true), "true");
+    roundtrip(Scalar::Number(3.0), "3");
+    roundtrip(
+        Scalar::Pair("a".to_string(), "b".to_string()),
+        r#"["a","b"]"#,
+    );
+    roundtrip(Scalar::Nothing, "null");
+
+    assert!(Scalar::from_json(&parse(r#"{"a":1}"#).unwrap()).is_err());
+}
+
+#[test]
+fn test_derive_nested_collections() {
+    let mut map = HashMap::new();
+    map.insert("origin".to_string(), Point(0, 0));
+    let json = map.to_json();
+    assert_eq!(stringify(&json), r#"{"origin":[0,0]}"#);
+    let back: HashMap<String, Point> = FromJson::from_json(&json).unwrap();
+    assert_eq!(back, map);
+}
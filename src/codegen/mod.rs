@@ -0,0 +1,3 @@
+pub mod rust_generator;
+pub mod type_model;
+pub mod typescript_generator;
@@ -0,0 +1,203 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::codegen::type_model::{FieldType, TypeModel, snake_case};
+use crate::core::json_value::JsonValue;
+
+const KEYWORDS: [&str; 48] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+// Keywords that cannot be written as raw identifiers
+const RESERVED: [&str; 4] = ["crate", "self", "Self", "super"];
+
+// The crates providing the derives that need importing
+const DERIVE_CRATES: [(&str, &str); 4] = [
+    ("FromJson", "parse_light"),
+    ("ToJson", "parse_light"),
+    ("Deserialize", "serde"),
+    ("Serialize", "serde"),
+];
+
+/// Renders a `TypeModel` as Rust struct definitions.
+///
+/// Optional or nullable members become `Option<T>`, members of mixed types
+/// become `JsonValue`, and keys that are not valid snake_case identifiers get
+/// a rename attribute. Keys that map to the same identifier are told apart
+/// with a `_2`, `_3`, ... suffix. The output starts with the `use` lines for
+/// `JsonValue` and the derives of this crate and serde; any other derive
+/// must already be in scope.
+#[derive(Debug, Clone)]
+pub struct RustGenerator {
+    derives: Vec<String>,
+    attribute: String,
+}
+
+impl Default for RustGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RustGenerator {
+    /// Derives `Debug, Clone, PartialEq, FromJson, ToJson` and renames with
+    /// `#[json(rename = "...")]`.
+    pub fn new() -> Self {
+        Self {
+            derives: ["Debug", "Clone", "PartialEq", "FromJson", "ToJson"]
+                .map(String::from)
+                .to_vec(),
+            attribute: "json".to_string(),
+        }
+    }
+
+    pub fn with_derives(mut self, derives: &[&str]) -> Self {
+        self.derives = derives.iter().map(|d| d.to_string()).collect();
+        self
+    }
+
+    /// The helper attribute used for renames, e.g. `"serde"` when deriving
+    /// serde's traits instead of this crate's.
+    pub fn with_attribute(mut self, attribute: &str) -> Self {
+        self.attribute = attribute.to_string();
+        self
+    }
+
+    pub fn generate(&self, model: &TypeModel) -> String {
+        let mut blocks = Vec::new();
+        let imports = self.imports(model);
+        if !imports.is_empty() {
+            blocks.push(imports);
+        }
+
+        if model.root() != &FieldType::Named(model.root_name().to_string()) {
+            blocks.push(format!(
+                "pub type {} = {};\n",
+                model.root_name(),
+                self.type_name(model.root())
+            ));
+        }
+
+        for definition in model.types() {
+            let mut block = String::new();
+            if !self.derives.is_empty() {
+                block.push_str(&format!("#[derive({})]\n", self.derives.join(", ")));
+            }
+            block.push_str(&format!("pub struct {} {{\n", definition.name()));
+            let mut used = HashSet::new();
+            for field in definition.fields() {
+                let (ident, renamed) = unique_ident(field.key(), &mut used);
+                if renamed {
+                    block.push_str(&format!(
+                        "    #[{}(rename = {})]\n",
+                        self.attribute,
+                        JsonValue::String(field.key().to_string()).stringify()
+                    ));
+                }
+                let mut type_name = self.type_name(field.field_type());
+                if field.is_optional() && !is_option(field.field_type()) {
+                    type_name = format!("Option<{}>", type_name);
+                }
+                block.push_str(&format!("    pub {}: {},\n", ident, type_name));
+            }
+            block.push_str("}\n");
+            blocks.push(block);
+        }
+
+        blocks.join("\n")
+    }
+
+    fn imports(&self, model: &TypeModel) -> String {
+        let mut lines = Vec::new();
+        let uses_json_value = uses_json_value(model.root())
+            || model
+                .types()
+                .iter()
+                .flat_map(|definition| definition.fields())
+                .any(|field| uses_json_value(field.field_type()));
+        if uses_json_value {
+            lines.push("use parse_light::core::json_value::JsonValue;\n".to_string());
+        }
+
+        let mut by_crate: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for (derive, krate) in DERIVE_CRATES {
+            if self.derives.iter().any(|d| d == derive) {
+                by_crate.entry(krate).or_default().push(derive);
+            }
+        }
+        for (krate, derives) in by_crate {
+            lines.push(match derives.as_slice() {
+                [derive] => format!("use {}::{};\n", krate, derive),
+                _ => format!("use {}::{{{}}};\n", krate, derives.join(", ")),
+            });
+        }
+        lines.concat()
+    }
+
+    fn type_name(&self, field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::Any | FieldType::Union(_) => "JsonValue".to_string(),
+            FieldType::Null => "Option<JsonValue>".to_string(),
+            FieldType::Boolean => "bool".to_string(),
+            FieldType::Integer => "i64".to_string(),
+            FieldType::Number => "f64".to_string(),
+            FieldType::String | FieldType::StringEnum(_) => "String".to_string(),
+            FieldType::Array(items) => format!("Vec<{}>", self.type_name(items)),
+            FieldType::Named(name) => name.clone(),
+            // JsonValue already represents null
+            FieldType::Nullable(inner) if matches!(**inner, FieldType::Union(_)) => {
+                "JsonValue".to_string()
+            }
+            FieldType::Nullable(inner) => format!("Option<{}>", self.type_name(inner)),
+        }
+    }
+}
+
+/// Types that already accept a missing member without another `Option`.
+fn is_option(field_type: &FieldType) -> bool {
+    matches!(field_type, FieldType::Null | FieldType::Nullable(_))
+}
+
+/// Whether the Rust type for `field_type` refers to `JsonValue`.
+fn uses_json_value(field_type: &FieldType) -> bool {
+    match field_type {
+        FieldType::Any | FieldType::Union(_) | FieldType::Null => true,
+        FieldType::Array(inner) | FieldType::Nullable(inner) => uses_json_value(inner),
+        _ => false,
+    }
+}
+
+/// Like `field_ident`, but suffixed with `_2`, `_3`, ... when an earlier
+/// field of the same struct already took the identifier.
+fn unique_ident(key: &str, used: &mut HashSet<String>) -> (String, bool) {
+    let (ident, renamed) = field_ident(key);
+    if used.insert(ident.clone()) {
+        return (ident, renamed);
+    }
+    let base = ident.trim_start_matches("r#");
+    let mut n = 2;
+    loop {
+        let candidate = format!("{}_{}", base, n);
+        if used.insert(candidate.clone()) {
+            return (candidate, true);
+        }
+        n += 1;
+    }
+}
+
+/// The Rust identifier for a key and whether it needs a rename attribute.
+fn field_ident(key: &str) -> (String, bool) {
+    let name = snake_case(key);
+    let renamed = name != key;
+    if KEYWORDS.contains(&name.as_str()) {
+        // derives strip the `r#` prefix, so no rename is needed when the key matches
+        (format!("r#{}", name), renamed)
+    } else if RESERVED.contains(&name.as_str()) {
+        (format!("{}_", name), true)
+    } else {
+        (name, renamed)
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+
+use crate::core::json_value::JsonValue;
+use crate::schema::infer::infer_schema;
+
+/// The type of a single field, independent of the target language.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldType {
+    /// Values of different or unknown shapes.
+    Any,
+    /// Only `null` was ever seen.
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    /// A string limited to a few known values.
+    StringEnum(Vec<String>),
+    Array(Box<FieldType>),
+    /// A reference to a `TypeDef` by name.
+    Named(String),
+    Nullable(Box<FieldType>),
+    Union(Vec<FieldType>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    key: String,
+    field_type: FieldType,
+    optional: bool,
+}
+
+impl Field {
+    /// The member name as it appears in the JSON documents.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn field_type(&self) -> &FieldType {
+        &self.field_type
+    }
+
+    /// Whether the member was absent from some of the sampled objects.
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+}
+
+/// An object shape that becomes a struct or interface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeDef {
+    name: String,
+    fields: Vec<Field>,
+}
+
+impl TypeDef {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+}
+
+/// Named object types collected from an inferred schema, ready to be rendered
+/// by a language generator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeModel {
+    root_name: String,
+    root: FieldType,
+    types: Vec<TypeDef>,
+}
+
+impl TypeModel {
+    /// Builds a model from sample documents via `infer_schema`.
+    pub fn from_samples<'a>(root_name: &str, samples: impl Iterator<Item = &'a JsonValue>) -> Self {
+        Self::from_schema(root_name, &infer_schema(samples))
+    }
+
+    /// Builds a model from a schema using `type`, `properties`, `required`,
+    /// `items` and string `enum` keywords; anything else is ignored.
+    pub fn from_schema(root_name: &str, schema: &JsonValue) -> Self {
+        let root_name = pascal_case(root_name);
+        let mut model = Self {
+            root_name: root_name.clone(),
+            root: FieldType::Any,
+            types: Vec::new(),
+        };
+        model.root = model.field_type(&root_name, schema);
+        model
+    }
+
+    pub fn root_name(&self) -> &str {
+        &self.root_name
+    }
+
+    /// The type of the whole document. When this is not `Named(root_name)`
+    /// generators emit an alias so the root name is always defined.
+    pub fn root(&self) -> &FieldType {
+        &self.root
+    }
+
+    /// Object types in definition order, outermost first.
+    pub fn types(&self) -> &[TypeDef] {
+        &self.types
+    }
+
+    fn field_type(&mut self, name: &str, schema: &JsonValue) -> FieldType {
+        let mut types: Vec<&str> = match schema.as_object().and_then(|map| map.get("type")) {
+            Some(JsonValue::String(name)) => vec![name.as_str()],
+            Some(JsonValue::Array(names)) => names.iter().filter_map(JsonValue::as_str).collect(),
+            _ => Vec::new(),
+        };
+        let nullable = types.contains(&"null");
+        types.retain(|t| *t != "null");
+
+        let field_type = match types.as_slice() {
+            [] if nullable => return FieldType::Null,
+            [] => FieldType::Any,
+            [single] => self.single_type(name, single, schema),
+            several => FieldType::Union(
+                several
+                    .iter()
+                    .map(|t| self.single_type(name, t, schema))
+                    .collect(),
+            ),
+        };
+        if nullable {
+            FieldType::Nullable(Box::new(field_type))
+        } else {
+            field_type
+        }
+    }
+
+    fn single_type(&mut self, name: &str, type_name: &str, schema: &JsonValue) -> FieldType {
+        match type_name {
+            "boolean" => FieldType::Boolean,
+            "integer" => FieldType::Integer,
+            "number" => FieldType::Number,
+            "string" => match schema["enum"].as_array() {
+                Some(values) => FieldType::StringEnum(
+                    values
+                        .iter()
+                        .filter_map(|v| v.as_str())
+                        .map(String::from)
+                        .collect(),
+                ),
+                None => FieldType::String,
+            },
+            "array" => {
+                let items = match &schema["items"] {
+                    JsonValue::Null => FieldType::Any,
+                    items => self.field_type(&item_name(name), items),
+                };
+                FieldType::Array(Box::new(items))
+            }
+            "object" => FieldType::Named(self.define(name, schema)),
+            _ => FieldType::Any,
+        }
+    }
+
+    /// Registers an object type and returns its final name. Identical shapes
+    /// share one definition; different shapes with the same name get a numeric
+    /// suffix.
+    fn define(&mut self, name: &str, schema: &JsonValue) -> String {
+        let required: Vec<&str> = schema["required"]
+            .as_array()
+            .map(|keys| keys.iter().filter_map(|k| k.as_str()).collect())
+            .unwrap_or_default();
+        let empty = HashMap::new();
+        let properties = schema["properties"].as_object().unwrap_or(&empty);
+        let mut keys: Vec<&String> = properties.keys().collect();
+        keys.sort();
+
+        // reserve the slot first so outer types are listed before inner ones
+        let index = self.types.len();
+        self.types.push(TypeDef {
+            name: String::new(),
+            fields: Vec::new(),
+        });
+
+        let fields = keys
+            .into_iter()
+            .map(|key| Field {
+                key: key.clone(),
+                field_type: self.field_type(&pascal_case(key), &properties[key]),
+                optional: !required.contains(&key.as_str()),
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(existing) = self
+            .types
+            .iter()
+            .find(|t| t.fields == fields && !t.name.is_empty())
+        {
+            let existing = existing.name.clone();
+            self.types.remove(index);
+            return existing;
+        }
+
+        let mut unique = name.to_string();
+        let mut suffix = 2;
+        while self.types.iter().any(|t| t.name == unique) {
+            unique = format!("{}{}", name, suffix);
+            suffix += 1;
+        }
+        self.types[index] = TypeDef {
+            name: unique.clone(),
+            fields,
+        };
+        unique
+    }
+}
+
+/// Converts a JSON key to an `UpperCamelCase` type name.
+pub fn pascal_case(key: &str) -> String {
+    let name: String = words(key)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => {
+                    first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase()
+                }
+                None => String::new(),
+            }
+        })
+        .collect();
+    match name.chars().next() {
+        None => "Item".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("T{}", name),
+        Some(_) => name,
+    }
+}
+
+/// Converts a JSON key to a `snake_case` field name.
+pub fn snake_case(key: &str) -> String {
+    let name = words(key)
+        .iter()
+        .map(|word| word.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    match name.chars().next() {
+        None => "field".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("field_{}", name),
+        Some(_) => name,
+    }
+}
+
+/// Splits a key into ASCII alphanumeric words at separators and at
+/// lower-to-upper case boundaries (`"flightNumber"` -> `flight`, `Number`).
+fn words(key: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in key.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && previous_lower {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Names the element type of an array from the array's own name, e.g.
+/// `Flights` -> `Flight`, `Categories` -> `Category`, `Data` -> `DataItem`.
+fn item_name(name: &str) -> String {
+    if let Some(stem) = name.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if name.ends_with("ss") || !name.ends_with('s') || name.len() == 1 {
+        format!("{}Item", name)
+    } else {
+        name[..name.len() - 1].to_string()
+    }
+}
@@ -0,0 +1,97 @@
+use crate::codegen::type_model::{FieldType, TypeModel};
+use crate::core::json_value::JsonValue;
+
+/// Renders a `TypeModel` as TypeScript interfaces.
+///
+/// Optional members are marked with `?`, nullable ones get `| null`, and
+/// enum-like strings become unions of string literals.
+#[derive(Debug, Clone)]
+pub struct TypeScriptGenerator {
+    export: bool,
+}
+
+impl Default for TypeScriptGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeScriptGenerator {
+    pub fn new() -> Self {
+        Self { export: true }
+    }
+
+    /// Whether declarations are prefixed with `export`. Enabled by default.
+    pub fn with_export(mut self, export: bool) -> Self {
+        self.export = export;
+        self
+    }
+
+    pub fn generate(&self, model: &TypeModel) -> String {
+        let export = if self.export { "export " } else { "" };
+        let mut blocks = Vec::new();
+
+        if model.root() != &FieldType::Named(model.root_name().to_string()) {
+            blocks.push(format!(
+                "{}type {} = {};\n",
+                export,
+                model.root_name(),
+                type_name(model.root())
+            ));
+        }
+
+        for definition in model.types() {
+            let mut block = format!("{}interface {} {{\n", export, definition.name());
+            for field in definition.fields() {
+                block.push_str(&format!(
+                    "  {}{}: {};\n",
+                    property_name(field.key()),
+                    if field.is_optional() { "?" } else { "" },
+                    type_name(field.field_type())
+                ));
+            }
+            block.push_str("}\n");
+            blocks.push(block);
+        }
+
+        blocks.join("\n")
+    }
+}
+
+fn type_name(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::Any => "unknown".to_string(),
+        FieldType::Null => "null".to_string(),
+        FieldType::Boolean => "boolean".to_string(),
+        FieldType::Integer | FieldType::Number => "number".to_string(),
+        FieldType::String => "string".to_string(),
+        FieldType::StringEnum(values) => values
+            .iter()
+            .map(|value| JsonValue::String(value.clone()).stringify())
+            .collect::<Vec<_>>()
+            .join(" | "),
+        FieldType::Array(items) => match **items {
+            FieldType::StringEnum(_) | FieldType::Nullable(_) | FieldType::Union(_) => {
+                format!("({})[]", type_name(items))
+            }
+            _ => format!("{}[]", type_name(items)),
+        },
+        FieldType::Named(name) => name.clone(),
+        FieldType::Nullable(inner) => format!("{} | null", type_name(inner)),
+        FieldType::Union(types) => types.iter().map(type_name).collect::<Vec<_>>().join(" | "),
+    }
+}
+
+/// Quotes keys that are not valid identifiers.
+fn property_name(key: &str) -> String {
+    let mut chars = key.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        key.to_string()
+    } else {
+        JsonValue::String(key.to_string()).stringify()
+    }
+}
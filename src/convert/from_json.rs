@@ -0,0 +1,301 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::core::{json_pointer::escape_pointer_token, json_value::JsonValue};
+
+/// Error raised by `FromJson`, carrying the JSON Pointer of the value that failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FromJsonError {
+    path: String,
+    message: String,
+}
+
+impl FromJsonError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            path: String::new(),
+            message: message.into(),
+        }
+    }
+
+    pub fn expected(expected: &str, found: &JsonValue) -> Self {
+        Self::new(format!("Expected {}, found {}", expected, type_name(found)))
+    }
+
+    pub fn missing_field(name: &str) -> Self {
+        Self::new(format!("Missing field '{}'", name))
+    }
+
+    /// Prefixes the path with an enclosing object key or array index.
+    pub fn within(mut self, token: &str) -> Self {
+        self.path = format!("/{}{}", escape_pointer_token(token), self.path);
+        self
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl std::fmt::Display for FromJsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{} at {}", self.message, self.path)
+        }
+    }
+}
+
+impl std::error::Error for FromJsonError {}
+
+impl From<FromJsonError> for String {
+    fn from(error: FromJsonError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Builds a Rust value from a `JsonValue`.
+pub trait FromJson: Sized {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError>;
+
+    /// Value to use when an object member is absent. Only `Option` provides one.
+    fn from_missing() -> Option<Self> {
+        None
+    }
+}
+
+pub fn type_name(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "null",
+        JsonValue::Boolean(_) => "boolean",
+        JsonValue::Number(_) => "number",
+        JsonValue::String(_) => "string",
+        JsonValue::Array(_) => "array",
+        JsonValue::Object(_) => "object",
+        JsonValue::Raw(_) => "raw",
+    }
+}
+
+/// Borrows the members of an object value; used by `#[derive(FromJson)]`.
+pub fn from_json_object(value: &JsonValue) -> Result<&HashMap<String, JsonValue>, FromJsonError> {
+    value
+        .as_object()
+        .ok_or_else(|| FromJsonError::expected("object", value))
+}
+
+/// Borrows the items of an array value of exactly `len` items; used by `#[derive(FromJson)]`.
+pub fn from_json_array(value: &JsonValue, len: usize) -> Result<&[JsonValue], FromJsonError> {
+    let items = value
+        .as_array()
+        .ok_or_else(|| FromJsonError::expected("array", value))?;
+    if items.len() != len {
+        return Err(FromJsonError::new(format!(
+            "Expected array of length {}, found length {}",
+            len,
+            items.len()
+        )));
+    }
+    Ok(items)
+}
+
+/// Reads a required member of an object; used by `#[derive(FromJson)]`.
+pub fn from_json_field<T: FromJson>(
+    object: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<T, FromJsonError> {
+    match object.get(key) {
+        Some(value) => T::from_json(value).map_err(|e| e.within(key)),
+        None => T::from_missing().ok_or_else(|| FromJsonError::missing_field(key)),
+    }
+}
+
+/// Reads a member that falls back to `Default` when absent; used by `#[json(default)]`.
+pub fn from_json_field_or_default<T: FromJson + Default>(
+    object: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<T, FromJsonError> {
+    match object.get(key) {
+        Some(value) => T::from_json(value).map_err(|e| e.within(key)),
+        None => Ok(T::default()),
+    }
+}
+
+impl FromJson for JsonValue {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        Ok(value.clone())
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        value
+            .as_bool()
+            .ok_or_else(|| FromJsonError::expected("boolean", value))
+    }
+}
+
+impl FromJson for String {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        value
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| FromJsonError::expected("string", value))
+    }
+}
+
+impl FromJson for char {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        let s = value
+            .as_str()
+            .ok_or_else(|| FromJsonError::expected("single character string", value))?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(FromJsonError::new(format!(
+                "Expected single character string, found \"{}\"",
+                s
+            ))),
+        }
+    }
+}
+
+impl FromJson for f64 {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        value
+            .as_f64()
+            .ok_or_else(|| FromJsonError::expected("number", value))
+    }
+}
+
+impl FromJson for f32 {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        f64::from_json(value).map(|n| n as f32)
+    }
+}
+
+macro_rules! from_json_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl FromJson for $ty {
+                fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+                    let n = value
+                        .as_f64()
+                        .ok_or_else(|| FromJsonError::expected("integer", value))?;
+                    value
+                        .as_i64()
+                        .and_then(|n| <$ty>::try_from(n).ok())
+                        .ok_or_else(|| {
+                            FromJsonError::new(format!(
+                                "Number {} does not fit in {}",
+                                n,
+                                stringify!($ty)
+                            ))
+                        })
+                }
+            }
+        )*
+    };
+}
+
+from_json_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        match value {
+            JsonValue::Null => Ok(None),
+            other => T::from_json(other).map(Some),
+        }
+    }
+
+    fn from_missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl<T: FromJson> FromJson for Box<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        T::from_json(value).map(Box::new)
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        let items = value
+            .as_array()
+            .ok_or_else(|| FromJsonError::expected("array", value))?;
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| T::from_json(item).map_err(|e| e.within(&i.to_string())))
+            .collect()
+    }
+}
+
+impl<T: FromJson> FromJson for HashMap<String, T> {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        let map = value
+            .as_object()
+            .ok_or_else(|| FromJsonError::expected("object", value))?;
+        map.iter()
+            .map(|(k, v)| {
+                T::from_json(v)
+                    .map(|v| (k.clone(), v))
+                    .map_err(|e| e.within(k))
+            })
+            .collect()
+    }
+}
+
+impl<T: FromJson> FromJson for BTreeMap<String, T> {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        let map = value
+            .as_object()
+            .ok_or_else(|| FromJsonError::expected("object", value))?;
+        map.iter()
+            .map(|(k, v)| {
+                T::from_json(v)
+                    .map(|v| (k.clone(), v))
+                    .map_err(|e| e.within(k))
+            })
+            .collect()
+    }
+}
+
+// Tuples are read from arrays of exactly the tuple's length
+macro_rules! from_json_tuple {
+    ($(($len:expr; $($name:ident : $idx:tt),+)),*) => {
+        $(
+            impl<$($name: FromJson),+> FromJson for ($($name,)+) {
+                fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+                    let items = from_json_array(value, $len)?;
+                    Ok(($(
+                        $name::from_json(&items[$idx])
+                            .map_err(|e| e.within(stringify!($idx)))?,
+                    )+))
+                }
+            }
+        )*
+    };
+}
+
+from_json_tuple!(
+    (1; A: 0),
+    (2; A: 0, B: 1),
+    (3; A: 0, B: 1, C: 2),
+    (4; A: 0, B: 1, C: 2, D: 3),
+    (5; A: 0, B: 1, C: 2, D: 3, E: 4),
+    (6; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5)
+);
+
+impl FromJson for () {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        match value {
+            JsonValue::Null => Ok(()),
+            other => Err(FromJsonError::expected("null", other)),
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod from_json;
+pub mod to_json;
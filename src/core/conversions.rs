@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use crate::core::{json_value::JsonValue, raw_value::RawValue};
+
+impl From<bool> for JsonValue {
+    fn from(value: bool) -> Self {
+        JsonValue::Boolean(value)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(value: &str) -> Self {
+        JsonValue::String(value.to_string())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(value: String) -> Self {
+        JsonValue::String(value)
+    }
+}
+
+// Numbers are stored as f64, so 64-bit integers beyond 2^53 lose precision
+macro_rules! from_number {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for JsonValue {
+                fn from(value: $ty) -> Self {
+                    JsonValue::Number(value as f64)
+                }
+            }
+        )*
+    };
+}
+
+from_number!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+impl<T: Into<JsonValue>> From<Vec<T>> for JsonValue {
+    fn from(value: Vec<T>) -> Self {
+        JsonValue::Array(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<JsonValue>> From<HashMap<String, T>> for JsonValue {
+    fn from(value: HashMap<String, T>) -> Self {
+        JsonValue::Object(value.into_iter().map(|(k, v)| (k, v.into())).collect())
+    }
+}
+
+impl From<RawValue> for JsonValue {
+    fn from(value: RawValue) -> Self {
+        JsonValue::Raw(value)
+    }
+}
+
+impl<T: Into<JsonValue>> From<Option<T>> for JsonValue {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => value.into(),
+            None => JsonValue::Null,
+        }
+    }
+}
+
+impl TryFrom<JsonValue> for bool {
+    type Error = String;
+
+    fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+        value
+            .as_bool()
+            .ok_or_else(|| format!("Expected boolean, found {}", value.stringify()))
+    }
+}
+
+impl TryFrom<JsonValue> for String {
+    type Error = String;
+
+    fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+        match value {
+            JsonValue::String(s) => Ok(s),
+            other => Err(format!("Expected string, found {}", other.stringify())),
+        }
+    }
+}
+
+impl TryFrom<JsonValue> for f64 {
+    type Error = String;
+
+    fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+        value
+            .as_f64()
+            .ok_or_else(|| format!("Expected number, found {}", value.stringify()))
+    }
+}
+
+impl TryFrom<JsonValue> for f32 {
+    type Error = String;
+
+    fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+        f64::try_from(value).map(|n| n as f32)
+    }
+}
+
+// Integers must be integral and fit the target type
+macro_rules! try_from_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl TryFrom<JsonValue> for $ty {
+                type Error = String;
+
+                fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+                    value
+                        .as_i64()
+                        .and_then(|n| <$ty>::try_from(n).ok())
+                        .ok_or_else(|| {
+                            format!(
+                                "Expected {}, found {}",
+                                stringify!($ty),
+                                value.stringify()
+                            )
+                        })
+                }
+            }
+        )*
+    };
+}
+
+try_from_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl<T: TryFrom<JsonValue, Error = String>> TryFrom<JsonValue> for Vec<T> {
+    type Error = String;
+
+    fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+        match value {
+            JsonValue::Array(vec) => vec.into_iter().map(T::try_from).collect(),
+            other => Err(format!("Expected array, found {}", other.stringify())),
+        }
+    }
+}
+
+impl<T: TryFrom<JsonValue, Error = String>> TryFrom<JsonValue> for HashMap<String, T> {
+    type Error = String;
+
+    fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+        match value {
+            JsonValue::Object(map) => map
+                .into_iter()
+                .map(|(k, v)| T::try_from(v).map(|v| (k, v)))
+                .collect(),
+            other => Err(format!("Expected object, found {}", other.stringify())),
+        }
+    }
+}
+
+impl<T: TryFrom<JsonValue, Error = String>> TryFrom<JsonValue> for Option<T> {
+    type Error = String;
+
+    fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+        match value {
+            JsonValue::Null => Ok(None),
+            other => T::try_from(other).map(Some),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use crate::core::json_value::JsonValue;
+
+/// Types that can index into a `JsonValue`: `&str`/`String` for object members
+/// and `usize` for array elements.
+pub trait JsonIndex {
+    fn index_into<'a>(&self, value: &'a JsonValue) -> Option<&'a JsonValue>;
+
+    fn index_into_mut<'a>(&self, value: &'a mut JsonValue) -> Option<&'a mut JsonValue>;
+
+    /// Used by `IndexMut`; may create the slot, and panics when it cannot.
+    fn index_or_insert<'a>(&self, value: &'a mut JsonValue) -> &'a mut JsonValue;
+}
+
+impl JsonIndex for usize {
+    fn index_into<'a>(&self, value: &'a JsonValue) -> Option<&'a JsonValue> {
+        match value {
+            JsonValue::Array(vec) => vec.get(*self),
+            _ => None,
+        }
+    }
+
+    fn index_into_mut<'a>(&self, value: &'a mut JsonValue) -> Option<&'a mut JsonValue> {
+        match value {
+            JsonValue::Array(vec) => vec.get_mut(*self),
+            _ => None,
+        }
+    }
+
+    fn index_or_insert<'a>(&self, value: &'a mut JsonValue) -> &'a mut JsonValue {
+        match value {
+            JsonValue::Array(vec) => {
+                let len = vec.len();
+                vec.get_mut(*self)
+                    .unwrap_or_else(|| panic!("cannot index array of length {} with {}", len, self))
+            }
+            _ => panic!("cannot index non-array JSON value with {}", self),
+        }
+    }
+}
+
+impl JsonIndex for str {
+    fn index_into<'a>(&self, value: &'a JsonValue) -> Option<&'a JsonValue> {
+        match value {
+            JsonValue::Object(map) => map.get(self),
+            _ => None,
+        }
+    }
+
+    fn index_into_mut<'a>(&self, value: &'a mut JsonValue) -> Option<&'a mut JsonValue> {
+        match value {
+            JsonValue::Object(map) => map.get_mut(self),
+            _ => None,
+        }
+    }
+
+    // `null` turns into an empty object so nested members can be assigned in one go
+    fn index_or_insert<'a>(&self, value: &'a mut JsonValue) -> &'a mut JsonValue {
+        if let JsonValue::Null = value {
+            *value = JsonValue::Object(HashMap::new());
+        }
+        match value {
+            JsonValue::Object(map) => map.entry(self.to_string()).or_insert(JsonValue::Null),
+            _ => panic!("cannot index non-object JSON value with \"{}\"", self),
+        }
+    }
+}
+
+impl JsonIndex for String {
+    fn index_into<'a>(&self, value: &'a JsonValue) -> Option<&'a JsonValue> {
+        self.as_str().index_into(value)
+    }
+
+    fn index_into_mut<'a>(&self, value: &'a mut JsonValue) -> Option<&'a mut JsonValue> {
+        self.as_str().index_into_mut(value)
+    }
+
+    fn index_or_insert<'a>(&self, value: &'a mut JsonValue) -> &'a mut JsonValue {
+        self.as_str().index_or_insert(value)
+    }
+}
+
+impl<T: JsonIndex + ?Sized> JsonIndex for &T {
+    fn index_into<'a>(&self, value: &'a JsonValue) -> Option<&'a JsonValue> {
+        (**self).index_into(value)
+    }
+
+    fn index_into_mut<'a>(&self, value: &'a mut JsonValue) -> Option<&'a mut JsonValue> {
+        (**self).index_into_mut(value)
+    }
+
+    fn index_or_insert<'a>(&self, value: &'a mut JsonValue) -> &'a mut JsonValue {
+        (**self).index_or_insert(value)
+    }
+}
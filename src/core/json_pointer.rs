@@ -0,0 +1,162 @@
+use crate::core::json_value::JsonValue;
+
+/// Splits an RFC 6901 JSON Pointer into its unescaped reference tokens.
+///
+/// The empty pointer `""` refers to the whole document and yields no tokens.
+pub fn parse_pointer(pointer: &str) -> Result<Vec<String>, String> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !pointer.starts_with('/') {
+        return Err(format!("JSON Pointer must start with '/': {}", pointer));
+    }
+    pointer[1..].split('/').map(unescape_token).collect()
+}
+
+/// Escapes a single reference token (`~` -> `~0`, `/` -> `~1`).
+pub fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+// the pointer to member or element `token` of the value at `parent`
+pub(crate) fn child_path(parent: &str, token: &str) -> String {
+    format!("{}/{}", parent, escape_pointer_token(token))
+}
+
+/// Builds a JSON Pointer from unescaped reference tokens.
+pub fn build_pointer<S: AsRef<str>>(tokens: &[S]) -> String {
+    let mut pointer = String::new();
+    for token in tokens {
+        pointer.push('/');
+        pointer.push_str(&escape_pointer_token(token.as_ref()));
+    }
+    pointer
+}
+
+fn unescape_token(token: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        if c == '~' {
+            match chars.next() {
+                Some('0') => out.push('~'),
+                Some('1') => out.push('/'),
+                Some(other) => return Err(format!("Invalid JSON Pointer escape: ~{}", other)),
+                None => return Err("Incomplete JSON Pointer escape: ~".to_string()),
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    Ok(out)
+}
+
+// Array indices are decimal without leading zeros; "-" (past the end) is handled by callers
+pub(crate) fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+    if !token.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    token.parse::<usize>().ok()
+}
+
+impl JsonValue {
+    /// Looks up a value by JSON Pointer, e.g. `"/flights/0/origin"`.
+    pub fn pointer(&self, pointer: &str) -> Option<&JsonValue> {
+        let tokens = parse_pointer(pointer).ok()?;
+        let mut current = self;
+        for token in &tokens {
+            current = match current {
+                JsonValue::Object(map) => map.get(token)?,
+                JsonValue::Array(vec) => vec.get(parse_index(token)?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    /// Mutable variant of `pointer`.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut JsonValue> {
+        let tokens = parse_pointer(pointer).ok()?;
+        let mut current = self;
+        for token in &tokens {
+            current = match current {
+                JsonValue::Object(map) => map.get_mut(token)?,
+                JsonValue::Array(vec) => vec.get_mut(parse_index(token)?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    /// Inserts `value` at `pointer`, following the RFC 6902 `add` rules.
+    ///
+    /// Object members are created or replaced, array elements are inserted
+    /// before the given index (`-` appends). Returns the replaced value, if any.
+    pub fn insert_at_pointer(
+        &mut self,
+        pointer: &str,
+        value: JsonValue,
+    ) -> Result<Option<JsonValue>, String> {
+        let mut tokens = parse_pointer(pointer)?;
+        let last = match tokens.pop() {
+            Some(last) => last,
+            None => return Ok(Some(std::mem::replace(self, value))),
+        };
+        let parent = self
+            .pointer_mut(&build_pointer(&tokens))
+            .ok_or_else(|| format!("Parent of JSON Pointer does not exist: {}", pointer))?;
+
+        match parent {
+            JsonValue::Object(map) => Ok(map.insert(last, value)),
+            JsonValue::Array(vec) => {
+                if last == "-" {
+                    vec.push(value);
+                    return Ok(None);
+                }
+                let idx = parse_index(&last)
+                    .ok_or_else(|| format!("Invalid array index in JSON Pointer: {}", last))?;
+                if idx > vec.len() {
+                    return Err(format!("Array index out of bounds: {}", idx));
+                }
+                vec.insert(idx, value);
+                Ok(None)
+            }
+            _ => Err(format!(
+                "JSON Pointer parent is not a container: {}",
+                pointer
+            )),
+        }
+    }
+
+    /// Removes and returns the value at `pointer`.
+    pub fn remove_at_pointer(&mut self, pointer: &str) -> Result<JsonValue, String> {
+        let mut tokens = parse_pointer(pointer)?;
+        let last = tokens
+            .pop()
+            .ok_or_else(|| "Cannot remove the document root".to_string())?;
+        let parent = self
+            .pointer_mut(&build_pointer(&tokens))
+            .ok_or_else(|| format!("Parent of JSON Pointer does not exist: {}", pointer))?;
+
+        match parent {
+            JsonValue::Object(map) => map
+                .remove(&last)
+                .ok_or_else(|| format!("No value at JSON Pointer: {}", pointer)),
+            JsonValue::Array(vec) => {
+                let idx = parse_index(&last)
+                    .ok_or_else(|| format!("Invalid array index in JSON Pointer: {}", last))?;
+                if idx >= vec.len() {
+                    return Err(format!("Array index out of bounds: {}", idx));
+                }
+                Ok(vec.remove(idx))
+            }
+            _ => Err(format!(
+                "JSON Pointer parent is not a container: {}",
+                pointer
+            )),
+        }
+    }
+}
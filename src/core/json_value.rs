@@ -1,4 +1,13 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    ops::{Index, IndexMut},
+};
+
+use crate::core::{
+    json_index::JsonIndex, raw_value::RawValue, stringify_options::StringifyOptions,
+};
+
+static NULL: JsonValue = JsonValue::Null;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum JsonValue {
@@ -8,16 +17,127 @@ pub enum JsonValue {
     String(String),
     Array(Vec<JsonValue>),
     Object(HashMap<String, JsonValue>),
+    /// A value kept as source text; see `Parser::parse_with_raw`.
+    Raw(RawValue),
 }
 
 impl JsonValue {
+    pub fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+
+    pub fn is_boolean(&self) -> bool {
+        matches!(self, JsonValue::Boolean(_))
+    }
+
+    pub fn is_number(&self) -> bool {
+        matches!(self, JsonValue::Number(_))
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(self, JsonValue::String(_))
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(self, JsonValue::Array(_))
+    }
+
+    pub fn is_object(&self) -> bool {
+        matches!(self, JsonValue::Object(_))
+    }
+
+    pub fn is_raw(&self) -> bool {
+        matches!(self, JsonValue::Raw(_))
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns the number as an `i64` if it is integral and within range.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            JsonValue::Number(n)
+                if n.fract() == 0.0 && *n >= i64::MIN as f64 && *n < i64::MAX as f64 =>
+            {
+                Some(*n as i64)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(vec) => Some(vec),
+            _ => None,
+        }
+    }
+
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(vec) => Some(vec),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&HashMap<String, JsonValue>> {
+        match self {
+            JsonValue::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    pub fn as_object_mut(&mut self) -> Option<&mut HashMap<String, JsonValue>> {
+        match self {
+            JsonValue::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    pub fn as_raw(&self) -> Option<&RawValue> {
+        match self {
+            JsonValue::Raw(raw) => Some(raw),
+            _ => None,
+        }
+    }
+
+    /// Looks up an object member by key or an array element by index.
+    pub fn get<I: JsonIndex>(&self, index: I) -> Option<&JsonValue> {
+        index.index_into(self)
+    }
+
+    pub fn get_mut<I: JsonIndex>(&mut self, index: I) -> Option<&mut JsonValue> {
+        index.index_into_mut(self)
+    }
+
     pub fn stringify(&self) -> String {
+        self.stringify_with(&StringifyOptions::default())
+    }
+
+    pub fn stringify_with(&self, options: &StringifyOptions) -> String {
         let mut stringer = String::new();
-        self.format_into(&mut stringer);
+        self.format_into(&mut stringer, options);
         stringer
     }
 
-    fn format_into(&self, stringer: &mut String) {
+    fn format_into(&self, stringer: &mut String, options: &StringifyOptions) {
         match self {
             JsonValue::Null => stringer.push_str("null"),
             JsonValue::Boolean(b) => {
@@ -27,19 +147,15 @@ impl JsonValue {
                     stringer.push_str("false");
                 }
             }
-            JsonValue::Number(n) => stringer.push_str(&n.to_string()),
-            JsonValue::String(s) => {
-                stringer.push('"');
-                Self::escape_string(s, stringer);
-                stringer.push('"');
-            }
+            JsonValue::Number(n) => Self::write_number(*n, stringer, options),
+            JsonValue::String(s) => Self::write_string(s, stringer, options),
             JsonValue::Array(arr) => {
                 stringer.push('[');
                 for (i, val) in arr.iter().enumerate() {
                     if i > 0 {
                         stringer.push(',');
                     }
-                    val.format_into(stringer);
+                    val.format_into(stringer, options);
                 }
                 stringer.push(']');
             }
@@ -52,36 +168,114 @@ impl JsonValue {
                     if i > 0 {
                         stringer.push(',');
                     }
-                    stringer.push('"');
-                    Self::escape_string(key, stringer);
-                    stringer.push_str("\":");
+                    Self::write_key(key, stringer, options);
+                    stringer.push(':');
 
                     if let Some(val) = obj.get(*key) {
-                        val.format_into(stringer);
+                        val.format_into(stringer, options);
                     }
                 }
                 stringer.push('}');
             }
+            JsonValue::Raw(raw) if raw.is_verbatim(options) => stringer.push_str(raw.get()),
+            // the text was checked when it was read, so it parses
+            JsonValue::Raw(raw) => match raw.parse_with(raw.options()) {
+                Ok(value) => value.format_into(stringer, options),
+                Err(_) => stringer.push_str(raw.get()),
+            },
+        }
+    }
+
+    pub(crate) fn write_number(n: f64, stringer: &mut String, options: &StringifyOptions) {
+        if options.json5() && n.is_nan() {
+            stringer.push_str("NaN");
+        } else if options.json5() && n.is_infinite() {
+            stringer.push_str(if n > 0.0 { "Infinity" } else { "-Infinity" });
+        } else {
+            stringer.push_str(&n.to_string());
+        }
+    }
+
+    /// Writes a quoted string. JSON5 output prefers single quotes, falling back
+    /// to double quotes when the string contains more `'` than `"`.
+    pub(crate) fn write_string(s: &str, stringer: &mut String, options: &StringifyOptions) {
+        let quote = if options.json5() && s.matches('\'').count() <= s.matches('"').count() {
+            '\''
+        } else {
+            '"'
+        };
+        stringer.push(quote);
+        Self::escape_quoted(s, quote, stringer, options);
+        stringer.push(quote);
+    }
+
+    /// Writes an object key, unquoted in JSON5 output when it is an identifier.
+    pub(crate) fn write_key(key: &str, stringer: &mut String, options: &StringifyOptions) {
+        let mut chars = key.chars();
+        let is_identifier = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+        if options.json5() && is_identifier {
+            stringer.push_str(key);
+        } else {
+            Self::write_string(key, stringer, options);
         }
     }
 
-    fn escape_string(s: &str, stringer: &mut String) {
+    /// Escapes `s` for a string literal delimited by `quote`.
+    fn escape_quoted(s: &str, quote: char, stringer: &mut String, options: &StringifyOptions) {
         for c in s.chars() {
             match c {
                 // standard json escapes
-                '"' => stringer.push_str("\\\""),
+                _ if c == quote => {
+                    stringer.push('\\');
+                    stringer.push(quote);
+                }
                 '\\' => stringer.push_str("\\\\"),
                 '\x08' => stringer.push_str("\\b"),
                 '\x0c' => stringer.push_str("\\f"),
                 '\n' => stringer.push_str("\\n"),
                 '\r' => stringer.push_str("\\r"),
                 '\t' => stringer.push_str("\\t"),
+                '/' if options.escape_slash() => stringer.push_str("\\/"),
+                '<' | '>' | '&' | '\u{2028}' | '\u{2029}' if options.html_safe() => {
+                    Self::push_unicode_escape(c, stringer);
+                }
                 _ if c.is_control() => {
                     // fall back to \uXXXX loop for other control characters
-                    stringer.push_str(&format!("\\u{:04x}", c as u32));
+                    Self::push_unicode_escape(c, stringer);
+                }
+                _ if !c.is_ascii() && options.ensure_ascii() => {
+                    Self::push_unicode_escape(c, stringer);
                 }
                 _ => stringer.push(c),
             }
         }
     }
+
+    // writes one \uXXXX escape per utf-16 unit, so non-BMP chars become a surrogate pair
+    fn push_unicode_escape(c: char, stringer: &mut String) {
+        let mut units = [0u16; 2];
+        for unit in c.encode_utf16(&mut units) {
+            stringer.push_str(&format!("\\u{:04x}", unit));
+        }
+    }
+}
+
+/// Missing members and out-of-range indices yield `JsonValue::Null`.
+impl<I: JsonIndex> Index<I> for JsonValue {
+    type Output = JsonValue;
+
+    fn index(&self, index: I) -> &JsonValue {
+        index.index_into(self).unwrap_or(&NULL)
+    }
+}
+
+/// String indices insert missing members (turning `null` into an object);
+/// panics on out-of-range array indices or a type mismatch.
+impl<I: JsonIndex> IndexMut<I> for JsonValue {
+    fn index_mut(&mut self, index: I) -> &mut JsonValue {
+        index.index_or_insert(self)
+    }
 }
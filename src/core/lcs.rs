@@ -0,0 +1,18 @@
+use crate::core::json_value::JsonValue;
+
+/// `lengths[i][j]` is the length of the longest common subsequence of
+/// `a[i..]` and `b[j..]`.
+pub(crate) fn lcs_lengths(a: &[JsonValue], b: &[JsonValue]) -> Vec<Vec<usize>> {
+    let (n, m) = (a.len(), b.len());
+    let mut lengths = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if a[i] == b[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+    lengths
+}
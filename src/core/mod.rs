@@ -1,4 +1,12 @@
+pub mod conversions;
+pub mod json_index;
+pub mod json_pointer;
 pub mod json_value;
+pub mod lcs;
+pub mod parse_options;
+pub mod raw_value;
+pub mod span;
+pub mod stringify_options;
 pub mod token;
 pub mod tokentype;
 pub mod tokentyperelationships;
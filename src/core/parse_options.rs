@@ -0,0 +1,50 @@
+/// Controls which extensions to strict JSON the tokenizer accepts.
+///
+/// The default options accept only standard JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    allow_comments: bool,
+    json5: bool,
+    emit_trivia: bool,
+}
+
+impl ParseOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Treat `//` line comments and `/* */` block comments as whitespace,
+    /// as in JSONC files such as `tsconfig.json`.
+    pub fn with_allow_comments(mut self, allow_comments: bool) -> Self {
+        self.allow_comments = allow_comments;
+        self
+    }
+
+    /// Accept JSON5: unquoted identifier keys, single-quoted and multi-line
+    /// strings, trailing commas, hexadecimal numbers, leading or trailing
+    /// decimal points, an explicit `+` sign, `Infinity`, `NaN` and comments.
+    pub fn with_json5(mut self, json5: bool) -> Self {
+        self.json5 = json5;
+        self
+    }
+
+    /// Emit whitespace and comments as tokens instead of skipping them, so a
+    /// concrete syntax tree can reproduce the input exactly. The parser
+    /// ignores these tokens.
+    pub fn with_emit_trivia(mut self, emit_trivia: bool) -> Self {
+        self.emit_trivia = emit_trivia;
+        self
+    }
+
+    pub fn allow_comments(&self) -> bool {
+        self.allow_comments
+    }
+
+    pub fn json5(&self) -> bool {
+        self.json5
+    }
+
+    pub fn emit_trivia(&self) -> bool {
+        self.emit_trivia
+    }
+}
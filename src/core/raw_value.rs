@@ -0,0 +1,80 @@
+use std::fmt;
+
+use crate::{
+    core::{
+        json_value::JsonValue, parse_options::ParseOptions, stringify_options::StringifyOptions,
+    },
+    lexer::tokenizer::Tokenizer,
+    parser::parser::Parser,
+};
+
+/// The source text of a JSON value that has been checked but not parsed.
+///
+/// `Parser::parse_with_raw` keeps the subtrees it is asked to as raw values,
+/// so they cost no allocations beyond their text and `stringify` writes them
+/// back exactly as they appeared, number spelling and whitespace included.
+/// Text read as JSON5 or with comments, or written with escaping options, is
+/// parsed and written out again instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawValue {
+    json: String,
+    // the dialect the text was checked in
+    options: ParseOptions,
+}
+
+impl RawValue {
+    /// Checks that `json` is a single valid JSON value and wraps it.
+    pub fn from_string(json: String) -> Result<Self, String> {
+        Parser::new(Tokenizer::new(Some(json.clone()), None))?.skip_document()?;
+        Ok(Self {
+            json,
+            options: ParseOptions::default(),
+        })
+    }
+
+    /// Wraps text the parser has already checked with `options`.
+    pub(crate) fn from_validated(json: String, options: ParseOptions) -> Self {
+        Self { json, options }
+    }
+
+    /// The text as it appeared in the source.
+    pub fn get(&self) -> &str {
+        &self.json
+    }
+
+    /// The options the text was checked with, e.g. JSON5 when the document
+    /// was parsed that way.
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    /// Whether the text can be written as it is under `options`: it is strict
+    /// JSON and nothing needs escaping beyond what it already has.
+    pub(crate) fn is_verbatim(&self, options: &StringifyOptions) -> bool {
+        !self.options.json5()
+            && !self.options.allow_comments()
+            && !options.ensure_ascii()
+            && !options.html_safe()
+            && !options.escape_slash()
+    }
+
+    pub fn into_string(self) -> String {
+        self.json
+    }
+
+    pub fn parse(&self) -> Result<JsonValue, String> {
+        self.parse_with(&ParseOptions::default())
+    }
+
+    /// Parses the text, e.g. with JSON5 enabled when it was read that way.
+    pub fn parse_with(&self, options: &ParseOptions) -> Result<JsonValue, String> {
+        let tokenizer = Tokenizer::new(Some(self.json.clone()), None).with_options(*options);
+        Parser::new(tokenizer)?.parse()
+    }
+}
+
+impl fmt::Display for RawValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.json)
+    }
+}
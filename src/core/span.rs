@@ -0,0 +1,112 @@
+/// A point in the source text.
+///
+/// `offset` counts bytes from the start of the input; `line` and `column` are
+/// 1-based, with columns counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Position {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self::new(0, 1, 1)
+    }
+}
+
+impl Position {
+    pub fn new(offset: usize, line: usize, column: usize) -> Self {
+        Self {
+            offset,
+            line,
+            column,
+        }
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+/// The source range covered by a token; `end` is exclusive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    start: usize,
+    end: usize,
+    start_line: usize,
+    start_col: usize,
+    end_line: usize,
+    end_col: usize,
+}
+
+impl Span {
+    pub fn new(start: Position, end: Position) -> Self {
+        Self {
+            start: start.offset(),
+            end: end.offset(),
+            start_line: start.line(),
+            start_col: start.column(),
+            end_line: end.line(),
+            end_col: end.column(),
+        }
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    pub fn start_line(&self) -> usize {
+        self.start_line
+    }
+
+    pub fn start_col(&self) -> usize {
+        self.start_col
+    }
+
+    pub fn end_line(&self) -> usize {
+        self.end_line
+    }
+
+    pub fn end_col(&self) -> usize {
+        self.end_col
+    }
+
+    pub fn start_position(&self) -> Position {
+        Position::new(self.start, self.start_line, self.start_col)
+    }
+
+    pub fn end_position(&self) -> Position {
+        Position::new(self.end, self.end_line, self.end_col)
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}-{}:{}",
+            self.start_line, self.start_col, self.end_line, self.end_col
+        )
+    }
+}
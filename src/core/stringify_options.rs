@@ -0,0 +1,61 @@
+/// Controls how `JsonValue::stringify_with` escapes strings.
+///
+/// The default options match `JsonValue::stringify`: only the characters JSON
+/// requires are escaped and all other Unicode is written verbatim.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StringifyOptions {
+    ensure_ascii: bool,
+    html_safe: bool,
+    escape_slash: bool,
+    json5: bool,
+}
+
+impl StringifyOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Escape every character above 0x7F as `\uXXXX`, using surrogate pairs
+    /// for characters outside the Basic Multilingual Plane.
+    pub fn with_ensure_ascii(mut self, ensure_ascii: bool) -> Self {
+        self.ensure_ascii = ensure_ascii;
+        self
+    }
+
+    /// Escape `<`, `>`, `&`, U+2028 and U+2029 so the output can be embedded
+    /// in a `<script>` tag.
+    pub fn with_html_safe(mut self, html_safe: bool) -> Self {
+        self.html_safe = html_safe;
+        self
+    }
+
+    /// Escape `/` as `\/`.
+    pub fn with_escape_slash(mut self, escape_slash: bool) -> Self {
+        self.escape_slash = escape_slash;
+        self
+    }
+
+    /// Write JSON5 for human-edited files: identifier keys are left unquoted,
+    /// strings use single quotes unless that needs more escaping, and
+    /// non-finite numbers are written as `Infinity`, `-Infinity` or `NaN`.
+    pub fn with_json5(mut self, json5: bool) -> Self {
+        self.json5 = json5;
+        self
+    }
+
+    pub fn ensure_ascii(&self) -> bool {
+        self.ensure_ascii
+    }
+
+    pub fn html_safe(&self) -> bool {
+        self.html_safe
+    }
+
+    pub fn escape_slash(&self) -> bool {
+        self.escape_slash
+    }
+
+    pub fn json5(&self) -> bool {
+        self.json5
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::tokentype::TokenType;
+use crate::core::{span::Span, tokentype::TokenType};
 
 #[derive(Debug, Clone)]
 pub struct Token {
@@ -7,17 +7,19 @@ pub struct Token {
     start_pos: usize,
     end_pos: Option<usize>,
     token_idx: usize,
+    span: Span,
 }
 
 impl Token {
     pub fn new(token_type: TokenType, start_pos: usize, token_idx: usize) -> Self {
-        return Self {
+        Self {
             token_type,
             value: None,
             start_pos,
             end_pos: None,
             token_idx,
-        };
+            span: Span::default(),
+        }
     }
 
     pub fn token_type(&self) -> TokenType {
@@ -28,19 +30,42 @@ impl Token {
         self.token_idx
     }
 
+    pub fn start_pos(&self) -> usize {
+        self.start_pos
+    }
+
+    /// Byte offset just past the token; set once the tokenizer attaches a span.
+    pub fn end_pos(&self) -> Option<usize> {
+        self.end_pos
+    }
+
+    /// Where the token appears in the source. Tokens built outside the
+    /// tokenizer have an empty default span until `with_span` is called.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.start_pos = span.start();
+        self.end_pos = Some(span.end());
+        self.span = span;
+        self
+    }
+
     pub fn with_value(
         token_type: TokenType,
         start_pos: usize,
         token_idx: usize,
         value: String,
     ) -> Self {
-        return Self {
+        Self {
             token_type,
             value: Some(value),
             start_pos,
             end_pos: None,
             token_idx,
-        };
+            span: Span::default(),
+        }
     }
 
     pub fn get_value_as_string(&self) -> Option<String> {
@@ -50,8 +75,10 @@ impl Token {
     pub fn value(&self) -> Option<&String> {
         self.value.as_ref()
     }
+}
 
-    pub fn to_string(&self) -> String {
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.token_type {
             TokenType::DoubleQuote
             | TokenType::Number
@@ -59,11 +86,11 @@ impl Token {
             | TokenType::Null
             | TokenType::Text => {
                 if let Some(ref val) = self.value {
-                    return val.clone();
+                    return write!(f, "{}", val);
                 }
-                format!("{}", self.token_type)
+                write!(f, "{}", self.token_type)
             }
-            _ => format!("{}", self.token_type),
+            _ => write!(f, "{}", self.token_type),
         }
     }
 }
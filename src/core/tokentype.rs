@@ -20,6 +20,8 @@ pub enum TokenType {
     CarriageReturn = 16,
     Unknown = 17,
     Invalid = 18,
+    WhiteSpace = 19,
+    Comment = 20,
 }
 
 impl std::fmt::Display for TokenType {
@@ -44,6 +46,8 @@ impl std::fmt::Display for TokenType {
             TokenType::CarriageReturn => write!(f, "CarriageReturn"),
             TokenType::Unknown => write!(f, "Unknown"),
             TokenType::Invalid => write!(f, "Invalid"),
+            TokenType::WhiteSpace => write!(f, "WhiteSpace"),
+            TokenType::Comment => write!(f, "Comment"),
         }
     }
 }
@@ -51,7 +55,7 @@ impl std::fmt::Display for TokenType {
 impl TokenType {
     #[inline]
     pub fn get_token_type_from_byte(c: u8) -> TokenType {
-        if (c >= b'0' && c <= b'9') {
+        if c.is_ascii_digit() {
             return TokenType::Number;
         }
         match c {
@@ -73,10 +77,36 @@ impl TokenType {
     }
     #[inline]
     pub fn is_single_byte_token(c: u8) -> bool {
-        return match c {
-            b'{' | b'}' | b'[' | b']' | b'.' | b':' | b',' | b'-' | b'\n' | b'\t' | b'\r' => true,
-            _ => false,
-        };
+        matches!(
+            c,
+            b'{' | b'}' | b'[' | b']' | b'.' | b':' | b',' | b'-' | b'\n' | b'\t' | b'\r'
+        )
+    }
+    /// Whitespace and comments, which are only emitted as tokens when trivia
+    /// is requested.
+    #[inline]
+    pub fn is_trivia(self) -> bool {
+        matches!(
+            self,
+            TokenType::WhiteSpace
+                | TokenType::Tab
+                | TokenType::NewLine
+                | TokenType::CarriageReturn
+                | TokenType::Comment
+        )
+    }
+    /// Tokens that can begin a value: a scalar or an opening bracket.
+    #[inline]
+    pub fn starts_value(self) -> bool {
+        matches!(
+            self,
+            TokenType::LeftBrace
+                | TokenType::LeftSquareBracket
+                | TokenType::Text
+                | TokenType::Number
+                | TokenType::Boolean
+                | TokenType::Null
+        )
     }
     #[inline]
     pub fn is_whitespace(c: u8) -> bool {
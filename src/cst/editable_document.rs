@@ -0,0 +1,595 @@
+use std::fmt;
+
+use crate::{
+    core::{
+        json_pointer::{parse_index, parse_pointer},
+        json_value::JsonValue,
+        parse_options::ParseOptions,
+        stringify_options::StringifyOptions,
+        tokentype::TokenType,
+    },
+    cst::{
+        syntax_node::{NodeKind, SyntaxElement, SyntaxNode, SyntaxToken},
+        syntax_tree::SyntaxTree,
+    },
+    lexer::tokenizer::Tokenizer,
+    parser::parser::Parser,
+};
+
+/// A JSON document that can be edited by JSON Pointer without reformatting
+/// it. Only the values an edit touches are rewritten; whitespace, comments
+/// and the order of everything else stay as they were in the source.
+///
+/// Inserted values copy the document's indentation, line endings and
+/// spacing around `:`. Token spans keep referring to the original source.
+pub struct EditableDocument {
+    tree: SyntaxTree,
+    options: ParseOptions,
+    style: Style,
+}
+
+// layout detected from the source and applied to inserted values
+struct Style {
+    // `None` for documents written on a single line
+    indent_unit: Option<String>,
+    newline: String,
+    // the tokens between a key and its value, e.g. `: `
+    colon: Vec<SyntaxElement>,
+}
+
+impl EditableDocument {
+    pub fn parse(source: &str, options: &ParseOptions) -> Result<Self, String> {
+        let tree = SyntaxTree::parse(source, options)?;
+        let style = Style::detect(tree.root());
+        Ok(Self {
+            tree,
+            options: *options,
+            style,
+        })
+    }
+
+    /// Replaces the value at `pointer`. A missing object member, or the
+    /// array index `-`, is added as with `insert`.
+    pub fn set(&mut self, pointer: &str, value: &JsonValue) -> Result<(), String> {
+        let mut tokens = parse_pointer(pointer)?;
+        if let Ok(path) = self.locate(&tokens, pointer) {
+            let indent = self.line_indent(self.node_at(&path));
+            let node = self.build_value(value, &indent);
+            *self.element_at_mut(&path) = SyntaxElement::Node(node);
+            return Ok(());
+        }
+
+        let last = tokens
+            .pop()
+            .ok_or_else(|| "Document has no value".to_string())?;
+        let parent = self
+            .locate(&tokens, pointer)
+            .map_err(|_| format!("Parent of JSON Pointer does not exist: {}", pointer))?;
+        self.insert_into(&parent, &last, value)
+    }
+
+    /// Adds `key` to the object at `pointer`, after its last member, or
+    /// inserts into the array at `pointer` before index `key` (`-` appends).
+    pub fn insert(&mut self, pointer: &str, key: &str, value: &JsonValue) -> Result<(), String> {
+        let path = self.locate(&parse_pointer(pointer)?, pointer)?;
+        let container = self.node_at(&path);
+        if container.kind() == NodeKind::Object && self.member_index(container, key).is_some() {
+            return Err(format!("Key already exists: {}", key));
+        }
+        self.insert_into(&path, key, value)
+    }
+
+    /// Removes the value at `pointer` together with its separating comma and
+    /// any comment lines directly above it.
+    pub fn remove(&mut self, pointer: &str) -> Result<(), String> {
+        let tokens = parse_pointer(pointer)?;
+        if tokens.is_empty() {
+            return Err("Cannot remove the document root".to_string());
+        }
+        let mut path = self.locate(&tokens, pointer)?;
+        if self.node_at(&path[..path.len() - 1]).kind() == NodeKind::Member {
+            path.pop();
+        }
+        let idx = path.pop().ok_or("Document has no value")?;
+        let children = self.node_at_mut(&path).children_mut();
+
+        let prev = (0..idx)
+            .rev()
+            .find(|&i| !is_trivia(&children[i]))
+            .ok_or("Unbalanced brackets")?;
+        let next = (idx + 1..children.len())
+            .find(|&i| !is_trivia(&children[i]))
+            .ok_or("Unbalanced brackets")?;
+        let first_newline = (prev + 1..idx).find(|&i| is_newline(&children[i]));
+        // trivia on the same line as the removed value goes with it
+        let line_end = |from: usize| {
+            (from..children.len())
+                .find(|&i| !is_trivia(&children[i]) || is_newline(&children[i]))
+                .unwrap_or(children.len())
+        };
+
+        if is_comma(&children[next]) {
+            let end = line_end(next + 1);
+            children.drain(first_newline.unwrap_or(idx)..end);
+        } else if is_comma(&children[prev]) {
+            let end = line_end(idx + 1);
+            match first_newline {
+                Some(newline) => {
+                    children.drain(newline..end);
+                    children.remove(prev);
+                }
+                None => {
+                    children.drain(prev..end);
+                }
+            }
+        } else {
+            // the only element: leave the container empty
+            children.drain(prev + 1..next);
+        }
+        Ok(())
+    }
+
+    /// Renames the object member at `pointer`, keeping its value and
+    /// position.
+    pub fn rename_key(&mut self, pointer: &str, new_key: &str) -> Result<(), String> {
+        let tokens = parse_pointer(pointer)?;
+        if tokens.is_empty() {
+            return Err("Cannot rename the document root".to_string());
+        }
+        let mut path = self.locate(&tokens, pointer)?;
+        path.pop();
+        let member = self.node_at(&path);
+        if member.kind() != NodeKind::Member {
+            return Err(format!(
+                "JSON Pointer does not name an object member: {}",
+                pointer
+            ));
+        }
+        let object = self.node_at(&path[..path.len() - 1]);
+        if self
+            .member_index(object, new_key)
+            .is_some_and(|idx| Some(&idx) != path.last())
+        {
+            return Err(format!("Key already exists: {}", new_key));
+        }
+
+        let old = member.key().map(SyntaxToken::text).unwrap_or_default();
+        let mut text = String::new();
+        if old.starts_with('"') {
+            JsonValue::write_string(new_key, &mut text, &StringifyOptions::default());
+        } else {
+            // JSON5 keys stay unquoted or single-quoted
+            let options = StringifyOptions::new().with_json5(true);
+            JsonValue::write_key(new_key, &mut text, &options);
+        }
+        self.node_at_mut(&path).children_mut()[0] =
+            SyntaxElement::Token(SyntaxToken::new(TokenType::Text, text));
+        Ok(())
+    }
+
+    /// Parses the edited text back into a value.
+    pub fn to_value(&self) -> Result<JsonValue, String> {
+        let tokenizer = Tokenizer::new(Some(self.to_string()), None).with_options(self.options);
+        Parser::new(tokenizer)?.parse()
+    }
+
+    pub fn tree(&self) -> &SyntaxTree {
+        &self.tree
+    }
+
+    fn insert_into(&mut self, path: &[usize], key: &str, value: &JsonValue) -> Result<(), String> {
+        let container = self.node_at(path);
+        let kind = container.kind();
+        let elements: Vec<usize> = (0..container.children().len())
+            .filter(|&i| container.children()[i].as_node().is_some())
+            .collect();
+        let position = match kind {
+            NodeKind::Object => elements.len(),
+            NodeKind::Array if key == "-" => elements.len(),
+            NodeKind::Array => parse_index(key)
+                .filter(|idx| *idx <= elements.len())
+                .ok_or_else(|| format!("Array index out of bounds: {}", key))?,
+            _ => return Err("JSON Pointer parent is not a container".to_string()),
+        };
+        let container_indent = self.line_indent(container);
+
+        // copy the line break and indentation in front of a neighbour
+        // (the first element has none before it, so use the second's)
+        let neighbour = elements.get(position.max(1)).or(elements.last()).copied();
+        let separator = match neighbour {
+            // a sole element follows the bracket rather than a comma, so its
+            // spacing only carries over when it starts a new line
+            Some(idx) if elements.len() == 1 => {
+                let separator = self.separator(container.children(), idx);
+                if separator.iter().any(is_newline) {
+                    separator
+                } else {
+                    self.inline_separator()
+                }
+            }
+            Some(idx) => self.separator(container.children(), idx),
+            None => match &self.style.indent_unit {
+                Some(unit) => whitespace(&format!(
+                    "{}{}{}",
+                    self.style.newline, container_indent, unit
+                )),
+                None => Vec::new(),
+            },
+        };
+        let indent = match separator.iter().rposition(is_newline) {
+            Some(newline) => separator[newline + 1..]
+                .iter()
+                .filter_map(SyntaxElement::as_token)
+                .map(SyntaxToken::text)
+                .collect(),
+            None => container_indent.clone(),
+        };
+        let node = SyntaxElement::Node(match kind {
+            NodeKind::Object => self.build_member(key, value, &indent),
+            _ => self.build_value(value, &indent),
+        });
+        let comma = || SyntaxElement::Token(SyntaxToken::new(TokenType::Comma, ","));
+        let closing = whitespace(&format!("{}{}", self.style.newline, container_indent));
+
+        let children = self.node_at_mut(path).children_mut();
+        let (at, inserted) = if elements.is_empty() {
+            let mut inserted = separator;
+            inserted.push(node);
+            if !inserted.is_empty() && is_newline(&inserted[0]) {
+                inserted.extend(closing);
+            }
+            (1, inserted)
+        } else if position < elements.len() {
+            let mut inserted = vec![node, comma()];
+            inserted.extend(separator);
+            (elements[position], inserted)
+        } else {
+            let last = elements[elements.len() - 1];
+            let next = (last + 1..children.len())
+                .find(|&i| !is_trivia(&children[i]))
+                .ok_or("Unbalanced brackets")?;
+            if is_comma(&children[next]) {
+                // keep the trailing comma style
+                let mut inserted = separator;
+                inserted.extend([node, comma()]);
+                (next + 1, inserted)
+            } else {
+                let mut inserted = vec![comma()];
+                inserted.extend(separator);
+                inserted.push(node);
+                (last + 1, inserted)
+            }
+        };
+        children.splice(at..at, inserted);
+        Ok(())
+    }
+
+    /// The whitespace between `idx` and the token before it, starting at the
+    /// last line break; comments are left out.
+    fn separator(&self, children: &[SyntaxElement], idx: usize) -> Vec<SyntaxElement> {
+        let prev = (0..idx)
+            .rev()
+            .find(|&i| !is_trivia(&children[i]))
+            .unwrap_or(0);
+        let start = match (prev + 1..idx).rev().find(|&i| {
+            children[i].as_token().map(SyntaxToken::token_type) == Some(TokenType::NewLine)
+        }) {
+            // include the `\r` of a `\r\n`
+            Some(newline) if newline > prev + 1 && is_newline(&children[newline - 1]) => {
+                newline - 1
+            }
+            Some(newline) => newline,
+            None => prev + 1,
+        };
+        children[start..idx]
+            .iter()
+            .filter(|e| {
+                e.as_token()
+                    .is_some_and(|t| t.is_trivia() && t.token_type() != TokenType::Comment)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// The whitespace after a comma on a single line: a space when the
+    /// document puts one after its colons.
+    fn inline_separator(&self) -> Vec<SyntaxElement> {
+        if self.style.colon.last().is_some_and(is_trivia) {
+            whitespace(" ")
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn build_value(&self, value: &JsonValue, indent: &str) -> SyntaxNode {
+        let (kind, open, close) = match value {
+            JsonValue::Object(_) => (NodeKind::Object, TokenType::LeftBrace, "{"),
+            JsonValue::Array(_) => (NodeKind::Array, TokenType::LeftSquareBracket, "["),
+            _ => {
+                let token_type = match value {
+                    JsonValue::Null => TokenType::Null,
+                    JsonValue::Boolean(_) => TokenType::Boolean,
+                    JsonValue::Number(_) => TokenType::Number,
+                    _ => TokenType::Text,
+                };
+                let mut scalar = SyntaxNode::new(NodeKind::Scalar);
+                scalar.push(SyntaxElement::Token(SyntaxToken::new(
+                    token_type,
+                    value.stringify(),
+                )));
+                return scalar;
+            }
+        };
+
+        let mut node = SyntaxNode::new(kind);
+        node.push(SyntaxElement::Token(SyntaxToken::new(open, close)));
+        let inner = self
+            .style
+            .indent_unit
+            .as_ref()
+            .map(|unit| format!("{}{}", indent, unit));
+        let entries: Vec<(Option<&String>, &JsonValue)> = match value {
+            JsonValue::Object(map) => {
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                keys.into_iter().map(|key| (Some(key), &map[key])).collect()
+            }
+            JsonValue::Array(items) => items.iter().map(|item| (None, item)).collect(),
+            _ => Vec::new(),
+        };
+
+        for (i, (key, item)) in entries.iter().enumerate() {
+            if i > 0 {
+                node.push(SyntaxElement::Token(SyntaxToken::new(
+                    TokenType::Comma,
+                    ",",
+                )));
+                if inner.is_none() {
+                    node.children_mut().extend(self.inline_separator());
+                }
+            }
+            if let Some(inner) = &inner {
+                node.children_mut()
+                    .extend(whitespace(&format!("{}{}", self.style.newline, inner)));
+            }
+            let item_indent = inner.as_deref().unwrap_or(indent);
+            node.push(SyntaxElement::Node(match key {
+                Some(key) => self.build_member(key, item, item_indent),
+                None => self.build_value(item, item_indent),
+            }));
+        }
+        if !entries.is_empty() && inner.is_some() {
+            node.children_mut()
+                .extend(whitespace(&format!("{}{}", self.style.newline, indent)));
+        }
+
+        let (close_type, close) = if kind == NodeKind::Object {
+            (TokenType::RightBrace, "}")
+        } else {
+            (TokenType::RightSquareBracket, "]")
+        };
+        node.push(SyntaxElement::Token(SyntaxToken::new(close_type, close)));
+        node
+    }
+
+    fn build_member(&self, key: &str, value: &JsonValue, indent: &str) -> SyntaxNode {
+        let mut text = String::new();
+        JsonValue::write_string(key, &mut text, &StringifyOptions::default());
+        let mut member = SyntaxNode::new(NodeKind::Member);
+        member.push(SyntaxElement::Token(SyntaxToken::new(
+            TokenType::Text,
+            text,
+        )));
+        member
+            .children_mut()
+            .extend(self.style.colon.iter().cloned());
+        member.push(SyntaxElement::Node(self.build_value(value, indent)));
+        member
+    }
+
+    /// The indentation of the line `target` starts on.
+    fn line_indent(&self, target: &SyntaxNode) -> String {
+        let first = target.first_token();
+        let mut indent = String::new();
+        let mut line_start = true;
+        for token in self.tree.root().tokens() {
+            if first.is_some_and(|first| std::ptr::eq(first, token)) {
+                break;
+            }
+            match token.token_type() {
+                TokenType::NewLine | TokenType::CarriageReturn => {
+                    indent.clear();
+                    line_start = true;
+                }
+                TokenType::WhiteSpace | TokenType::Tab if line_start => {
+                    indent.push_str(token.text())
+                }
+                _ => line_start = false,
+            }
+        }
+        indent
+    }
+
+    /// The child indices leading from the document node to the value at
+    /// `tokens`, passing through `Member` nodes.
+    fn locate(&self, tokens: &[String], pointer: &str) -> Result<Vec<usize>, String> {
+        let root = self.tree.root();
+        let mut path = vec![value_index(root).ok_or("Document has no value")?];
+        let mut node = self.node_at(&path);
+        for token in tokens {
+            let idx = match node.kind() {
+                NodeKind::Object => self.member_index(node, token),
+                NodeKind::Array => parse_index(token).and_then(|idx| {
+                    (0..node.children().len())
+                        .filter(|&i| node.children()[i].as_node().is_some())
+                        .nth(idx)
+                }),
+                _ => None,
+            }
+            .ok_or_else(|| format!("No value at JSON Pointer: {}", pointer))?;
+            path.push(idx);
+            node = self.node_at(&path);
+            if node.kind() == NodeKind::Member {
+                path.push(value_index(node).ok_or("Member has no value")?);
+                node = self.node_at(&path);
+            }
+        }
+        Ok(path)
+    }
+
+    fn member_index(&self, object: &SyntaxNode, key: &str) -> Option<usize> {
+        object.children().iter().position(|child| {
+            child
+                .as_node()
+                .filter(|node| node.kind() == NodeKind::Member)
+                .and_then(|member| self.key_name(member))
+                .is_some_and(|name| name == key)
+        })
+    }
+
+    // the unescaped key of a member, whether quoted or a JSON5 identifier
+    fn key_name(&self, member: &SyntaxNode) -> Option<String> {
+        let text = member.key()?.text();
+        let raw = if text.starts_with('"') || text.starts_with('\'') {
+            &text[1..text.len() - 1]
+        } else {
+            text
+        };
+        Parser::unescape_string(raw, self.options.json5()).ok()
+    }
+
+    fn node_at(&self, path: &[usize]) -> &SyntaxNode {
+        let mut node = self.tree.root();
+        for &idx in path {
+            node = node.children()[idx]
+                .as_node()
+                .expect("path leads through nodes");
+        }
+        node
+    }
+
+    fn node_at_mut(&mut self, path: &[usize]) -> &mut SyntaxNode {
+        let mut node = self.tree.root_mut();
+        for &idx in path {
+            node = node.children_mut()[idx]
+                .as_node_mut()
+                .expect("path leads through nodes");
+        }
+        node
+    }
+
+    fn element_at_mut(&mut self, path: &[usize]) -> &mut SyntaxElement {
+        let (last, parent) = path.split_last().expect("path is not empty");
+        &mut self.node_at_mut(parent).children_mut()[*last]
+    }
+}
+
+impl Style {
+    fn detect(root: &SyntaxNode) -> Self {
+        let tokens = root.tokens();
+        let mut indent_unit: Option<String> = None;
+        let mut newline = "\n";
+        for pair in tokens.windows(2) {
+            match (pair[0].token_type(), pair[1].token_type()) {
+                (TokenType::CarriageReturn, TokenType::NewLine) => newline = "\r\n",
+                (TokenType::NewLine, TokenType::WhiteSpace | TokenType::Tab) => {
+                    let indent = pair[1].text();
+                    if indent_unit
+                        .as_ref()
+                        .is_none_or(|unit| indent.len() < unit.len())
+                    {
+                        indent_unit = Some(indent.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let colon = find_member(root)
+            .map(|member| {
+                let value = value_index(member).unwrap_or(member.children().len());
+                member.children()[1..value]
+                    .iter()
+                    .filter(|e| {
+                        !e.as_token().is_some_and(|t| {
+                            matches!(
+                                t.token_type(),
+                                TokenType::Comment | TokenType::NewLine | TokenType::CarriageReturn
+                            )
+                        })
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_else(|| {
+                vec![
+                    SyntaxElement::Token(SyntaxToken::new(TokenType::Colon, ":")),
+                    SyntaxElement::Token(SyntaxToken::new(TokenType::WhiteSpace, " ")),
+                ]
+            });
+
+        Self {
+            indent_unit,
+            newline: newline.to_string(),
+            colon,
+        }
+    }
+}
+
+impl fmt::Display for EditableDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tree)
+    }
+}
+
+fn find_member(node: &SyntaxNode) -> Option<&SyntaxNode> {
+    if node.kind() == NodeKind::Member {
+        return Some(node);
+    }
+    node.child_nodes().find_map(find_member)
+}
+
+fn value_index(node: &SyntaxNode) -> Option<usize> {
+    node.children().iter().position(|c| c.as_node().is_some())
+}
+
+fn is_trivia(element: &SyntaxElement) -> bool {
+    element.as_token().is_some_and(SyntaxToken::is_trivia)
+}
+
+fn is_newline(element: &SyntaxElement) -> bool {
+    element.as_token().is_some_and(|t| {
+        matches!(
+            t.token_type(),
+            TokenType::NewLine | TokenType::CarriageReturn
+        )
+    })
+}
+
+fn is_comma(element: &SyntaxElement) -> bool {
+    element
+        .as_token()
+        .is_some_and(|t| t.token_type() == TokenType::Comma)
+}
+
+// splits whitespace text into trivia tokens the way the tokenizer does
+fn whitespace(text: &str) -> Vec<SyntaxElement> {
+    let mut elements = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let (token_type, mut run) = match c {
+            '\n' => (TokenType::NewLine, String::from("\n")),
+            '\r' => (TokenType::CarriageReturn, String::from("\r")),
+            '\t' => (TokenType::Tab, String::from("\t")),
+            _ => (TokenType::WhiteSpace, c.to_string()),
+        };
+        if matches!(c, ' ' | '\t') {
+            while chars.peek() == Some(&c) {
+                run.push(c);
+                chars.next();
+            }
+        }
+        elements.push(SyntaxElement::Token(SyntaxToken::new(token_type, run)));
+    }
+    elements
+}
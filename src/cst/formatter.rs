@@ -0,0 +1,301 @@
+use std::{fs, mem, path::Path};
+
+use crate::{
+    core::{parse_options::ParseOptions, tokentype::TokenType},
+    cst::{
+        syntax_node::{NodeKind, SyntaxElement, SyntaxNode, SyntaxToken},
+        syntax_tree::SyntaxTree,
+    },
+};
+
+/// Reformats JSON source with consistent indentation.
+///
+/// Non-empty objects always put one member per line; arrays stay on one line
+/// when they fit within the print width and hold no comments or objects.
+/// Comments are kept next to the value they belong to, a blank line between
+/// members is kept (several collapse into one), and string and number
+/// literals are written exactly as in the source. Formatting is idempotent:
+/// formatting the output again returns it unchanged.
+#[derive(Debug, Clone)]
+pub struct Formatter {
+    indent: String,
+    print_width: usize,
+    options: ParseOptions,
+}
+
+impl Default for Formatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Formatter {
+    /// Two-space indentation and an 80 column print width.
+    pub fn new() -> Self {
+        Self {
+            indent: "  ".to_string(),
+            print_width: 80,
+            options: ParseOptions::default(),
+        }
+    }
+
+    /// The string used for one level of indentation, e.g. `"\t"`.
+    pub fn with_indent(mut self, indent: &str) -> Self {
+        self.indent = indent.to_string();
+        self
+    }
+
+    pub fn with_print_width(mut self, print_width: usize) -> Self {
+        self.print_width = print_width;
+        self
+    }
+
+    /// The dialect of the input, e.g. allowing comments for JSONC files.
+    pub fn with_parse_options(mut self, options: ParseOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn format(&self, source: &str) -> Result<String, String> {
+        let tree = SyntaxTree::parse(source, &self.options)?;
+        let layout = Layout::of(tree.root().children());
+        let mut out = String::new();
+
+        for entry in &layout.entries {
+            for comment in &entry.leading {
+                out.push_str(comment.text());
+                out.push('\n');
+            }
+            self.write_value(entry.node, "", &mut out);
+            for comment in &entry.trailing {
+                out.push(' ');
+                out.push_str(comment.text());
+            }
+        }
+        for comment in &layout.dangling {
+            out.push('\n');
+            out.push_str(comment.text());
+        }
+        out.push('\n');
+        Ok(out)
+    }
+
+    /// Whether `source` is already formatted.
+    pub fn check(&self, source: &str) -> Result<bool, String> {
+        Ok(self.format(source)? == source)
+    }
+
+    /// Whether the file at `path` is already formatted.
+    pub fn check_file(&self, path: impl AsRef<Path>) -> Result<bool, String> {
+        let source = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        self.check(&source)
+    }
+
+    fn write_value(&self, node: &SyntaxNode, indent: &str, out: &mut String) {
+        if node.kind() == NodeKind::Scalar {
+            out.push_str(&Self::scalar(node));
+            return;
+        }
+
+        if let Some(flat) = self.flat(node) {
+            let column = out.rsplit('\n').next().unwrap_or("").chars().count();
+            if node.child_nodes().next().is_none()
+                || column + flat.chars().count() <= self.print_width
+            {
+                out.push_str(&flat);
+                return;
+            }
+        }
+
+        let (open, close) = if node.kind() == NodeKind::Object {
+            ('{', '}')
+        } else {
+            ('[', ']')
+        };
+        let inner = format!("{}{}", indent, self.indent);
+        let layout = Layout::of(node.children());
+
+        out.push(open);
+        for (i, entry) in layout.entries.iter().enumerate() {
+            out.push('\n');
+            if entry.blank_line_before && i > 0 {
+                out.push('\n');
+            }
+            for comment in &entry.leading {
+                out.push_str(&inner);
+                out.push_str(comment.text());
+                out.push('\n');
+            }
+            out.push_str(&inner);
+            if entry.node.kind() == NodeKind::Member {
+                self.write_member(entry.node, &inner, out);
+            } else {
+                self.write_value(entry.node, &inner, out);
+            }
+            if i + 1 < layout.entries.len() {
+                out.push(',');
+            }
+            for comment in &entry.trailing {
+                out.push(' ');
+                out.push_str(comment.text());
+            }
+        }
+        for comment in &layout.dangling {
+            out.push('\n');
+            out.push_str(&inner);
+            out.push_str(comment.text());
+        }
+        out.push('\n');
+        out.push_str(indent);
+        out.push(close);
+    }
+
+    // comments between a key and its value are moved after the colon; after
+    // a line comment the value continues one level deeper on the next line
+    fn write_member(&self, member: &SyntaxNode, indent: &str, out: &mut String) {
+        out.push_str(member.key().map(SyntaxToken::text).unwrap_or("\"\""));
+        out.push(':');
+        let mut space = true;
+        let mut value_indent = indent.to_string();
+        for child in member.children() {
+            match child {
+                SyntaxElement::Token(token) if token.token_type() == TokenType::Comment => {
+                    out.push(' ');
+                    out.push_str(token.text());
+                    space = true;
+                    if token.text().starts_with("//") {
+                        value_indent = format!("{}{}", indent, self.indent);
+                        out.push('\n');
+                        out.push_str(&value_indent);
+                        space = false;
+                    }
+                }
+                SyntaxElement::Node(value) => {
+                    if space {
+                        out.push(' ');
+                    }
+                    self.write_value(value, &value_indent, out);
+                }
+                SyntaxElement::Token(_) => {}
+            }
+        }
+    }
+
+    // the literal with any sign directly before it; trivia between the two
+    // is dropped
+    fn scalar(node: &SyntaxNode) -> String {
+        let text: String = node
+            .tokens()
+            .into_iter()
+            .filter(|token| !token.is_trivia())
+            .map(SyntaxToken::text)
+            .collect();
+        if text.is_empty() {
+            "null".to_string()
+        } else {
+            text
+        }
+    }
+
+    /// The container on a single line, if it may be written that way.
+    fn flat(&self, node: &SyntaxNode) -> Option<String> {
+        let (open, close) = match node.kind() {
+            NodeKind::Scalar => return Some(Self::scalar(node)),
+            NodeKind::Object => ("{", "}"),
+            NodeKind::Array => ("[", "]"),
+            _ => return None,
+        };
+        let layout = Layout::of(node.children());
+        if layout.has_comments() {
+            return None;
+        }
+        if layout.entries.is_empty() {
+            return Some(format!("{}{}", open, close));
+        }
+        if node.kind() == NodeKind::Object {
+            return None;
+        }
+        let items = layout
+            .entries
+            .iter()
+            .map(|entry| self.flat(entry.node))
+            .collect::<Option<Vec<String>>>()?;
+        Some(format!("{}{}{}", open, items.join(", "), close))
+    }
+}
+
+// the children of a container grouped by member or element, with the
+// comments around each
+struct Layout<'a> {
+    entries: Vec<Entry<'a>>,
+    // comments after the last entry, or in an empty container
+    dangling: Vec<&'a SyntaxToken>,
+}
+
+struct Entry<'a> {
+    node: &'a SyntaxNode,
+    // comments on the lines above
+    leading: Vec<&'a SyntaxToken>,
+    // comments later on the same line
+    trailing: Vec<&'a SyntaxToken>,
+    blank_line_before: bool,
+}
+
+impl<'a> Layout<'a> {
+    fn of(children: &'a [SyntaxElement]) -> Self {
+        let mut entries: Vec<Entry<'a>> = Vec::new();
+        let mut pending = Vec::new();
+        let mut newlines = 0;
+        let mut blank_line = false;
+        let mut same_line = false;
+
+        for child in children {
+            match child {
+                SyntaxElement::Node(node) => {
+                    entries.push(Entry {
+                        node,
+                        leading: mem::take(&mut pending),
+                        trailing: Vec::new(),
+                        blank_line_before: blank_line || newlines >= 2,
+                    });
+                    blank_line = false;
+                    newlines = 0;
+                    same_line = true;
+                }
+                SyntaxElement::Token(token) => match token.token_type() {
+                    TokenType::NewLine => {
+                        newlines += 1;
+                        same_line = false;
+                    }
+                    TokenType::Comment if same_line && !entries.is_empty() => {
+                        if let Some(entry) = entries.last_mut() {
+                            entry.trailing.push(token);
+                        }
+                    }
+                    TokenType::Comment => {
+                        if pending.is_empty() {
+                            blank_line = newlines >= 2;
+                        }
+                        pending.push(token);
+                        newlines = 0;
+                    }
+                    TokenType::WhiteSpace | TokenType::Tab | TokenType::CarriageReturn => {}
+                    _ => newlines = 0,
+                },
+            }
+        }
+
+        Self {
+            entries,
+            dangling: pending,
+        }
+    }
+
+    fn has_comments(&self) -> bool {
+        !self.dangling.is_empty()
+            || self
+                .entries
+                .iter()
+                .any(|entry| !entry.leading.is_empty() || !entry.trailing.is_empty())
+    }
+}
@@ -0,0 +1,4 @@
+pub mod editable_document;
+pub mod formatter;
+pub mod syntax_node;
+pub mod syntax_tree;
@@ -0,0 +1,201 @@
+use std::fmt;
+
+use crate::core::{span::Span, tokentype::TokenType};
+
+/// What a `SyntaxNode` represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    /// The whole input: leading trivia, the root value and trailing trivia.
+    Document,
+    Object,
+    Array,
+    /// An object member, from its key up to the end of its value.
+    Member,
+    /// A string, number, boolean or null. A negative number starts with its
+    /// `-` token.
+    Scalar,
+}
+
+/// A token together with its exact source text, trivia included.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxToken {
+    token_type: TokenType,
+    text: String,
+    span: Span,
+}
+
+impl SyntaxToken {
+    pub fn new(token_type: TokenType, text: impl Into<String>) -> Self {
+        Self {
+            token_type,
+            text: text.into(),
+            span: Span::default(),
+        }
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+
+    pub fn token_type(&self) -> TokenType {
+        self.token_type
+    }
+
+    /// The token as written, e.g. `"a\n"` with its quotes and escapes.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Where the token appeared in the source; empty for tokens built by hand.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn is_trivia(&self) -> bool {
+        self.token_type.is_trivia()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+impl SyntaxElement {
+    pub fn as_node(&self) -> Option<&SyntaxNode> {
+        match self {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        }
+    }
+
+    pub fn as_node_mut(&mut self) -> Option<&mut SyntaxNode> {
+        match self {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        }
+    }
+
+    pub fn as_token(&self) -> Option<&SyntaxToken> {
+        match self {
+            SyntaxElement::Node(_) => None,
+            SyntaxElement::Token(token) => Some(token),
+        }
+    }
+}
+
+/// A node of the concrete syntax tree. Its children hold every byte of the
+/// source it covers, so printing a tree gives back the original text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxNode {
+    kind: NodeKind,
+    children: Vec<SyntaxElement>,
+}
+
+impl SyntaxNode {
+    pub fn new(kind: NodeKind) -> Self {
+        Self {
+            kind,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn kind(&self) -> NodeKind {
+        self.kind
+    }
+
+    pub fn children(&self) -> &[SyntaxElement] {
+        &self.children
+    }
+
+    pub fn children_mut(&mut self) -> &mut Vec<SyntaxElement> {
+        &mut self.children
+    }
+
+    pub fn push(&mut self, element: SyntaxElement) {
+        self.children.push(element);
+    }
+
+    pub fn child_nodes(&self) -> impl Iterator<Item = &SyntaxNode> {
+        self.children.iter().filter_map(SyntaxElement::as_node)
+    }
+
+    /// All tokens under this node in source order.
+    pub fn tokens(&self) -> Vec<&SyntaxToken> {
+        let mut tokens = Vec::new();
+        self.collect_tokens(&mut tokens);
+        tokens
+    }
+
+    fn collect_tokens<'a>(&'a self, tokens: &mut Vec<&'a SyntaxToken>) {
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => node.collect_tokens(tokens),
+                SyntaxElement::Token(token) => tokens.push(token),
+            }
+        }
+    }
+
+    /// The first token that is not trivia: a bracket, a key or a scalar.
+    pub fn first_token(&self) -> Option<&SyntaxToken> {
+        self.tokens().into_iter().find(|token| !token.is_trivia())
+    }
+
+    /// The span from the first to the last token, trivia included.
+    pub fn span(&self) -> Option<Span> {
+        let tokens = self.tokens();
+        let first = tokens.first()?;
+        let last = tokens.last()?;
+        Some(Span::new(
+            first.span().start_position(),
+            last.span().end_position(),
+        ))
+    }
+
+    /// The key token of a member.
+    pub fn key(&self) -> Option<&SyntaxToken> {
+        match self.kind {
+            NodeKind::Member => self.first_token(),
+            _ => None,
+        }
+    }
+
+    /// The value of a member or document.
+    pub fn value(&self) -> Option<&SyntaxNode> {
+        match self.kind {
+            NodeKind::Member | NodeKind::Document => self.child_nodes().next(),
+            _ => None,
+        }
+    }
+
+    /// The members of an object.
+    pub fn members(&self) -> impl Iterator<Item = &SyntaxNode> {
+        self.child_nodes()
+            .filter(|node| node.kind == NodeKind::Member)
+    }
+
+    /// The elements of an array.
+    pub fn elements(&self) -> impl Iterator<Item = &SyntaxNode> {
+        let is_array = self.kind == NodeKind::Array;
+        self.child_nodes().filter(move |_| is_array)
+    }
+
+    /// The source text the node covers.
+    pub fn text(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => write!(f, "{}", node)?,
+                SyntaxElement::Token(token) => f.write_str(&token.text)?,
+            }
+        }
+        Ok(())
+    }
+}
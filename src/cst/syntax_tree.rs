@@ -0,0 +1,141 @@
+use std::fmt;
+
+use crate::{
+    core::{parse_options::ParseOptions, token::Token, tokentype::TokenType},
+    cst::syntax_node::{NodeKind, SyntaxElement, SyntaxNode, SyntaxToken},
+    lexer::tokenizer::Tokenizer,
+    parser::parser::Parser,
+};
+
+/// A lossless concrete syntax tree. Unlike `JsonValue` it keeps whitespace,
+/// comments, key order and the exact spelling of every token, so
+/// `tree.to_string()` reproduces the input byte for byte.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxTree {
+    root: SyntaxNode,
+}
+
+impl SyntaxTree {
+    /// Parses `source`, which must be valid under `options`; trivia is
+    /// always kept regardless of `options`.
+    pub fn parse(source: &str, options: &ParseOptions) -> Result<Self, String> {
+        let options = options.with_emit_trivia(true);
+        let mut tokenizer = Tokenizer::new(Some(source.to_string()), None).with_options(options);
+        tokenizer.tokenize()?;
+        let mut tokens: Vec<Token> = tokenizer.fsm.all_tokens.values().cloned().collect();
+        tokens.sort_by_key(|t| t.token_idx());
+
+        // the tokenizer only checks neighbouring tokens, so run the full
+        // grammar before building the tree
+        Parser::from_tokens(tokens.clone(), options).parse()?;
+
+        let root = Self::build(source, &tokens)?;
+        Ok(Self { root })
+    }
+
+    fn build(source: &str, tokens: &[Token]) -> Result<SyntaxNode, String> {
+        let mut stack = vec![SyntaxNode::new(NodeKind::Document)];
+        let mut offset = 0;
+
+        for token in tokens {
+            let token_type = token.token_type();
+            if token_type == TokenType::EOF {
+                break;
+            }
+
+            let span = token.span();
+            if span.start() != offset {
+                return Err(format!("Unrecognised input at position {}", offset));
+            }
+            offset = span.end();
+            let text = source
+                .get(span.start()..span.end())
+                .ok_or_else(|| format!("Invalid token span {}", span))?;
+            let element = SyntaxElement::Token(SyntaxToken::new(token_type, text).with_span(span));
+
+            match token_type {
+                TokenType::LeftBrace | TokenType::LeftSquareBracket => {
+                    let kind = if token_type == TokenType::LeftBrace {
+                        NodeKind::Object
+                    } else {
+                        NodeKind::Array
+                    };
+                    let mut node = SyntaxNode::new(kind);
+                    node.push(element);
+                    stack.push(node);
+                }
+                TokenType::RightBrace | TokenType::RightSquareBracket => {
+                    let mut node = stack.pop().ok_or("Unbalanced brackets")?;
+                    node.push(element);
+                    Self::attach(&mut stack, node)?;
+                }
+                TokenType::Text if stack.last().map(SyntaxNode::kind) == Some(NodeKind::Object) => {
+                    let mut member = SyntaxNode::new(NodeKind::Member);
+                    member.push(element);
+                    stack.push(member);
+                }
+                // the sign opens the scalar and its number completes it
+                TokenType::MinusSign => {
+                    let mut scalar = SyntaxNode::new(NodeKind::Scalar);
+                    scalar.push(element);
+                    stack.push(scalar);
+                }
+                TokenType::Number
+                    if stack.last().map(SyntaxNode::kind) == Some(NodeKind::Scalar) =>
+                {
+                    let mut scalar = stack.pop().ok_or("Unbalanced brackets")?;
+                    scalar.push(element);
+                    Self::attach(&mut stack, scalar)?;
+                }
+                TokenType::Text | TokenType::Number | TokenType::Boolean | TokenType::Null => {
+                    let mut scalar = SyntaxNode::new(NodeKind::Scalar);
+                    scalar.push(element);
+                    Self::attach(&mut stack, scalar)?;
+                }
+                _ => stack.last_mut().ok_or("Unbalanced brackets")?.push(element),
+            }
+        }
+
+        if offset != source.len() {
+            return Err(format!("Unrecognised input at position {}", offset));
+        }
+        match stack.pop() {
+            Some(root) if stack.is_empty() => Ok(root),
+            _ => Err("Unbalanced brackets".to_string()),
+        }
+    }
+
+    /// Adds a finished value to its parent, closing the member it completes.
+    fn attach(stack: &mut Vec<SyntaxNode>, node: SyntaxNode) -> Result<(), String> {
+        let parent = stack.last_mut().ok_or("Unbalanced brackets")?;
+        parent.push(SyntaxElement::Node(node));
+        if parent.kind() == NodeKind::Member {
+            let member = stack.pop().ok_or("Unbalanced brackets")?;
+            stack
+                .last_mut()
+                .ok_or("Unbalanced brackets")?
+                .push(SyntaxElement::Node(member));
+        }
+        Ok(())
+    }
+
+    /// The `Document` node.
+    pub fn root(&self) -> &SyntaxNode {
+        &self.root
+    }
+
+    pub fn root_mut(&mut self) -> &mut SyntaxNode {
+        &mut self.root
+    }
+
+    /// The root value, without surrounding trivia.
+    pub fn value(&self) -> Option<&SyntaxNode> {
+        self.root.value()
+    }
+}
+
+impl fmt::Display for SyntaxTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.root)
+    }
+}
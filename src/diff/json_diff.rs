@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use crate::core::{json_pointer::child_path, json_value::JsonValue, lcs::lcs_lengths};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One difference between two documents, located by a JSON Pointer.
+///
+/// Removed entries point into the old document, added and changed entries into the new one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffEntry {
+    kind: DiffKind,
+    path: String,
+    old_value: Option<JsonValue>,
+    new_value: Option<JsonValue>,
+}
+
+impl DiffEntry {
+    pub fn kind(&self) -> DiffKind {
+        self.kind
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn old_value(&self) -> Option<&JsonValue> {
+        self.old_value.as_ref()
+    }
+
+    pub fn new_value(&self) -> Option<&JsonValue> {
+        self.new_value.as_ref()
+    }
+}
+
+/// How array elements are paired up before they are compared.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ArrayDiffMode {
+    /// Compare elements at the same index
+    #[default]
+    Index,
+    /// Keep the longest common subsequence of equal elements and diff the rest
+    Lcs,
+    /// Match object elements by the value of the given member, e.g. `"id"`
+    ByKey(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffOptions {
+    array_mode: ArrayDiffMode,
+}
+
+impl DiffOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_array_mode(mut self, array_mode: ArrayDiffMode) -> Self {
+        self.array_mode = array_mode;
+        self
+    }
+
+    pub fn array_mode(&self) -> &ArrayDiffMode {
+        &self.array_mode
+    }
+}
+
+/// Reports the paths that were added, removed or changed between `a` and `b`.
+pub fn json_diff(a: &JsonValue, b: &JsonValue) -> Vec<DiffEntry> {
+    json_diff_with(a, b, &DiffOptions::default())
+}
+
+pub fn json_diff_with(a: &JsonValue, b: &JsonValue, options: &DiffOptions) -> Vec<DiffEntry> {
+    let mut entries = Vec::new();
+    diff_values(a, b, "", options, &mut entries);
+    entries
+}
+
+/// Renders diff entries in a unified-diff-like layout, one hunk per path.
+pub fn render_diff(entries: &[DiffEntry]) -> String {
+    let mut out = String::from("--- a\n+++ b\n");
+    for entry in entries {
+        let path = if entry.path.is_empty() {
+            "/"
+        } else {
+            &entry.path
+        };
+        out.push_str(&format!("@@ {} @@\n", path));
+        if let Some(old) = &entry.old_value {
+            out.push_str(&format!("-{}\n", old.stringify()));
+        }
+        if let Some(new) = &entry.new_value {
+            out.push_str(&format!("+{}\n", new.stringify()));
+        }
+    }
+    out
+}
+
+fn diff_values(
+    a: &JsonValue,
+    b: &JsonValue,
+    path: &str,
+    options: &DiffOptions,
+    entries: &mut Vec<DiffEntry>,
+) {
+    if a == b {
+        return;
+    }
+    match (a, b) {
+        (JsonValue::Object(a_map), JsonValue::Object(b_map)) => {
+            let mut keys: Vec<&String> = a_map.keys().chain(b_map.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let child = child_path(path, key);
+                match (a_map.get(key), b_map.get(key)) {
+                    (Some(a_val), Some(b_val)) => {
+                        diff_values(a_val, b_val, &child, options, entries)
+                    }
+                    (Some(a_val), None) => entries.push(removed(child, a_val)),
+                    (None, Some(b_val)) => entries.push(added(child, b_val)),
+                    (None, None) => {}
+                }
+            }
+        }
+        (JsonValue::Array(a_vec), JsonValue::Array(b_vec)) => match &options.array_mode {
+            ArrayDiffMode::Index => diff_by_index(a_vec, b_vec, path, options, entries),
+            ArrayDiffMode::Lcs => diff_by_lcs(a_vec, b_vec, path, options, entries),
+            ArrayDiffMode::ByKey(key) => diff_by_key(a_vec, b_vec, key, path, options, entries),
+        },
+        _ => entries.push(DiffEntry {
+            kind: DiffKind::Changed,
+            path: path.to_string(),
+            old_value: Some(a.clone()),
+            new_value: Some(b.clone()),
+        }),
+    }
+}
+
+fn diff_by_index(
+    a_vec: &[JsonValue],
+    b_vec: &[JsonValue],
+    path: &str,
+    options: &DiffOptions,
+    entries: &mut Vec<DiffEntry>,
+) {
+    for i in 0..a_vec.len().max(b_vec.len()) {
+        let child = child_path(path, &i.to_string());
+        match (a_vec.get(i), b_vec.get(i)) {
+            (Some(a_val), Some(b_val)) => diff_values(a_val, b_val, &child, options, entries),
+            (Some(a_val), None) => entries.push(removed(child, a_val)),
+            (None, Some(b_val)) => entries.push(added(child, b_val)),
+            (None, None) => {}
+        }
+    }
+}
+
+fn diff_by_lcs(
+    a_vec: &[JsonValue],
+    b_vec: &[JsonValue],
+    path: &str,
+    options: &DiffOptions,
+    entries: &mut Vec<DiffEntry>,
+) {
+    let (n, m) = (a_vec.len(), b_vec.len());
+    let lengths = lcs_lengths(a_vec, b_vec);
+
+    // Walk the table; unmatched runs between common elements are paired positionally
+    let (mut i, mut j) = (0, 0);
+    let mut a_gap: Vec<usize> = Vec::new();
+    let mut b_gap: Vec<usize> = Vec::new();
+    while i < n || j < m {
+        if i < n && j < m && a_vec[i] == b_vec[j] {
+            flush_gap(a_vec, b_vec, &mut a_gap, &mut b_gap, path, options, entries);
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lengths[i][j + 1] >= lengths[i + 1][j]) {
+            b_gap.push(j);
+            j += 1;
+        } else {
+            a_gap.push(i);
+            i += 1;
+        }
+    }
+    flush_gap(a_vec, b_vec, &mut a_gap, &mut b_gap, path, options, entries);
+}
+
+fn flush_gap(
+    a_vec: &[JsonValue],
+    b_vec: &[JsonValue],
+    a_gap: &mut Vec<usize>,
+    b_gap: &mut Vec<usize>,
+    path: &str,
+    options: &DiffOptions,
+    entries: &mut Vec<DiffEntry>,
+) {
+    let common = a_gap.len().min(b_gap.len());
+    for k in 0..common {
+        let child = child_path(path, &b_gap[k].to_string());
+        diff_values(&a_vec[a_gap[k]], &b_vec[b_gap[k]], &child, options, entries);
+    }
+    for &i in &a_gap[common..] {
+        entries.push(removed(child_path(path, &i.to_string()), &a_vec[i]));
+    }
+    for &j in &b_gap[common..] {
+        entries.push(added(child_path(path, &j.to_string()), &b_vec[j]));
+    }
+    a_gap.clear();
+    b_gap.clear();
+}
+
+fn diff_by_key(
+    a_vec: &[JsonValue],
+    b_vec: &[JsonValue],
+    key: &str,
+    path: &str,
+    options: &DiffOptions,
+    entries: &mut Vec<DiffEntry>,
+) {
+    // Key values are compared through their serialized form so any JSON type can be a key
+    let key_of = |value: &JsonValue| -> Option<String> {
+        match value {
+            JsonValue::Object(map) => map.get(key).map(|k| k.stringify()),
+            _ => None,
+        }
+    };
+    let mut b_index: HashMap<String, usize> = HashMap::new();
+    for (j, value) in b_vec.iter().enumerate() {
+        if let Some(k) = key_of(value) {
+            b_index.entry(k).or_insert(j);
+        }
+    }
+
+    let mut matched = vec![false; b_vec.len()];
+    for (i, a_val) in a_vec.iter().enumerate() {
+        match key_of(a_val).and_then(|k| b_index.get(&k).copied()) {
+            Some(j) if !matched[j] => {
+                matched[j] = true;
+                let child = child_path(path, &j.to_string());
+                diff_values(a_val, &b_vec[j], &child, options, entries);
+            }
+            _ => entries.push(removed(child_path(path, &i.to_string()), a_val)),
+        }
+    }
+    for (j, b_val) in b_vec.iter().enumerate() {
+        if !matched[j] {
+            entries.push(added(child_path(path, &j.to_string()), b_val));
+        }
+    }
+}
+
+fn added(path: String, value: &JsonValue) -> DiffEntry {
+    DiffEntry {
+        kind: DiffKind::Added,
+        path,
+        old_value: None,
+        new_value: Some(value.clone()),
+    }
+}
+
+fn removed(path: String, value: &JsonValue) -> DiffEntry {
+    DiffEntry {
+        kind: DiffKind::Removed,
+        path,
+        old_value: Some(value.clone()),
+        new_value: None,
+    }
+}
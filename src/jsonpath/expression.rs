@@ -0,0 +1,76 @@
+use crate::core::json_value::JsonValue;
+
+/// A parsed JSONPath query: `$` or `@` followed by segments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub root: QueryRoot,
+    pub segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryRoot {
+    /// `$`, the document root
+    Root,
+    /// `@`, the node currently tested by a filter
+    Current,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    /// `.name`, `.*` or `[...]`
+    Child(Vec<Selector>),
+    /// `..name`, `..*` or `..[...]`
+    Descendant(Vec<Selector>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Selector {
+    Name(String),
+    Wildcard,
+    Index(i64),
+    Slice {
+        start: Option<i64>,
+        end: Option<i64>,
+        step: Option<i64>,
+    },
+    Filter(FilterExpr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr {
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Not(Box<FilterExpr>),
+    Comparison(Comparable, CompareOp, Comparable),
+    /// Passes when the query selects at least one node
+    Exists(Query),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Comparable {
+    Literal(JsonValue),
+    /// Always a singular query (only single name/index child segments)
+    Query(Query),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    NotEq,
+    Less,
+    LessEq,
+    Greater,
+    GreaterEq,
+}
+
+impl Query {
+    pub fn is_singular(&self) -> bool {
+        self.segments.iter().all(|segment| match segment {
+            Segment::Child(selectors) => {
+                selectors.len() == 1
+                    && matches!(selectors[0], Selector::Name(_) | Selector::Index(_))
+            }
+            Segment::Descendant(_) => false,
+        })
+    }
+}
@@ -0,0 +1,311 @@
+use crate::{
+    core::json_value::JsonValue,
+    jsonpath::{
+        expression::{Comparable, CompareOp, FilterExpr, Query, QueryRoot, Segment, Selector},
+        path_parser::PathParser,
+    },
+};
+
+/// A compiled RFC 9535 JSONPath expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+    query: Query,
+}
+
+/// A node selected by a `JsonPath`, with its normalized path (e.g. `$['flights'][0]`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathMatch<'a> {
+    path: String,
+    value: &'a JsonValue,
+}
+
+impl<'a> PathMatch<'a> {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn value(&self) -> &'a JsonValue {
+        self.value
+    }
+}
+
+#[derive(Debug, Clone)]
+enum PathElement {
+    Name(String),
+    Index(usize),
+}
+
+type Node<'a> = (Vec<PathElement>, &'a JsonValue);
+
+impl JsonPath {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let query = PathParser::new(expression).parse()?;
+        Ok(Self { query })
+    }
+
+    pub fn expression(&self) -> &Query {
+        &self.query
+    }
+
+    /// Returns every matching node in document order, together with its normalized path.
+    pub fn query<'a>(&self, root: &'a JsonValue) -> Vec<PathMatch<'a>> {
+        Self::eval_query(&self.query, root, root)
+            .into_iter()
+            .map(|(path, value)| PathMatch {
+                path: Self::normalized_path(&path),
+                value,
+            })
+            .collect()
+    }
+
+    /// Returns only the matching values.
+    pub fn query_values<'a>(&self, root: &'a JsonValue) -> Vec<&'a JsonValue> {
+        Self::eval_query(&self.query, root, root)
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect()
+    }
+
+    fn eval_query<'a>(query: &Query, root: &'a JsonValue, current: &'a JsonValue) -> Vec<Node<'a>> {
+        let start = match query.root {
+            QueryRoot::Root => root,
+            QueryRoot::Current => current,
+        };
+        let mut nodes: Vec<Node<'a>> = vec![(Vec::new(), start)];
+
+        for segment in &query.segments {
+            let mut next = Vec::new();
+            for (path, value) in &nodes {
+                match segment {
+                    Segment::Child(selectors) => {
+                        for selector in selectors {
+                            Self::select(selector, root, path, value, &mut next);
+                        }
+                    }
+                    Segment::Descendant(selectors) => {
+                        Self::select_descendants(selectors, root, path.clone(), value, &mut next);
+                    }
+                }
+            }
+            nodes = next;
+        }
+        nodes
+    }
+
+    // Visits the node and then its descendants in document order
+    fn select_descendants<'a>(
+        selectors: &[Selector],
+        root: &'a JsonValue,
+        path: Vec<PathElement>,
+        value: &'a JsonValue,
+        out: &mut Vec<Node<'a>>,
+    ) {
+        for selector in selectors {
+            Self::select(selector, root, &path, value, out);
+        }
+        for (element, child) in Self::children(value) {
+            let mut child_path = path.clone();
+            child_path.push(element);
+            Self::select_descendants(selectors, root, child_path, child, out);
+        }
+    }
+
+    fn select<'a>(
+        selector: &Selector,
+        root: &'a JsonValue,
+        path: &[PathElement],
+        value: &'a JsonValue,
+        out: &mut Vec<Node<'a>>,
+    ) {
+        let mut push = |element: PathElement, child: &'a JsonValue| {
+            let mut child_path = path.to_vec();
+            child_path.push(element);
+            out.push((child_path, child));
+        };
+
+        match selector {
+            Selector::Name(name) => {
+                if let JsonValue::Object(map) = value
+                    && let Some(child) = map.get(name)
+                {
+                    push(PathElement::Name(name.clone()), child);
+                }
+            }
+            Selector::Wildcard => {
+                for (element, child) in Self::children(value) {
+                    push(element, child);
+                }
+            }
+            Selector::Index(idx) => {
+                if let JsonValue::Array(vec) = value
+                    && let Some(i) = Self::normalize_index(*idx, vec.len())
+                {
+                    push(PathElement::Index(i), &vec[i]);
+                }
+            }
+            Selector::Slice { start, end, step } => {
+                if let JsonValue::Array(vec) = value {
+                    for i in Self::slice_indices(*start, *end, *step, vec.len()) {
+                        push(PathElement::Index(i), &vec[i]);
+                    }
+                }
+            }
+            Selector::Filter(expr) => {
+                for (element, child) in Self::children(value) {
+                    if Self::eval_filter(expr, root, child) {
+                        push(element, child);
+                    }
+                }
+            }
+        }
+    }
+
+    // Object members are visited in sorted key order so results are deterministic
+    fn children(value: &JsonValue) -> Vec<(PathElement, &JsonValue)> {
+        match value {
+            JsonValue::Array(vec) => vec
+                .iter()
+                .enumerate()
+                .map(|(i, child)| (PathElement::Index(i), child))
+                .collect(),
+            JsonValue::Object(map) => {
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                keys.into_iter()
+                    .map(|key| (PathElement::Name(key.clone()), &map[key]))
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn normalize_index(idx: i64, len: usize) -> Option<usize> {
+        let len = len as i64;
+        let idx = if idx < 0 { len + idx } else { idx };
+        if idx >= 0 && idx < len {
+            Some(idx as usize)
+        } else {
+            None
+        }
+    }
+
+    fn slice_indices(
+        start: Option<i64>,
+        end: Option<i64>,
+        step: Option<i64>,
+        len: usize,
+    ) -> Vec<usize> {
+        let len = len as i64;
+        let step = step.unwrap_or(1);
+        let normalize = |i: i64| if i >= 0 { i } else { len + i };
+        let mut indices = Vec::new();
+
+        if step > 0 {
+            let lower = normalize(start.unwrap_or(0)).clamp(0, len);
+            let upper = normalize(end.unwrap_or(len)).clamp(0, len);
+            let mut i = lower;
+            while i < upper {
+                indices.push(i as usize);
+                i += step;
+            }
+        } else if step < 0 {
+            let upper = normalize(start.unwrap_or(len - 1)).clamp(-1, len - 1);
+            let lower = normalize(end.unwrap_or(-len - 1)).clamp(-1, len - 1);
+            let mut i = upper;
+            while lower < i {
+                indices.push(i as usize);
+                i += step;
+            }
+        }
+        indices
+    }
+
+    fn eval_filter(expr: &FilterExpr, root: &JsonValue, current: &JsonValue) -> bool {
+        match expr {
+            FilterExpr::Or(left, right) => {
+                Self::eval_filter(left, root, current) || Self::eval_filter(right, root, current)
+            }
+            FilterExpr::And(left, right) => {
+                Self::eval_filter(left, root, current) && Self::eval_filter(right, root, current)
+            }
+            FilterExpr::Not(inner) => !Self::eval_filter(inner, root, current),
+            FilterExpr::Exists(query) => !Self::eval_query(query, root, current).is_empty(),
+            FilterExpr::Comparison(left, op, right) => {
+                let left = Self::comparable_value(left, root, current);
+                let right = Self::comparable_value(right, root, current);
+                Self::compare(left, *op, right)
+            }
+        }
+    }
+
+    // `None` stands for "Nothing", the result of a singular query that selects no node
+    fn comparable_value<'a>(
+        comparable: &'a Comparable,
+        root: &'a JsonValue,
+        current: &'a JsonValue,
+    ) -> Option<&'a JsonValue> {
+        match comparable {
+            Comparable::Literal(value) => Some(value),
+            Comparable::Query(query) => Self::eval_query(query, root, current)
+                .into_iter()
+                .next()
+                .map(|(_, value)| value),
+        }
+    }
+
+    fn compare(left: Option<&JsonValue>, op: CompareOp, right: Option<&JsonValue>) -> bool {
+        match op {
+            CompareOp::Eq => left == right,
+            CompareOp::NotEq => left != right,
+            CompareOp::Less => Self::less_than(left, right),
+            CompareOp::LessEq => Self::less_than(left, right) || left == right,
+            CompareOp::Greater => Self::less_than(right, left),
+            CompareOp::GreaterEq => Self::less_than(right, left) || left == right,
+        }
+    }
+
+    // Only numbers and strings are ordered; every other pairing compares false
+    fn less_than(left: Option<&JsonValue>, right: Option<&JsonValue>) -> bool {
+        match (left, right) {
+            (Some(JsonValue::Number(a)), Some(JsonValue::Number(b))) => a < b,
+            (Some(JsonValue::String(a)), Some(JsonValue::String(b))) => a < b,
+            _ => false,
+        }
+    }
+
+    fn normalized_path(path: &[PathElement]) -> String {
+        let mut out = String::from("$");
+        for element in path {
+            match element {
+                PathElement::Name(name) => {
+                    out.push_str("['");
+                    for c in name.chars() {
+                        match c {
+                            '\'' => out.push_str("\\'"),
+                            '\\' => out.push_str("\\\\"),
+                            '\x08' => out.push_str("\\b"),
+                            '\x0c' => out.push_str("\\f"),
+                            '\n' => out.push_str("\\n"),
+                            '\r' => out.push_str("\\r"),
+                            '\t' => out.push_str("\\t"),
+                            _ if (c as u32) < 0x20 => {
+                                out.push_str(&format!("\\u{:04x}", c as u32));
+                            }
+                            _ => out.push(c),
+                        }
+                    }
+                    out.push_str("']");
+                }
+                PathElement::Index(i) => out.push_str(&format!("[{}]", i)),
+            }
+        }
+        out
+    }
+}
+
+impl JsonValue {
+    /// Evaluates a JSONPath expression against this value.
+    pub fn query(&self, expression: &str) -> Result<Vec<PathMatch<'_>>, String> {
+        Ok(JsonPath::parse(expression)?.query(self))
+    }
+}
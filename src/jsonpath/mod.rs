@@ -0,0 +1,3 @@
+pub mod expression;
+pub mod json_path;
+pub mod path_parser;
@@ -0,0 +1,467 @@
+use crate::{
+    core::json_value::JsonValue,
+    jsonpath::expression::{
+        Comparable, CompareOp, FilterExpr, Query, QueryRoot, Segment, Selector,
+    },
+};
+
+// I-JSON exact integer range required by RFC 9535 for indices and slice bounds
+const MAX_SAFE_INT: i64 = (1 << 53) - 1;
+
+/// Recursive descent parser for RFC 9535 JSONPath expressions.
+pub struct PathParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl PathParser {
+    pub fn new(expression: &str) -> Self {
+        Self {
+            chars: expression.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    /// Parses a complete `$`-rooted query; trailing input is an error.
+    pub fn parse(&mut self) -> Result<Query, String> {
+        if self.peek() != Some('$') {
+            return Err(self.error("JSONPath must start with '$'"));
+        }
+        let query = self.parse_query()?;
+        if self.pos < self.chars.len() {
+            return Err(self.error("Unexpected character"));
+        }
+        Ok(query)
+    }
+
+    fn parse_query(&mut self) -> Result<Query, String> {
+        let root = match self.peek() {
+            Some('$') => QueryRoot::Root,
+            Some('@') => QueryRoot::Current,
+            _ => return Err(self.error("Expected '$' or '@'")),
+        };
+        self.pos += 1;
+
+        let mut segments = Vec::new();
+        loop {
+            // Blank space is allowed between segments, but must not be consumed
+            // if no segment follows (it may precede an operator in a filter)
+            let saved = self.pos;
+            self.skip_blank();
+            match self.peek() {
+                Some('[') => segments.push(Segment::Child(self.parse_bracketed()?)),
+                Some('.') if self.peek_at(1) == Some('.') => {
+                    self.pos += 2;
+                    segments.push(Segment::Descendant(self.parse_descendant()?));
+                }
+                Some('.') => {
+                    self.pos += 1;
+                    segments.push(Segment::Child(self.parse_shorthand()?));
+                }
+                _ => {
+                    self.pos = saved;
+                    break;
+                }
+            }
+        }
+        Ok(Query { root, segments })
+    }
+
+    fn parse_descendant(&mut self) -> Result<Vec<Selector>, String> {
+        match self.peek() {
+            Some('[') => self.parse_bracketed(),
+            _ => self.parse_shorthand(),
+        }
+    }
+
+    fn parse_shorthand(&mut self) -> Result<Vec<Selector>, String> {
+        match self.peek() {
+            Some('*') => {
+                self.pos += 1;
+                Ok(vec![Selector::Wildcard])
+            }
+            Some(c) if Self::is_name_first(c) => {
+                let mut name = String::new();
+                while let Some(c) = self.peek() {
+                    if Self::is_name_first(c) || c.is_ascii_digit() {
+                        name.push(c);
+                        self.pos += 1;
+                    } else {
+                        break;
+                    }
+                }
+                Ok(vec![Selector::Name(name)])
+            }
+            _ => Err(self.error("Expected member name or '*'")),
+        }
+    }
+
+    fn parse_bracketed(&mut self) -> Result<Vec<Selector>, String> {
+        self.expect('[')?;
+        let mut selectors = Vec::new();
+        loop {
+            self.skip_blank();
+            selectors.push(self.parse_selector()?);
+            self.skip_blank();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => return Err(self.error("Expected ',' or ']'")),
+            }
+        }
+        Ok(selectors)
+    }
+
+    fn parse_selector(&mut self) -> Result<Selector, String> {
+        match self.peek() {
+            Some('\'') | Some('"') => Ok(Selector::Name(self.parse_string_literal()?)),
+            Some('*') => {
+                self.pos += 1;
+                Ok(Selector::Wildcard)
+            }
+            Some('?') => {
+                self.pos += 1;
+                self.skip_blank();
+                Ok(Selector::Filter(self.parse_logical_or()?))
+            }
+            Some(c) if c == ':' || c == '-' || c.is_ascii_digit() => self.parse_index_or_slice(),
+            _ => Err(self.error("Expected selector")),
+        }
+    }
+
+    fn parse_index_or_slice(&mut self) -> Result<Selector, String> {
+        let start = self.parse_optional_int()?;
+        self.skip_blank();
+        if self.peek() != Some(':') {
+            return match start {
+                Some(idx) => Ok(Selector::Index(idx)),
+                None => Err(self.error("Expected index")),
+            };
+        }
+        self.pos += 1;
+        self.skip_blank();
+        let end = self.parse_optional_int()?;
+        self.skip_blank();
+        let mut step = None;
+        if self.peek() == Some(':') {
+            self.pos += 1;
+            self.skip_blank();
+            step = self.parse_optional_int()?;
+        }
+        Ok(Selector::Slice { start, end, step })
+    }
+
+    fn parse_optional_int(&mut self) -> Result<Option<i64>, String> {
+        match self.peek() {
+            Some(c) if c == '-' || c.is_ascii_digit() => Ok(Some(self.parse_int()?)),
+            _ => Ok(None),
+        }
+    }
+
+    fn parse_int(&mut self) -> Result<i64, String> {
+        let start = self.pos;
+        let negative = self.peek() == Some('-');
+        if negative {
+            self.pos += 1;
+        }
+        let digits_start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let digits: String = self.chars[digits_start..self.pos].iter().collect();
+        if digits.is_empty() {
+            return Err(self.error("Expected digits"));
+        }
+        if (digits.len() > 1 && digits.starts_with('0')) || (negative && digits == "0") {
+            return Err(format!("Invalid integer at position {}", start));
+        }
+        let value = digits
+            .parse::<i64>()
+            .ok()
+            .filter(|v| *v <= MAX_SAFE_INT)
+            .ok_or_else(|| format!("Integer out of range at position {}", start))?;
+        Ok(if negative { -value } else { value })
+    }
+
+    fn parse_logical_or(&mut self) -> Result<FilterExpr, String> {
+        let mut left = self.parse_logical_and()?;
+        loop {
+            self.skip_blank();
+            if self.consume_str("||") {
+                self.skip_blank();
+                let right = self.parse_logical_and()?;
+                left = FilterExpr::Or(Box::new(left), Box::new(right));
+            } else {
+                return Ok(left);
+            }
+        }
+    }
+
+    fn parse_logical_and(&mut self) -> Result<FilterExpr, String> {
+        let mut left = self.parse_basic()?;
+        loop {
+            self.skip_blank();
+            if self.consume_str("&&") {
+                self.skip_blank();
+                let right = self.parse_basic()?;
+                left = FilterExpr::And(Box::new(left), Box::new(right));
+            } else {
+                return Ok(left);
+            }
+        }
+    }
+
+    fn parse_basic(&mut self) -> Result<FilterExpr, String> {
+        match self.peek() {
+            Some('!') => {
+                self.pos += 1;
+                self.skip_blank();
+                let inner = match self.peek() {
+                    Some('(') => self.parse_paren()?,
+                    Some('$') | Some('@') => FilterExpr::Exists(self.parse_query()?),
+                    _ => return Err(self.error("Expected '(' or query after '!'")),
+                };
+                Ok(FilterExpr::Not(Box::new(inner)))
+            }
+            Some('(') => self.parse_paren(),
+            _ => {
+                let start = self.pos;
+                let left = self.parse_comparable_or_query()?;
+                self.skip_blank();
+                match self.parse_compare_op() {
+                    Some(op) => {
+                        self.skip_blank();
+                        let right = self.parse_comparable_or_query()?;
+                        Ok(FilterExpr::Comparison(
+                            Self::to_comparable(left, start)?,
+                            op,
+                            Self::to_comparable(right, start)?,
+                        ))
+                    }
+                    None => match left {
+                        Comparable::Query(query) => Ok(FilterExpr::Exists(query)),
+                        Comparable::Literal(_) => Err(format!(
+                            "Literal must be part of a comparison at position {}",
+                            start
+                        )),
+                    },
+                }
+            }
+        }
+    }
+
+    fn parse_paren(&mut self) -> Result<FilterExpr, String> {
+        self.expect('(')?;
+        self.skip_blank();
+        let expr = self.parse_logical_or()?;
+        self.skip_blank();
+        self.expect(')')?;
+        Ok(expr)
+    }
+
+    // Queries are only comparable when singular, checked once we know a comparison follows
+    fn to_comparable(comparable: Comparable, start: usize) -> Result<Comparable, String> {
+        if let Comparable::Query(query) = &comparable
+            && !query.is_singular()
+        {
+            return Err(format!(
+                "Non-singular query used in comparison at position {}",
+                start
+            ));
+        }
+        Ok(comparable)
+    }
+
+    fn parse_comparable_or_query(&mut self) -> Result<Comparable, String> {
+        match self.peek() {
+            Some('$') | Some('@') => Ok(Comparable::Query(self.parse_query()?)),
+            Some('\'') | Some('"') => Ok(Comparable::Literal(JsonValue::String(
+                self.parse_string_literal()?,
+            ))),
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                Ok(Comparable::Literal(self.parse_number_literal()?))
+            }
+            _ => {
+                if self.consume_keyword("true") {
+                    Ok(Comparable::Literal(JsonValue::Boolean(true)))
+                } else if self.consume_keyword("false") {
+                    Ok(Comparable::Literal(JsonValue::Boolean(false)))
+                } else if self.consume_keyword("null") {
+                    Ok(Comparable::Literal(JsonValue::Null))
+                } else {
+                    Err(self.error("Expected literal or query"))
+                }
+            }
+        }
+    }
+
+    fn parse_compare_op(&mut self) -> Option<CompareOp> {
+        for (text, op) in [
+            ("==", CompareOp::Eq),
+            ("!=", CompareOp::NotEq),
+            ("<=", CompareOp::LessEq),
+            (">=", CompareOp::GreaterEq),
+            ("<", CompareOp::Less),
+            (">", CompareOp::Greater),
+        ] {
+            if self.consume_str(text) {
+                return Some(op);
+            }
+        }
+        None
+    }
+
+    fn parse_number_literal(&mut self) -> Result<JsonValue, String> {
+        let start = self.pos;
+        if self.peek() == Some('-') {
+            self.pos += 1;
+        }
+        while let Some(c) = self.peek() {
+            if c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-') {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        text.parse::<f64>()
+            .map(JsonValue::Number)
+            .map_err(|_| format!("Invalid number literal '{}' at position {}", text, start))
+    }
+
+    fn parse_string_literal(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        let quote = self.next().ok_or_else(|| self.error("Expected string"))?;
+        let mut out = String::new();
+        loop {
+            match self.next() {
+                None => return Err(format!("Unterminated string at position {}", start)),
+                Some(c) if c == quote => return Ok(out),
+                Some('\\') => {
+                    let escaped = match self.peek() {
+                        Some('b') => '\x08',
+                        Some('f') => '\x0c',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('/') => '/',
+                        Some('\\') => '\\',
+                        Some(c) if c == quote => c,
+                        Some('u') => {
+                            self.pos += 1;
+                            out.push(self.parse_unicode_escape()?);
+                            continue;
+                        }
+                        _ => return Err(self.error("Invalid escape sequence")),
+                    };
+                    self.pos += 1;
+                    out.push(escaped);
+                }
+                Some(c) if (c as u32) < 0x20 => {
+                    self.pos -= 1;
+                    return Err(self.error("Control character in string"));
+                }
+                Some(c) => out.push(c),
+            }
+        }
+    }
+
+    fn parse_unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.parse_hex4()?;
+        if (0xD800..0xDC00).contains(&high) {
+            if !self.consume_str("\\u") {
+                return Err(self.error("Unpaired surrogate in unicode escape"));
+            }
+            let low = self.parse_hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("Invalid low surrogate in unicode escape"));
+            }
+            let code_point = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+            return char::from_u32(code_point).ok_or_else(|| self.error("Invalid unicode scalar"));
+        }
+        char::from_u32(high).ok_or_else(|| self.error("Invalid unicode scalar"))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, String> {
+        if self.pos + 4 > self.chars.len() {
+            return Err(self.error("Incomplete unicode escape"));
+        }
+        let hex: String = self.chars[self.pos..self.pos + 4].iter().collect();
+        let value = u32::from_str_radix(&hex, 16)
+            .map_err(|_| self.error(&format!("Invalid unicode escape: \\u{}", hex)))?;
+        self.pos += 4;
+        Ok(value)
+    }
+
+    fn is_name_first(c: char) -> bool {
+        c.is_ascii_alphabetic() || c == '_' || !c.is_ascii()
+    }
+
+    fn skip_blank(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn consume_str(&mut self, text: &str) -> bool {
+        let len = text.chars().count();
+        if self.pos + len <= self.chars.len()
+            && self.chars[self.pos..self.pos + len]
+                .iter()
+                .copied()
+                .eq(text.chars())
+        {
+            self.pos += len;
+            true
+        } else {
+            false
+        }
+    }
+
+    // keywords must not run into a following name character, e.g. `trueish`
+    fn consume_keyword(&mut self, keyword: &str) -> bool {
+        let saved = self.pos;
+        if self.consume_str(keyword)
+            && !matches!(self.peek(), Some(c) if Self::is_name_first(c) || c.is_ascii_digit())
+        {
+            return true;
+        }
+        self.pos = saved;
+        false
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("Expected '{}'", expected)))
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.pos += 1;
+        }
+        c
+    }
+
+    fn error(&self, message: &str) -> String {
+        match self.peek() {
+            Some(c) => format!("{}, found '{}' at position {}", message, c, self.pos),
+            None => format!("{}, found end of expression", message),
+        }
+    }
+}
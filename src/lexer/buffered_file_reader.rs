@@ -1,4 +1,5 @@
-use crate::lexer::{byte_reader::ByteReader, constants};
+use crate::core::span::Position;
+use crate::lexer::{byte_reader::ByteReader, constants, line_tracker::LineTracker};
 use memchr::memchr;
 use std::{
     fs::File,
@@ -10,6 +11,7 @@ pub struct BufferedFileReader {
     path: PathBuf,
     chunk_size: usize,
     offset: usize,
+    lines: LineTracker,
     reader: Option<BufReader<File>>,
     file_size: usize,
 }
@@ -26,8 +28,9 @@ impl BufferedFileReader {
                 .expect("Failed to get file metadata")
                 .len() as usize;
             Self {
-                path: path,
+                path,
                 offset: 0,
+                lines: LineTracker::new(),
                 chunk_size: constants::DEFAULT_CHUNK_SIZE_FILE,
                 reader: None,
                 file_size,
@@ -45,6 +48,10 @@ impl BufferedFileReader {
         self.chunk_size = chunk_size;
         self
     }
+
+    pub fn file_size(&self) -> usize {
+        self.file_size
+    }
 }
 
 impl ByteReader for BufferedFileReader {
@@ -56,9 +63,10 @@ impl ByteReader for BufferedFileReader {
         }
         let b = buff.unwrap()[0];
         self.reader.as_mut().unwrap().consume(1);
-        self.offset = self.offset + 1;
+        self.lines.advance(&[b]);
+        self.offset += 1;
 
-        return Ok(b);
+        Ok(b)
     }
 
     fn peek_byte(&mut self) -> Result<u8, String> {
@@ -67,10 +75,13 @@ impl ByteReader for BufferedFileReader {
         if buff.unwrap().is_empty() {
             return Err("The stream has ended".to_string());
         }
-        return Ok(buff.unwrap()[0]);
+        Ok(buff.unwrap()[0])
     }
     fn offset(&mut self) -> usize {
-        return self.offset;
+        self.offset
+    }
+    fn position(&mut self) -> Position {
+        Position::new(self.offset, self.lines.line(), self.lines.column())
     }
     fn next_chunk(&mut self) -> Result<Vec<u8>, String> {
         self.throw_if_consumed()?;
@@ -97,6 +108,7 @@ impl ByteReader for BufferedFileReader {
             }
 
             out.extend_from_slice(&buf[..n]);
+            self.lines.advance(&buf[..n]);
             reader.consume(n);
             self.offset += n;
         }
@@ -116,11 +128,13 @@ impl ByteReader for BufferedFileReader {
             let chunk = buff.unwrap()[..n].to_vec();
             if let Some(pos) = memchr(byte, &chunk) {
                 response_vector.extend_from_slice(&chunk[..pos]);
+                self.lines.advance(&chunk[..pos]);
                 self.reader.as_mut().unwrap().consume(pos);
                 self.offset += pos;
                 return Ok(response_vector);
             }
             response_vector.extend_from_slice(&chunk);
+            self.lines.advance(&chunk);
             self.reader.as_mut().unwrap().consume(n);
             self.offset += n;
         }
@@ -158,6 +172,7 @@ impl ByteReader for BufferedFileReader {
 
             if let Some(pos) = min_pos {
                 result.extend_from_slice(&chunk[..pos]);
+                self.lines.advance(&chunk[..pos]);
                 self.reader.as_mut().unwrap().consume(pos);
                 self.offset += pos;
                 return Ok(result);
@@ -165,6 +180,7 @@ impl ByteReader for BufferedFileReader {
 
             let n = chunk.len();
             result.extend_from_slice(chunk);
+            self.lines.advance(chunk);
             self.reader.as_mut().unwrap().consume(n);
             self.offset += n;
         }
@@ -182,6 +198,7 @@ impl ByteReader for BufferedFileReader {
             let b = buf[0];
             if b == b' ' || b == b'\n' || b == b'\t' || b == b'\r' {
                 reader.consume(1);
+                self.lines.advance(&[b]);
                 self.offset += 1;
             } else {
                 return;
@@ -195,7 +212,7 @@ impl ByteReader for BufferedFileReader {
 
         let reader = self.reader.as_mut().unwrap();
         match reader.fill_buf() {
-            Ok(buf) if buf.is_empty() => Err("Input file is consumed".to_string()),
+            Ok([]) => Err("Input file is consumed".to_string()),
             Ok(_) => Ok(()),
             Err(_) => Err("Error reading file".to_string()),
         }
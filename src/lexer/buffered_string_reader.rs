@@ -1,18 +1,21 @@
-use crate::lexer::{byte_reader::ByteReader, constants};
+use crate::core::span::Position;
+use crate::lexer::{byte_reader::ByteReader, constants, line_tracker::LineTracker};
 use memchr::memchr;
 
 pub struct BufferedStringReader {
     value: Vec<u8>,
     chunk_size: usize,
     offset: usize,
+    lines: LineTracker,
 }
 
 impl BufferedStringReader {
     pub fn new(value: Vec<u8>) -> Self {
         Self {
-            value: value,
+            value,
             chunk_size: constants::DEFAULT_CHUNK_SIZE_TEXT,
             offset: 0,
+            lines: LineTracker::new(),
         }
     }
 
@@ -25,6 +28,7 @@ impl ByteReader for BufferedStringReader {
     fn next_byte(&mut self) -> Result<u8, String> {
         self.throw_if_consumed()?;
         let res = self.value[self.offset];
+        self.lines.advance(&[res]);
         self.offset += 1;
         Ok(res)
     }
@@ -38,6 +42,7 @@ impl ByteReader for BufferedStringReader {
         self.throw_if_consumed()?;
         let end = (self.offset + self.chunk_size).min(self.value.len());
         let res = self.value[self.offset..end].to_vec();
+        self.lines.advance(&res);
         self.offset = end;
         Ok(res)
     }
@@ -46,6 +51,7 @@ impl ByteReader for BufferedStringReader {
         if let Some(pos) = memchr(byte, &self.value[self.offset..]) {
             let end = self.offset + pos;
             let res = self.value[self.offset..end].to_vec();
+            self.lines.advance(&res);
             self.offset = end;
             Ok(res)
         } else {
@@ -71,11 +77,13 @@ impl ByteReader for BufferedStringReader {
         if let Some(pos) = min_pos {
             let end = self.offset + pos;
             let result = self.value[self.offset..end].to_vec();
+            self.lines.advance(&result);
             self.offset = end;
             Ok(result)
         } else {
             // Delimiter not found, so consume till the end
             let result = self.value[self.offset..].to_vec();
+            self.lines.advance(&result);
             self.offset = self.value.len();
             Ok(result)
         }
@@ -85,6 +93,7 @@ impl ByteReader for BufferedStringReader {
         while self.offset < self.value.len() {
             let b = self.value[self.offset];
             if b == b' ' || b == b'\n' || b == b'\t' || b == b'\r' {
+                self.lines.advance(&[b]);
                 self.offset += 1;
             } else {
                 break;
@@ -93,7 +102,11 @@ impl ByteReader for BufferedStringReader {
     }
 
     fn offset(&mut self) -> usize {
-        return self.offset;
+        self.offset
+    }
+
+    fn position(&mut self) -> Position {
+        Position::new(self.offset, self.lines.line(), self.lines.column())
     }
     fn throw_if_consumed(&mut self) -> Result<(), String> {
         if (self.offset >= self.value.len()) {
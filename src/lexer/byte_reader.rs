@@ -1,3 +1,5 @@
+use crate::core::span::Position;
+
 pub trait ByteReader {
     fn next_byte(&mut self) -> Result<u8, String>;
     fn peek_byte(&mut self) -> Result<u8, String>;
@@ -6,5 +8,13 @@ pub trait ByteReader {
     fn next_until_any(&mut self, bytes: &[u8]) -> Result<Vec<u8>, String>;
     fn skip_white_space(&mut self);
     fn offset(&mut self) -> usize;
+    /// The offset together with the line and column of the next byte.
+    ///
+    /// Readers that do not track lines inherit this default, which places
+    /// every byte on line 1 with the column counted in bytes.
+    fn position(&mut self) -> Position {
+        let offset = self.offset();
+        Position::new(offset, 1, offset + 1)
+    }
     fn throw_if_consumed(&mut self) -> Result<(), String>;
 }
@@ -10,6 +10,12 @@ pub struct FSM {
     pub current_token_idx: usize,
 }
 
+impl Default for FSM {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FSM {
     pub fn new() -> Self {
         Self {
@@ -20,18 +26,19 @@ impl FSM {
             current_token_idx: 0,
         }
     }
+    /// The most recent token that is not trivia, among those still kept.
     pub fn last_token(&self) -> Option<Token> {
-        if (self.current_token_idx == 0) {
-            return None;
-        } else {
-            return Some(self.all_tokens[&(self.current_token_idx - 1)].clone());
-        }
+        (0..self.current_token_idx)
+            .rev()
+            .map_while(|idx| self.all_tokens.get(&idx))
+            .find(|token| !token.token_type().is_trivia())
+            .cloned()
     }
     pub fn current_token(&self) -> Option<Token> {
         if (self.current_token_idx == 0) {
-            return None;
+            None
         } else {
-            return Some(self.all_tokens[&(self.current_token_idx)].clone());
+            Some(self.all_tokens[&(self.current_token_idx)].clone())
         }
     }
 }
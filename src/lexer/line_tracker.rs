@@ -0,0 +1,50 @@
+/// Tracks the line and column of a reader as bytes are consumed.
+///
+/// `\n`, `\r\n` and a lone `\r` each end a line. Columns advance once per
+/// character, so UTF-8 continuation bytes are not counted.
+#[derive(Debug, Clone)]
+pub struct LineTracker {
+    line: usize,
+    column: usize,
+    after_cr: bool,
+}
+
+impl Default for LineTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LineTracker {
+    pub fn new() -> Self {
+        Self {
+            line: 1,
+            column: 1,
+            after_cr: false,
+        }
+    }
+
+    pub fn advance(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            match b {
+                // the line already ended at the preceding \r
+                b'\n' if self.after_cr => {}
+                b'\n' | b'\r' => {
+                    self.line += 1;
+                    self.column = 1;
+                }
+                _ if b & 0xC0 == 0x80 => {}
+                _ => self.column += 1,
+            }
+            self.after_cr = b == b'\r';
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
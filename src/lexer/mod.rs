@@ -3,4 +3,5 @@ pub mod buffered_string_reader;
 pub mod byte_reader;
 pub mod constants;
 pub mod fsm;
+pub mod line_tracker;
 pub mod tokenizer;
@@ -1,15 +1,29 @@
 use crate::{
-    core::{token::Token, tokentype::TokenType, tokentyperelationships::TokenTypeRelationShips},
+    core::{
+        parse_options::ParseOptions,
+        span::{Position, Span},
+        token::Token,
+        tokentype::TokenType,
+        tokentyperelationships::TokenTypeRelationShips,
+    },
     lexer::{
         buffered_file_reader::BufferedFileReader, buffered_string_reader::BufferedStringReader,
         byte_reader::ByteReader, fsm::FSM,
     },
+    parser::parser::Parser,
 };
 
-use std::path::Path;
+use std::{collections::VecDeque, path::Path};
 pub struct Tokenizer {
     reader: Box<dyn ByteReader>,
     pub fsm: FSM,
+    options: ParseOptions,
+    // where the token currently being read starts
+    token_start: Position,
+    // whitespace and comments read but not yet emitted, in trivia mode
+    pending_trivia: VecDeque<(TokenType, Span, String)>,
+    // whether string and number tokens carry their text
+    keep_values: bool,
 }
 
 // Things to remember
@@ -22,19 +36,15 @@ impl Tokenizer {
             (Some(_str), Some(_path)) => {
                 panic!("both json_string and file_path are supplied! choose one...");
             }
-            (Some(str), None) => Self {
-                reader: Box::new(BufferedStringReader::new(str.as_bytes().to_vec())),
-                fsm: FSM::new(),
-            },
+            (Some(str), None) => {
+                Self::from_reader(BufferedStringReader::new(str.as_bytes().to_vec()))
+            }
             (None, Some(path_string)) => {
                 let path = Path::new(&path_string);
                 if (!path.exists()) {
                     panic!("the file_path : {} doesnt exist", path_string);
                 } else {
-                    Self {
-                        reader: Box::new(BufferedFileReader::new(path.to_path_buf())),
-                        fsm: FSM::new(),
-                    }
+                    Self::from_reader(BufferedFileReader::new(path.to_path_buf()))
                 }
             }
             (None, None) => {
@@ -43,11 +53,44 @@ impl Tokenizer {
         }
     }
 
+    /// Tokenizes bytes from any reader, e.g. a `BufferedFileReader` with a
+    /// custom chunk size.
+    pub fn from_reader(reader: impl ByteReader + 'static) -> Self {
+        Self {
+            reader: Box::new(reader),
+            fsm: FSM::new(),
+            options: ParseOptions::default(),
+            token_start: Position::default(),
+            pending_trivia: VecDeque::new(),
+            keep_values: true,
+        }
+    }
+
+    pub fn with_options(mut self, options: ParseOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    /// Whether string and number tokens carry their text. Off while a value
+    /// is skipped: the text is still checked but no `String` is built for it.
+    pub(crate) fn set_keep_values(&mut self, keep: bool) {
+        self.keep_values = keep;
+    }
+
     pub fn next_token(&mut self) -> Result<Token, String> {
-        if self.fsm.current_token_idx == 0 {
-            return self.handle_first_last_token();
+        self.skip_ignored()?;
+        if let Some(token) = self.next_trivia() {
+            return Ok(token);
+        }
+
+        if self.fsm.last_token().is_none() {
+            self.handle_first_last_token()
         } else {
-            self.reader.skip_white_space();
+            self.token_start = self.reader.position();
             let seq = match self.reader.next_byte() {
                 Ok(b) => b,
                 Err(_) => {
@@ -55,7 +98,8 @@ impl Tokenizer {
                         TokenType::EOF,
                         self.reader.offset(),
                         self.fsm.current_token_idx,
-                    );
+                    )
+                    .with_span(self.span());
                     self.fsm
                         .all_tokens
                         .insert(self.fsm.current_token_idx, token.clone());
@@ -63,39 +107,43 @@ impl Tokenizer {
                 }
             };
 
+            if self.options.json5()
+                && let Some(token) = self.handle_json5(seq)
+            {
+                return token;
+            }
+
             if (!TokenType::is_single_byte_token(seq) && seq != b'"') {
-                self.reader.skip_white_space();
-                return match seq {
+                self.skip_inner_white_space();
+                match seq {
                     b't' | b'f' | b'T' | b'F' => self.handle_boolean(seq),
                     b'n' | b'N' => self.handle_null(seq),
                     b'0'..=b'9' => self.handle_number(seq),
                     _ => self.handle_invalid(seq),
-                };
+                }
             } else if (seq == b'"') {
-                return self.handle_string();
+                self.handle_string(seq)
             } else {
                 let token = Token::new(
                     TokenType::get_token_type_from_byte(seq),
                     self.reader.offset() - 1,
                     self.fsm.current_token_idx,
-                );
+                )
+                .with_span(self.span());
 
-                TokenTypeRelationShips::is_valid_token_sequence(
-                    self.fsm.last_token().as_ref(),
-                    Some(&token),
-                )?;
+                self.check_sequence(&token)?;
                 self.fsm
                     .all_tokens
                     .insert(self.fsm.current_token_idx, token.clone());
                 self.fsm.current_token_idx += 1;
-                return Ok(token);
+                Ok(token)
             }
         }
     }
 
     fn handle_first_last_token(&mut self) -> Result<Token, String> {
-        if self.fsm.current_token_idx == 0 {
-            self.reader.skip_white_space();
+        if self.fsm.last_token().is_none() {
+            self.token_start = self.reader.position();
             let byte = self.reader.next_byte()?;
             let token_type = TokenType::get_token_type_from_byte(byte);
 
@@ -104,7 +152,8 @@ impl Tokenizer {
                     token_type,
                     self.reader.offset() - 1,
                     self.fsm.current_token_idx,
-                );
+                )
+                .with_span(self.span());
                 self.fsm
                     .all_tokens
                     .insert(self.fsm.current_token_idx, token.clone());
@@ -113,6 +162,12 @@ impl Tokenizer {
                 return Ok(token);
             }
 
+            if self.options.json5()
+                && let Some(token) = self.handle_json5(byte)
+            {
+                return token;
+            }
+
             match byte {
                 b'"' => {
                     let start_pos = self.reader.offset() - 1;
@@ -137,13 +192,9 @@ impl Tokenizer {
                             break;
                         }
                     }
-                    let s = String::from_utf8(output).map_err(|e| e.to_string())?;
-                    let token = Token::with_value(
-                        TokenType::Text,
-                        start_pos,
-                        self.fsm.current_token_idx,
-                        s,
-                    );
+                    let token = self
+                        .value_token(TokenType::Text, start_pos, output)?
+                        .with_span(self.span());
                     self.fsm
                         .all_tokens
                         .insert(self.fsm.current_token_idx, token.clone());
@@ -159,8 +210,21 @@ impl Tokenizer {
 
         Err("Invalid state".to_string())
     }
+
+    /// Reads the JSON5 tokens starting with `first_char` that plain JSON does
+    /// not have, or reads differently: single-quoted strings, numbers with a
+    /// sign or leading point, and identifiers.
+    fn handle_json5(&mut self, first_char: u8) -> Option<Result<Token, String>> {
+        match first_char {
+            b'\'' => Some(self.handle_string(first_char)),
+            b'+' | b'-' | b'.' | b'0'..=b'9' => Some(self.handle_number(first_char)),
+            _ if Self::is_identifier_byte(first_char) => Some(self.handle_identifier(first_char)),
+            _ => None,
+        }
+    }
+
     fn handle_boolean(&mut self, first_char: u8) -> Result<Token, String> {
-        self.reader.skip_white_space();
+        self.skip_inner_white_space();
         let start_pos = self.reader.offset() - 1;
 
         let mut bytes = vec![first_char];
@@ -177,7 +241,8 @@ impl Tokenizer {
 
         if s == "true" || s == "false" {
             let token =
-                Token::with_value(TokenType::Boolean, start_pos, self.fsm.current_token_idx, s);
+                Token::with_value(TokenType::Boolean, start_pos, self.fsm.current_token_idx, s)
+                    .with_span(self.span());
             self.fsm
                 .all_tokens
                 .insert(self.fsm.current_token_idx, token.clone());
@@ -189,7 +254,7 @@ impl Tokenizer {
     }
 
     fn handle_null(&mut self, first_char: u8) -> Result<Token, String> {
-        self.reader.skip_white_space();
+        self.skip_inner_white_space();
         let start_pos = self.reader.offset() - 1;
 
         let mut bytes = vec![first_char];
@@ -206,7 +271,8 @@ impl Tokenizer {
 
         if s == "null" {
             let token =
-                Token::with_value(TokenType::Null, start_pos, self.fsm.current_token_idx, s);
+                Token::with_value(TokenType::Null, start_pos, self.fsm.current_token_idx, s)
+                    .with_span(self.span());
             self.fsm
                 .all_tokens
                 .insert(self.fsm.current_token_idx, token.clone());
@@ -218,14 +284,26 @@ impl Tokenizer {
     }
 
     fn handle_number(&mut self, first_char: u8) -> Result<Token, String> {
-        self.reader.skip_white_space();
+        self.skip_inner_white_space();
         let start_pos = self.reader.offset() - 1;
-        let mut bytes = self.reader.next_until_any(&[b',', b']', b'}'])?;
+        // whitespace ends a number so its span stops at the last digit, and a
+        // comment may directly follow one
+        let delimiters: &[u8] = if self.comments_enabled() {
+            b",]} \t\r\n/"
+        } else {
+            b",]} \t\r\n"
+        };
+        // the end of the input also ends a number, e.g. a one-digit document
+        let mut bytes = if self.reader.peek_byte().is_ok() {
+            self.reader.next_until_any(delimiters)?
+        } else {
+            Vec::new()
+        };
         bytes.insert(0, first_char);
 
-        let s = String::from_utf8_lossy(&bytes).trim().to_string();
-
-        let token = Token::with_value(TokenType::Number, start_pos, self.fsm.current_token_idx, s);
+        let token = self
+            .value_token(TokenType::Number, start_pos, bytes)?
+            .with_span(self.span());
         self.fsm
             .all_tokens
             .insert(self.fsm.current_token_idx, token.clone());
@@ -233,12 +311,12 @@ impl Tokenizer {
         Ok(token)
     }
 
-    fn handle_string(&mut self) -> Result<Token, String> {
+    fn handle_string(&mut self, quote: u8) -> Result<Token, String> {
         let start_pos = self.reader.offset() - 1;
         let mut output: Vec<u8> = Vec::new();
 
         loop {
-            let b = self.reader.next_until(b'"')?;
+            let b = self.reader.next_until(quote)?;
             output.extend_from_slice(&b);
 
             // We are at '"'. Consume it.
@@ -265,21 +343,105 @@ impl Tokenizer {
                 // Escaped. The quote is part of the string.
                 // We shouldn't have consumed it? No, we consumed it from reader.
                 // We need to add it to output.
-                output.push(b'"');
+                output.push(quote);
             } else {
                 // Not escaped. This is the closing quote.
                 break;
             }
         }
 
-        let s = String::from_utf8(output).map_err(|e| e.to_string())?;
-        let token = Token::with_value(TokenType::Text, start_pos, self.fsm.current_token_idx, s);
+        let token = self
+            .value_token(TokenType::Text, start_pos, output)?
+            .with_span(self.span());
+
+        self.check_sequence(&token)?;
+
+        self.fsm
+            .all_tokens
+            .insert(self.fsm.current_token_idx, token.clone());
+        self.fsm.current_token_idx += 1;
+        Ok(token)
+    }
+
+    // a string or number token, holding its text unless values are skipped
+    fn value_token(
+        &self,
+        token_type: TokenType,
+        start_pos: usize,
+        bytes: Vec<u8>,
+    ) -> Result<Token, String> {
+        let token_idx = self.fsm.current_token_idx;
+        if token_type == TokenType::Number {
+            let s = String::from_utf8_lossy(&bytes);
+            let s = s.trim();
+            if self.keep_values {
+                return Ok(Token::with_value(
+                    token_type,
+                    start_pos,
+                    token_idx,
+                    s.to_string(),
+                ));
+            }
+            let valid = if self.options.json5() {
+                Parser::parse_json5_number(s).is_some()
+            } else {
+                s.parse::<f64>().is_ok()
+            };
+            return if valid {
+                Ok(Token::new(token_type, start_pos, token_idx))
+            } else {
+                Err("Invalid number format".to_string())
+            };
+        }
+        if self.keep_values {
+            let s = String::from_utf8(bytes).map_err(|e| e.to_string())?;
+            Ok(Token::with_value(token_type, start_pos, token_idx, s))
+        } else {
+            let text = std::str::from_utf8(&bytes).map_err(|e| e.to_string())?;
+            // only escapes can make the text invalid
+            if text.contains('\\') {
+                Parser::unescape_string(text, self.options.json5())?;
+            }
+            Ok(Token::new(token_type, start_pos, token_idx))
+        }
+    }
+
+    /// Reads a JSON5 identifier. It is a key when a colon follows, otherwise it
+    /// must be one of the literals `true`, `false`, `null`, `Infinity` or `NaN`.
+    fn handle_identifier(&mut self, first_char: u8) -> Result<Token, String> {
+        let start_pos = self.reader.offset() - 1;
+        let mut bytes = vec![first_char];
+        while let Ok(b) = self.reader.peek_byte() {
+            if Self::is_identifier_byte(b) || b.is_ascii_digit() {
+                bytes.push(b);
+                let _ = self.reader.next_byte();
+            } else {
+                break;
+            }
+        }
+        let word = String::from_utf8(bytes).map_err(|e| e.to_string())?;
+        let span = self.span();
 
-        TokenTypeRelationShips::is_valid_token_sequence(
-            self.fsm.last_token().as_ref(),
-            Some(&token),
-        )?;
+        self.skip_ignored()?;
+        let token_type = if self.reader.peek_byte() == Ok(b':') {
+            TokenType::Text
+        } else {
+            match word.as_str() {
+                "true" | "false" => TokenType::Boolean,
+                "null" => TokenType::Null,
+                "Infinity" | "NaN" => TokenType::Number,
+                _ => {
+                    return Err(format!(
+                        "Unexpected identifier '{}' at position {}",
+                        word, start_pos
+                    ));
+                }
+            }
+        };
 
+        let token = Token::with_value(token_type, start_pos, self.fsm.current_token_idx, word)
+            .with_span(span);
+        self.check_sequence(&token)?;
         self.fsm
             .all_tokens
             .insert(self.fsm.current_token_idx, token.clone());
@@ -287,12 +449,159 @@ impl Tokenizer {
         Ok(token)
     }
 
+    /// The span from the start of the current token to the reader's position.
+    fn span(&mut self) -> Span {
+        Span::new(self.token_start, self.reader.position())
+    }
+
+    // ASCII letters, `_`, `$` and any byte of a multi-byte UTF-8 character
+    fn is_identifier_byte(b: u8) -> bool {
+        b.is_ascii_alphabetic() || b == b'_' || b == b'$' || b >= 0x80
+    }
+
+    fn comments_enabled(&self) -> bool {
+        self.options.allow_comments() || self.options.json5()
+    }
+
+    /// Validates `token` against the previous one; JSON5 additionally allows a
+    /// trailing comma before `}`.
+    fn check_sequence(&self, token: &Token) -> Result<(), String> {
+        let last = self.fsm.last_token();
+        // which tokens may come first is decided by `handle_first_last_token`
+        if last.is_none() {
+            return Ok(());
+        }
+        let trailing_comma = last
+            .as_ref()
+            .is_some_and(|t| t.token_type() == TokenType::Comma)
+            && token.token_type() == TokenType::RightBrace;
+        if !(self.options.json5() && trailing_comma) {
+            TokenTypeRelationShips::is_valid_token_sequence(last.as_ref(), Some(token))?;
+        }
+        Ok(())
+    }
+
+    /// Skips whitespace and, when comments are allowed, any comments between
+    /// tokens. In trivia mode they are queued to be emitted as tokens.
+    fn skip_ignored(&mut self) -> Result<(), String> {
+        loop {
+            self.skip_white_space()?;
+            if !self.comments_enabled() || self.reader.peek_byte() != Ok(b'/') {
+                return Ok(());
+            }
+
+            let start = self.reader.position();
+            let start_pos = start.offset();
+            let mut text = vec![self.reader.next_byte()?];
+            match self.reader.next_byte() {
+                Ok(b'/') => {
+                    text.push(b'/');
+                    // runs to the end of the line, or of the input
+                    if self.reader.peek_byte().is_ok() {
+                        text.extend(self.reader.next_until_any(b"\n")?);
+                    }
+                }
+                Ok(b'*') => {
+                    text.push(b'*');
+                    loop {
+                        match self.reader.next_until(b'*') {
+                            Ok(bytes) => text.extend(bytes),
+                            Err(_) => {
+                                return Err(format!(
+                                    "Unterminated block comment starting at position {}",
+                                    start_pos
+                                ));
+                            }
+                        }
+                        text.push(self.reader.next_byte()?);
+                        if self.reader.peek_byte() == Ok(b'/') {
+                            text.push(self.reader.next_byte()?);
+                            break;
+                        }
+                    }
+                }
+                _ => return Err(format!("Invalid comment at position {}", start_pos)),
+            }
+            self.queue_trivia(TokenType::Comment, start, text)?;
+        }
+    }
+
+    /// Skips whitespace. In trivia mode runs of spaces and tabs become one
+    /// token each, and every `\r` and `\n` its own token.
+    fn skip_white_space(&mut self) -> Result<(), String> {
+        if !self.options.emit_trivia() {
+            self.reader.skip_white_space();
+            return Ok(());
+        }
+
+        while let Ok(b) = self.reader.peek_byte() {
+            let token_type = match b {
+                b' ' => TokenType::WhiteSpace,
+                b'\t' => TokenType::Tab,
+                b'\n' => TokenType::NewLine,
+                b'\r' => TokenType::CarriageReturn,
+                _ => break,
+            };
+            let start = self.reader.position();
+            let mut text = vec![self.reader.next_byte()?];
+            while matches!(b, b' ' | b'\t') && self.reader.peek_byte() == Ok(b) {
+                text.push(self.reader.next_byte()?);
+            }
+            self.queue_trivia(token_type, start, text)?;
+        }
+        Ok(())
+    }
+
+    // whitespace straight after the first byte of a literal is skipped, except
+    // in trivia mode where it is emitted as its own token
+    fn skip_inner_white_space(&mut self) {
+        if !self.options.emit_trivia() {
+            self.reader.skip_white_space();
+        }
+    }
+
+    fn queue_trivia(
+        &mut self,
+        token_type: TokenType,
+        start: Position,
+        text: Vec<u8>,
+    ) -> Result<(), String> {
+        if self.options.emit_trivia() {
+            let text = String::from_utf8(text).map_err(|e| e.to_string())?;
+            let span = Span::new(start, self.reader.position());
+            self.pending_trivia.push_back((token_type, span, text));
+        }
+        Ok(())
+    }
+
+    /// Emits the oldest queued trivia token, if any.
+    fn next_trivia(&mut self) -> Option<Token> {
+        let (token_type, span, text) = self.pending_trivia.pop_front()?;
+        let token = Token::with_value(token_type, span.start(), self.fsm.current_token_idx, text)
+            .with_span(span);
+        self.fsm
+            .all_tokens
+            .insert(self.fsm.current_token_idx, token.clone());
+        self.fsm.current_token_idx += 1;
+        Some(token)
+    }
+
     fn handle_invalid(&mut self, first_char: u8) -> Result<Token, String> {
         Err(format!(
             "Invalid token starting with: {}",
             first_char as char
         ))
     }
+    /// Forgets the tokens read so far except the last one, which the next
+    /// token is checked against, so a caller streaming through a large input
+    /// keeps memory bounded.
+    pub fn release_tokens(&mut self) {
+        if let Some(last) = self.fsm.last_token() {
+            let idx = last.token_idx();
+            self.fsm.all_tokens.retain(|&i, _| i >= idx);
+        }
+    }
+
     pub fn tokenize(&mut self) -> Result<(), String> {
         loop {
             let token = self.next_token()?;
@@ -301,7 +610,7 @@ impl Tokenizer {
                 break;
             }
         }
-        return Ok(());
+        Ok(())
     }
 }
 
@@ -309,10 +618,10 @@ impl Iterator for Tokenizer {
     type Item = Result<Token, String>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(token) = self.fsm.all_tokens.get(&self.fsm.current_token_idx) {
-            if token.token_type() == TokenType::EOF {
-                return None;
-            }
+        if let Some(token) = self.fsm.all_tokens.get(&self.fsm.current_token_idx)
+            && token.token_type() == TokenType::EOF
+        {
+            return None;
         }
         Some(self.next_token())
     }
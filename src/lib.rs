@@ -1,29 +1,123 @@
 #![allow(unused_parens)]
+pub mod codegen;
+pub mod convert;
 pub mod core;
+pub mod cst;
+pub mod diff;
+pub mod jsonpath;
 pub mod lexer;
+mod macros;
 pub mod parser;
+pub mod patch;
+pub mod schema;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod stream;
 
-use std::path::Path;
+#[cfg(feature = "derive")]
+pub use parse_light_derive::{FromJson, ToJson};
+
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
 
 use crate::core::json_value::JsonValue;
+use crate::core::parse_options::ParseOptions;
+use crate::core::stringify_options::StringifyOptions;
 use crate::lexer::tokenizer::Tokenizer;
 use crate::parser::parser::Parser;
+use crate::parser::partial::{PartialPolicy, PartialValue};
+use crate::parser::repair::Fix;
+use crate::parser::source_map::SourceMap;
 
 /// Parses a valid JSON string into a `JsonValue`.
 pub fn parse(json: &str) -> Result<JsonValue, String> {
-    let tokenizer: Tokenizer;
-    if Path::exists(Path::new(json)) {
-        tokenizer = Tokenizer::new(None, Some(json.to_string()))
-       
+    parse_with(json, &ParseOptions::default())
+}
+
+/// Parses a JSON string, or the file at that path, with the given options.
+pub fn parse_with(json: &str, options: &ParseOptions) -> Result<JsonValue, String> {
+    parser_for(json, options)?.parse()
+}
+
+/// Like `parse_with`, also returning the source location of every value.
+pub fn parse_with_source_map(
+    json: &str,
+    options: &ParseOptions,
+) -> Result<(JsonValue, SourceMap), String> {
+    parser_for(json, options)?.parse_with_source_map()
+}
+
+/// Like `parse_with`, keeping the values at `pointers` as unparsed
+/// `RawValue`s; see `Parser::parse_with_raw`.
+pub fn parse_with_raw(
+    json: &str,
+    options: &ParseOptions,
+    pointers: &[&str],
+) -> Result<JsonValue, String> {
+    // the raw text must come from the same input the parser reads
+    let from_file = is_path(json);
+    parser_from(json, from_file, options)?.parse_with_raw(pointers, |start, end| {
+        source_text(json, from_file, start, end)
+    })
+}
+
+/// Parses JSON that may have been cut off, completing whatever is still
+/// open; see `parser::partial::parse_partial`.
+pub fn parse_partial(json: &str, policy: &PartialPolicy) -> Result<PartialValue, String> {
+    parser::partial::parse_partial(json, policy)
+}
+
+/// Fixes common mistakes in hand-edited JSON; see `parser::repair::repair`.
+pub fn repair(input: &str) -> (String, Vec<Fix>) {
+    parser::repair::repair(input)
+}
+
+// `json` names a file when one exists at that path
+fn is_path(json: &str) -> bool {
+    Path::exists(Path::new(json))
+}
+
+fn parser_for(json: &str, options: &ParseOptions) -> Result<Parser, String> {
+    parser_from(json, is_path(json), options)
+}
+
+fn parser_from(json: &str, from_file: bool, options: &ParseOptions) -> Result<Parser, String> {
+    let tokenizer = if from_file {
+        Tokenizer::new(None, Some(json.to_string()))
     } else {
-         tokenizer = Tokenizer::new(Some(json.to_string()), None);
+        Tokenizer::new(Some(json.to_string()), None)
     }
+    .with_options(*options);
 
-    let mut parser = Parser::new(tokenizer)?;
-    parser.parse()
+    Parser::new(tokenizer)
+}
+
+// The text of bytes `start..end` of `json`, or of the file it names, reading
+// only that range of the file.
+fn source_text(json: &str, from_file: bool, start: usize, end: usize) -> Result<String, String> {
+    if !from_file {
+        return json
+            .get(start..end)
+            .map(str::to_string)
+            .ok_or_else(|| format!("Invalid source range {}..{}", start, end));
+    }
+    let mut file = File::open(json).map_err(|e| e.to_string())?;
+    file.seek(SeekFrom::Start(start as u64))
+        .map_err(|e| e.to_string())?;
+    let mut bytes = vec![0; end - start];
+    file.read_exact(&mut bytes).map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
 }
 
 /// Converts a `JsonValue` back into a valid JSON string.
 pub fn stringify(value: &JsonValue) -> String {
     value.stringify()
 }
+
+/// Converts a `JsonValue` into a JSON string using the given escaping options.
+pub fn stringify_with(value: &JsonValue, options: &StringifyOptions) -> String {
+    value.stringify_with(options)
+}
@@ -0,0 +1,84 @@
+/// Builds a `JsonValue` from JSON-like syntax.
+///
+/// Values may be `null`, nested arrays and objects, or any Rust expression with a
+/// `From` conversion into `JsonValue`. Object keys are string literals, or any
+/// expression implementing `ToString` when wrapped in parentheses.
+///
+/// ```
+/// use parse_light::json;
+///
+/// let tags = vec!["b", "c"];
+/// let value = json!({"name": "parse_light", "tags": ["a", tags], "n": 3, "none": null});
+/// assert_eq!(value["n"].as_f64(), Some(3.0));
+/// ```
+#[macro_export]
+macro_rules! json {
+    // Array elements, munched one at a time into `[$elems,]`
+    (@array [$($elems:expr,)*]) => {
+        vec![$($elems,)*]
+    };
+    (@array [$($elems:expr,)*] null $(, $($rest:tt)*)?) => {
+        $crate::json!(@array [$($elems,)* $crate::json!(null),] $($($rest)*)?)
+    };
+    (@array [$($elems:expr,)*] [$($array:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::json!(@array [$($elems,)* $crate::json!([$($array)*]),] $($($rest)*)?)
+    };
+    (@array [$($elems:expr,)*] {$($object:tt)*} $(, $($rest:tt)*)?) => {
+        $crate::json!(@array [$($elems,)* $crate::json!({$($object)*}),] $($($rest)*)?)
+    };
+    (@array [$($elems:expr,)*] $next:expr, $($rest:tt)*) => {
+        $crate::json!(@array [$($elems,)* $crate::json!($next),] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] $last:expr) => {
+        $crate::json!(@array [$($elems,)* $crate::json!($last),])
+    };
+
+    // Object members, inserted into `$map` one `key: value` pair at a time
+    (@object $map:ident) => {};
+    (@object $map:ident $key:literal : $($rest:tt)*) => {
+        $crate::json!(@value $map ($key) $($rest)*);
+    };
+    (@object $map:ident ($key:expr) : $($rest:tt)*) => {
+        $crate::json!(@value $map ($key) $($rest)*);
+    };
+    (@value $map:ident ($key:expr) null $(, $($rest:tt)*)?) => {
+        $map.insert(($key).to_string(), $crate::json!(null));
+        $crate::json!(@object $map $($($rest)*)?);
+    };
+    (@value $map:ident ($key:expr) [$($array:tt)*] $(, $($rest:tt)*)?) => {
+        $map.insert(($key).to_string(), $crate::json!([$($array)*]));
+        $crate::json!(@object $map $($($rest)*)?);
+    };
+    (@value $map:ident ($key:expr) {$($object:tt)*} $(, $($rest:tt)*)?) => {
+        $map.insert(($key).to_string(), $crate::json!({$($object)*}));
+        $crate::json!(@object $map $($($rest)*)?);
+    };
+    (@value $map:ident ($key:expr) $value:expr, $($rest:tt)*) => {
+        $map.insert(($key).to_string(), $crate::json!($value));
+        $crate::json!(@object $map $($rest)*);
+    };
+    (@value $map:ident ($key:expr) $value:expr) => {
+        $map.insert(($key).to_string(), $crate::json!($value));
+    };
+
+    (null) => {
+        $crate::core::json_value::JsonValue::Null
+    };
+    ([]) => {
+        $crate::core::json_value::JsonValue::Array(vec![])
+    };
+    ([ $($tt:tt)+ ]) => {
+        $crate::core::json_value::JsonValue::Array($crate::json!(@array [] $($tt)+))
+    };
+    ({}) => {
+        $crate::core::json_value::JsonValue::Object(::std::collections::HashMap::new())
+    };
+    ({ $($tt:tt)+ }) => {{
+        let mut map = ::std::collections::HashMap::new();
+        $crate::json!(@object map $($tt)+);
+        $crate::core::json_value::JsonValue::Object(map)
+    }};
+    ($other:expr) => {
+        $crate::core::json_value::JsonValue::from($other)
+    };
+}
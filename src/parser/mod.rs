@@ -1 +1,6 @@
+#[allow(clippy::module_inception)]
 pub mod parser;
+pub mod partial;
+pub mod repair;
+pub mod source_map;
+pub mod value_builder;
@@ -1,20 +1,27 @@
 use crate::{
-    core::{json_value::JsonValue, token::Token, tokentype::TokenType},
+    core::{
+        json_pointer::parse_pointer, json_value::JsonValue, parse_options::ParseOptions,
+        raw_value::RawValue, token::Token, tokentype::TokenType,
+    },
     lexer::tokenizer::Tokenizer,
+    parser::{source_map::SourceMap, value_builder::ValueBuilder},
 };
-use std::collections::HashMap;
 
 pub struct Parser {
     tokens: Vec<Token>,
     current_idx: usize,
+    options: ParseOptions,
 }
 
-enum Container {
-    Object(HashMap<String, JsonValue>, Option<String>), // Map, Pending Key
-    Array(Vec<JsonValue>),
+// the values `Parser::parse_with_raw` keeps as source text, and where to
+// read that text from
+struct RawPointers<'a> {
+    pointers: &'a [Vec<String>],
+    source: &'a mut dyn FnMut(usize, usize) -> Result<String, String>,
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq)]
+#[allow(clippy::enum_variant_names)]
 enum ParserState {
     ExpectValue,
     ExpectKey,
@@ -23,260 +30,172 @@ enum ParserState {
 }
 
 impl Parser {
+    /// Tokenizes the whole input up front; the tokenizer's options also decide
+    /// which JSON5 extensions the parser accepts.
     pub fn new(mut tokenizer: Tokenizer) -> Result<Self, String> {
+        let options = *tokenizer.options();
         tokenizer.tokenize()?;
-        let mut tokens: Vec<Token> = tokenizer
-            .fsm
-            .all_tokens
-            .iter()
-            .map(|(_, v)| v.clone())
-            .collect();
+        let mut tokens: Vec<Token> = tokenizer.fsm.all_tokens.values().cloned().collect();
         tokens.sort_by_key(|t| t.token_idx());
 
-        Ok(Self {
+        Ok(Self::from_tokens(tokens, options))
+    }
+
+    /// Builds a parser over already tokenized input, ignoring any trivia.
+    pub(crate) fn from_tokens(tokens: Vec<Token>, options: ParseOptions) -> Self {
+        let tokens = tokens
+            .into_iter()
+            .filter(|t| !t.token_type().is_trivia())
+            .collect();
+        Self {
             tokens,
             current_idx: 0,
-        })
+            options,
+        }
     }
 
     pub fn parse(&mut self) -> Result<JsonValue, String> {
+        self.parse_document(None)
+    }
+
+    /// Parses the document, keeping the values at `pointers` as `RawValue`s.
+    /// `source` returns the text of a byte range of the tokenized input and
+    /// is only asked for the raw values, so the rest of the input need not
+    /// be kept. Raw values are checked like any other but not built, so a
+    /// large subtree that is only passed on costs little and keeps its exact
+    /// formatting.
+    pub fn parse_with_raw(
+        &mut self,
+        pointers: &[&str],
+        mut source: impl FnMut(usize, usize) -> Result<String, String>,
+    ) -> Result<JsonValue, String> {
+        let pointers = pointers
+            .iter()
+            .map(|pointer| parse_pointer(pointer))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.parse_document(Some(RawPointers {
+            pointers: &pointers,
+            source: &mut source,
+        }))
+    }
+
+    fn parse_document(&mut self, mut raw: Option<RawPointers>) -> Result<JsonValue, String> {
         if self.tokens.is_empty() {
             return Ok(JsonValue::Null);
         }
 
-        let mut stack: Vec<Container> = Vec::new();
-        let mut state = ParserState::ExpectValue;
-        let mut root_value: Option<JsonValue> = None;
-
-        while self.current_idx < self.tokens.len() {
-            let token = &self.tokens[self.current_idx];
-            let token_type = token.token_type();
-
-            match state {
-                ParserState::ExpectValue => {
-                    match token_type {
-                        TokenType::LeftBrace => {
-                            stack.push(Container::Object(HashMap::new(), None));
-                            state = ParserState::ExpectKey;
-                            self.advance();
-                        }
-                        TokenType::LeftSquareBracket => {
-                            stack.push(Container::Array(Vec::new()));
-                            state = ParserState::ExpectValue; // Array expects value (or end) next
-                            self.advance();
-                        }
-                        TokenType::Text
-                        | TokenType::Number
-                        | TokenType::Boolean
-                        | TokenType::Null => {
-                            let val = self.token_to_value(token)?;
-                            self.advance();
-                            self.insert_value(&mut stack, &mut root_value, val)?;
-                            state = ParserState::ExpectCommaOrEnd;
-                            if stack.is_empty() {
-                                // Done.
-                            }
-                        }
-                        TokenType::RightSquareBracket => {
-                            // Valid only if we are in an Array and it's empty "[]"
-                            if let Some(Container::Array(vec)) = stack.last() {
-                                if vec.is_empty() {
-                                    let container = stack.pop().unwrap();
-                                    let val = self.container_to_value(container);
-                                    self.insert_value(&mut stack, &mut root_value, val)?;
-                                    self.advance();
-                                    state = ParserState::ExpectCommaOrEnd;
-                                } else {
-                                    return Err("Trailing comma in array".to_string());
-                                }
-                            } else {
-                                return Err(format!("Unexpected token: {:?}", token_type));
-                            }
-                        }
-                        // RightBrace handled in ExpectKey for empty object
-                        TokenType::RightBrace => {
-                            if let Some(Container::Object(_, _)) = stack.last() {
-                                return Err(format!("Unexpected token: {:?}", token_type));
-                            } else {
-                                return Err(format!("Unexpected token: {:?}", token_type));
-                            }
-                        }
-                        _ => return Err(format!("Unexpected token: {:?}", token_type)),
-                    }
-                }
-                ParserState::ExpectKey => {
-                    match token_type {
-                        TokenType::Text => {
-                            let key = self.extract_string_value(token)?;
-                            if let Some(Container::Object(_, pending_key)) = stack.last_mut() {
-                                *pending_key = Some(key);
-                            } else {
-                                return Err(
-                                    "Internal Error: ExpectKey but not in Object".to_string()
-                                );
-                            }
-                            self.advance();
-                            state = ParserState::ExpectColon;
-                        }
-                        TokenType::RightBrace => {
-                            // Empty object or End of object
-                            if let Some(Container::Object(map, _)) = stack.last() {
-                                if map.is_empty() {
-                                    let container = stack.pop().unwrap();
-                                    let val = self.container_to_value(container);
-                                    self.insert_value(&mut stack, &mut root_value, val)?;
-                                    self.advance();
-                                    state = ParserState::ExpectCommaOrEnd;
-                                } else {
-                                    return Err("Trailing comma in object".to_string());
-                                }
-                            } else {
-                                return Err(format!("Unexpected token: {:?}", token_type));
-                            }
-                        }
-                        _ => {
-                            return Err(format!(
-                                "Expected Object Key or '}}', found {:?}",
-                                token_type
-                            ));
-                        }
-                    }
-                }
-                ParserState::ExpectColon => match token_type {
-                    TokenType::Colon => {
-                        self.advance();
-                        state = ParserState::ExpectValue;
-                    }
-                    _ => return Err(format!("Expected ':', found {:?}", token_type)),
-                },
-                ParserState::ExpectCommaOrEnd => {
-                    match token_type {
-                        TokenType::Comma => {
-                            self.advance();
-                            // If in Object -> ExpectKey.
-                            // If in Array -> ExpectValue.
-                            // If Root -> Error (Trailing comma not allowed at root, nor multiple values).
-                            if let Some(container) = stack.last() {
-                                match container {
-                                    Container::Object(_, _) => state = ParserState::ExpectKey,
-                                    Container::Array(_) => state = ParserState::ExpectValue,
-                                }
-                            } else {
-                                return Err("Unexpected comma at root".to_string());
-                            }
-                        }
-                        TokenType::RightBrace => {
-                            if let Some(Container::Object(_, _)) = stack.last() {
-                                let container = stack.pop().unwrap();
-                                let val = self.container_to_value(container);
-                                self.insert_value(&mut stack, &mut root_value, val)?;
-                                self.advance();
-                                // Stay in ExpectCommaOrEnd because we just finished a value (the object)
-                            } else {
-                                return Err(format!("Unexpected '}}', found {:?}", token_type));
-                            }
-                        }
-                        TokenType::RightSquareBracket => {
-                            if let Some(Container::Array(_)) = stack.last() {
-                                let container = stack.pop().unwrap();
-                                let val = self.container_to_value(container);
-                                self.insert_value(&mut stack, &mut root_value, val)?;
-                                self.advance();
-                                // Stay in ExpectCommaOrEnd
-                            } else {
-                                return Err(format!("Unexpected ']', found {:?}", token_type));
-                            }
-                        }
-                        TokenType::EOF => {
-                            if stack.is_empty() {
-                                break;
-                            } else {
-                                return Err("Unexpected EOF".to_string());
-                            }
-                        }
-                        _ => {
-                            return Err(format!(
-                                "Expected ',' or '}}' or ']', found {:?}",
-                                token_type
-                            ));
-                        }
-                    }
-                }
-            }
-
-            // Allow one iteration to process insertion which might empty stack
-            if stack.is_empty() && root_value.is_some() && state == ParserState::ExpectCommaOrEnd {
-                // Check next token is EOF
-                if self.current_idx < self.tokens.len() {
-                    let next = &self.tokens[self.current_idx];
-                    if next.token_type() != TokenType::EOF {
-                        return Err(format!(
-                            "Unexpected token after root value: {:?}",
-                            next.token_type()
-                        ));
-                    }
-                }
+        let mut builder = ValueBuilder::new(self.options.json5());
+        while !builder.is_complete() {
+            let Some(token) = self.tokens.get(self.current_idx) else {
                 break;
+            };
+            if token.token_type().starts_value()
+                && let Some(raw) = raw.as_mut()
+                && builder.expects_value_at(raw.pointers)
+            {
+                let (start, end) = self.skip_value()?;
+                let text = (raw.source)(start, end)?;
+                builder.insert(JsonValue::Raw(RawValue::from_validated(text, self.options)))?;
+                continue;
             }
+            builder.push(token)?;
+            self.advance();
         }
 
-        root_value.ok_or("No JSON value found".to_string())
+        // Check next token is EOF
+        if let Some(next) = self.tokens.get(self.current_idx)
+            && next.token_type() != TokenType::EOF
+        {
+            return Err(format!(
+                "Unexpected token after root value: {:?}",
+                next.token_type()
+            ));
+        }
+        builder
+            .into_value()
+            .ok_or("No JSON value found".to_string())
     }
 
-    fn insert_value(
-        &self,
-        stack: &mut Vec<Container>,
-        root: &mut Option<JsonValue>,
-        val: JsonValue,
-    ) -> Result<(), String> {
-        if let Some(container) = stack.last_mut() {
-            match container {
-                Container::Object(map, pending_key) => {
-                    let key = pending_key
-                        .take()
-                        .ok_or("Missing key for object value".to_string())?;
-                    map.insert(key, val);
-                }
-                Container::Array(vec) => {
-                    vec.push(val);
-                }
-            }
-        } else {
-            *root = Some(val);
-        }
-        Ok(())
+    /// Parses the document and records where each of its values appears.
+    pub fn parse_with_source_map(&mut self) -> Result<(JsonValue, SourceMap), String> {
+        let value = self.parse()?;
+        let source_map = SourceMap::from_tokens(&self.tokens, self.options.json5())?;
+        Ok((value, source_map))
     }
 
-    fn container_to_value(&self, container: Container) -> JsonValue {
-        match container {
-            Container::Object(map, _) => JsonValue::Object(map),
-            Container::Array(vec) => JsonValue::Array(vec),
+    /// Checks that the input holds exactly one value, without building it.
+    pub(crate) fn skip_document(&mut self) -> Result<(), String> {
+        self.skip_value()?;
+        match self.tokens.get(self.current_idx).map(Token::token_type) {
+            None | Some(TokenType::EOF) => Ok(()),
+            Some(token_type) => Err(format!(
+                "Unexpected token after root value: {:?}",
+                token_type
+            )),
         }
     }
 
-    fn token_to_value(&self, token: &Token) -> Result<JsonValue, String> {
-        // Assume correct token type (scalar)
-        match token.token_type() {
-            TokenType::Text => {
-                let val = self.extract_string_value(token)?;
-                Ok(JsonValue::String(val))
-            }
-            TokenType::Number => {
-                let val_str = self.extract_string_value(token)?;
-                let num = val_str
-                    .parse::<f64>()
-                    .map_err(|_| "Invalid number format".to_string())?;
-                Ok(JsonValue::Number(num))
+    /// Checks the value starting at the current token and moves past it,
+    /// returning the byte range it covers.
+    pub(crate) fn skip_value(&mut self) -> Result<(usize, usize), String> {
+        let start = self
+            .tokens
+            .get(self.current_idx)
+            .ok_or("No JSON value found")?
+            .span()
+            .start();
+        // the closing bracket of each open container
+        let mut open: Vec<TokenType> = Vec::new();
+        let mut state = ParserState::ExpectValue;
+        let mut previous = None;
+
+        loop {
+            let token = self.tokens.get(self.current_idx).ok_or("Unexpected EOF")?;
+            let token_type = token.token_type();
+            let end = token.span().end();
+            match (state, token_type) {
+                (ParserState::ExpectValue, TokenType::LeftBrace) => {
+                    open.push(TokenType::RightBrace);
+                    state = ParserState::ExpectKey;
+                }
+                (ParserState::ExpectValue, TokenType::LeftSquareBracket) => {
+                    open.push(TokenType::RightSquareBracket);
+                }
+                (ParserState::ExpectValue, _) if token_type.starts_value() => {
+                    ValueBuilder::scalar(token, self.options.json5())?;
+                    state = ParserState::ExpectCommaOrEnd;
+                }
+                (ParserState::ExpectKey, TokenType::Text) => {
+                    ValueBuilder::string(token, self.options.json5())?;
+                    state = ParserState::ExpectColon;
+                }
+                (ParserState::ExpectColon, TokenType::Colon) => state = ParserState::ExpectValue,
+                (ParserState::ExpectCommaOrEnd, TokenType::Comma) if !open.is_empty() => {
+                    state = if open.last() == Some(&TokenType::RightBrace) {
+                        ParserState::ExpectKey
+                    } else {
+                        ParserState::ExpectValue
+                    };
+                }
+                (_, TokenType::RightBrace | TokenType::RightSquareBracket)
+                    if open.last() == Some(&token_type)
+                        && (state == ParserState::ExpectCommaOrEnd
+                            || matches!(
+                                previous,
+                                Some(TokenType::LeftBrace | TokenType::LeftSquareBracket)
+                            )
+                            || (self.options.json5() && previous == Some(TokenType::Comma))) =>
+                {
+                    open.pop();
+                    state = ParserState::ExpectCommaOrEnd;
+                }
+                _ => return Err(format!("Unexpected token: {:?}", token_type)),
             }
-            TokenType::Boolean => {
-                let val_str = self.extract_string_value(token)?;
-                let bool_val = val_str
-                    .parse::<bool>()
-                    .map_err(|_| "Invalid boolean format".to_string())?;
-                Ok(JsonValue::Boolean(bool_val))
+            self.advance();
+            previous = Some(token_type);
+            if open.is_empty() && state == ParserState::ExpectCommaOrEnd {
+                return Ok((start, end));
             }
-            TokenType::Null => Ok(JsonValue::Null),
-            _ => Err(format!("Not a scalar token: {:?}", token.token_type())),
         }
     }
 
@@ -286,23 +205,12 @@ impl Parser {
         }
     }
 
-    // Helper to extract string from Token's Any box
-    fn extract_string_value(&self, token: &Token) -> Result<String, String> {
-        let raw = token
-            .get_value_as_string()
-            .ok_or_else(|| "Token has no value".to_string())?;
-
-        // If it's just a Number/Boolean/Null, return as is (they store string rep)
-        match token.token_type() {
-            TokenType::Text => {
-                // Handle escapes
-                self.unescape_string(&raw)
-            }
-            _ => Ok(raw),
-        }
-    }
-
-    fn unescape_string(&self, raw: &str) -> Result<String, String> {
+    /// Decodes the escape sequences in the raw contents of a string token.
+    ///
+    /// With `json5` the escapes `\'`, `\v`, `\0` and `\xHH` are also decoded,
+    /// an escaped line break continues the string on the next line, and any
+    /// other non-digit character escapes to itself.
+    pub(crate) fn unescape_string(raw: &str, json5: bool) -> Result<String, String> {
         let mut out = String::with_capacity(raw.len());
         let mut chars = raw.chars().peekable();
 
@@ -318,23 +226,51 @@ impl Parser {
                     Some('r') => out.push('\r'),
                     Some('t') => out.push('\t'),
                     Some('u') => {
-                        // Unicode 4 hex digits
-                        let mut hex = String::new();
-                        for _ in 0..4 {
-                            if let Some(h) = chars.next() {
-                                hex.push(h);
-                            } else {
-                                return Err("Incomplete unicode escape".to_string());
+                        let mut code_point = Self::read_unicode_escape(&mut chars)?;
+                        // High surrogate must be followed by an escaped low surrogate
+                        if (0xD800..0xDC00).contains(&code_point) {
+                            if chars.next() != Some('\\') || chars.next() != Some('u') {
+                                return Err(format!(
+                                    "Unpaired surrogate in unicode escape: \\u{:04x}",
+                                    code_point
+                                ));
+                            }
+                            let low = Self::read_unicode_escape(&mut chars)?;
+                            if !(0xDC00..0xE000).contains(&low) {
+                                return Err(format!(
+                                    "Invalid low surrogate in unicode escape: \\u{:04x}",
+                                    low
+                                ));
                             }
+                            code_point = 0x10000 + ((code_point - 0xD800) << 10) + (low - 0xDC00);
                         }
-                        let code_point = u32::from_str_radix(&hex, 16)
-                            .map_err(|_| format!("Invalid unicode escape: \\u{}", hex))?;
                         if let Some(ch) = std::char::from_u32(code_point) {
                             out.push(ch);
                         } else {
-                            return Err(format!("Invalid unicode scalar: \\u{}", hex));
+                            return Err(format!("Invalid unicode scalar: \\u{:04x}", code_point));
+                        }
+                    }
+                    Some('v') if json5 => out.push('\x0b'),
+                    Some('0') if json5 && !chars.peek().is_some_and(char::is_ascii_digit) => {
+                        out.push('\0')
+                    }
+                    Some('x') if json5 => {
+                        let hex: String = chars.by_ref().take(2).collect();
+                        let code = u8::from_str_radix(&hex, 16)
+                            .ok()
+                            .filter(|_| {
+                                hex.len() == 2 && hex.chars().all(|h| h.is_ascii_hexdigit())
+                            })
+                            .ok_or_else(|| format!("Invalid hex escape: \\x{}", hex))?;
+                        out.push(char::from(code));
+                    }
+                    Some('\r') if json5 => {
+                        if chars.peek() == Some(&'\n') {
+                            chars.next();
                         }
                     }
+                    Some('\n' | '\u{2028}' | '\u{2029}') if json5 => {}
+                    Some(other) if json5 && !other.is_ascii_digit() => out.push(other),
                     Some(other) => return Err(format!("Invalid escape sequence: \\{}", other)),
                     None => return Err("Unexpected end of string in escape sequence".to_string()),
                 }
@@ -344,4 +280,42 @@ impl Parser {
         }
         Ok(out)
     }
+
+    /// Parses a JSON5 number: an optional sign followed by a decimal number,
+    /// which may start or end with a decimal point, a `0x` hexadecimal
+    /// integer, `Infinity` or `NaN`.
+    pub(crate) fn parse_json5_number(text: &str) -> Option<f64> {
+        let (sign, body) = match text.as_bytes().first() {
+            Some(b'-') => (-1.0, &text[1..]),
+            Some(b'+') => (1.0, &text[1..]),
+            _ => (1.0, text),
+        };
+        let magnitude = match body {
+            "Infinity" => f64::INFINITY,
+            "NaN" => f64::NAN,
+            _ if body.starts_with("0x") || body.starts_with("0X") => {
+                let digits = &body[2..];
+                if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return None;
+                }
+                u64::from_str_radix(digits, 16).ok()? as f64
+            }
+            _ if body.starts_with(|c: char| c.is_ascii_digit() || c == '.') => body.parse().ok()?,
+            _ => return None,
+        };
+        Some(sign * magnitude)
+    }
+
+    fn read_unicode_escape(chars: &mut impl Iterator<Item = char>) -> Result<u32, String> {
+        // Unicode 4 hex digits
+        let mut hex = String::new();
+        for _ in 0..4 {
+            if let Some(h) = chars.next() {
+                hex.push(h);
+            } else {
+                return Err("Incomplete unicode escape".to_string());
+            }
+        }
+        u32::from_str_radix(&hex, 16).map_err(|_| format!("Invalid unicode escape: \\u{}", hex))
+    }
 }
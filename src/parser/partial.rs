@@ -0,0 +1,337 @@
+use crate::{core::json_value::JsonValue, lexer::tokenizer::Tokenizer, parser::parser::Parser};
+
+/// How `parse_partial` treats entries that were cut off before they could
+/// be read in full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PartialPolicy {
+    keep_dangling_keys: bool,
+    keep_partial_numbers: bool,
+}
+
+impl PartialPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep a key whose value has not arrived yet, with a `null` value,
+    /// instead of dropping the member.
+    pub fn with_keep_dangling_keys(mut self, keep: bool) -> Self {
+        self.keep_dangling_keys = keep;
+        self
+    }
+
+    /// Keep the longest valid prefix of a half-written number such as `1.`
+    /// or `2e`, instead of dropping the entry.
+    pub fn with_keep_partial_numbers(mut self, keep: bool) -> Self {
+        self.keep_partial_numbers = keep;
+        self
+    }
+
+    pub fn keep_dangling_keys(&self) -> bool {
+        self.keep_dangling_keys
+    }
+
+    pub fn keep_partial_numbers(&self) -> bool {
+        self.keep_partial_numbers
+    }
+}
+
+/// A repair made to truncated input, in the order it was applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Completion {
+    /// An unterminated string was closed, dropping any half-written escape.
+    ClosedString,
+    /// A cut-off `true`, `false` or `null` was completed.
+    CompletedLiteral(String),
+    /// A half-written number was cut back to the given valid prefix.
+    TrimmedNumber(String),
+    /// A half-written number was dropped together with its entry.
+    DroppedNumber(String),
+    /// A key without a value was dropped; holds the key as written.
+    DroppedKey(String),
+    /// A key without a value was given `null`.
+    NullValue(String),
+    /// A comma with nothing after it was dropped.
+    DroppedTrailingComma,
+    ClosedObject,
+    ClosedArray,
+}
+
+/// The result of `parse_partial`: the value read so far and how the input
+/// was completed to get it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialValue {
+    value: JsonValue,
+    completions: Vec<Completion>,
+}
+
+impl PartialValue {
+    pub fn value(&self) -> &JsonValue {
+        &self.value
+    }
+
+    pub fn into_value(self) -> JsonValue {
+        self.value
+    }
+
+    pub fn completions(&self) -> &[Completion] {
+        &self.completions
+    }
+
+    /// Whether the input was a complete document that needed no repair.
+    pub fn is_complete(&self) -> bool {
+        self.completions.is_empty()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Expect {
+    Key,
+    Colon,
+    Value,
+    CommaOrEnd,
+}
+
+struct Frame {
+    is_object: bool,
+    expect: Expect,
+    // where to cut the input to drop the entry being read, including the
+    // comma before it
+    cut: usize,
+    after_comma: bool,
+    // the last key read, as written
+    key: String,
+}
+
+/// Parses JSON that may have been cut off mid-document, as when reading the
+/// output of a generator while it is still streaming.
+///
+/// At the end of the input open strings are closed, a cut-off literal is
+/// completed, a dangling key or half-written number is dropped or kept
+/// according to `policy`, and the containers still open are closed. Errors
+/// that are not caused by truncation are reported as by `parse`.
+pub fn parse_partial(json: &str, policy: &PartialPolicy) -> Result<PartialValue, String> {
+    let bytes = json.as_bytes();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut string_start: Option<usize> = None;
+    let mut scalar_start: Option<usize> = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        if let Some(start) = string_start {
+            match b {
+                b'\\' => i += 1,
+                b'"' => {
+                    string_start = None;
+                    if let Some(frame) = stack.last_mut() {
+                        if frame.expect == Expect::Key {
+                            frame.key = json[start..=i].to_string();
+                            frame.expect = Expect::Colon;
+                        } else {
+                            frame.expect = Expect::CommaOrEnd;
+                        }
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+
+        let in_scalar = b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.');
+        if !in_scalar
+            && scalar_start.take().is_some()
+            && let Some(frame) = stack.last_mut()
+        {
+            frame.expect = Expect::CommaOrEnd;
+        }
+        match b {
+            b'{' | b'[' => stack.push(Frame {
+                is_object: b == b'{',
+                expect: if b == b'{' {
+                    Expect::Key
+                } else {
+                    Expect::Value
+                },
+                cut: i + 1,
+                after_comma: false,
+                key: String::new(),
+            }),
+            b'}' | b']' => {
+                stack.pop();
+                if let Some(frame) = stack.last_mut() {
+                    frame.expect = Expect::CommaOrEnd;
+                }
+            }
+            b'"' => string_start = Some(i),
+            b':' => {
+                if let Some(frame) = stack.last_mut() {
+                    frame.expect = Expect::Value;
+                }
+            }
+            b',' => {
+                if let Some(frame) = stack.last_mut() {
+                    frame.expect = if frame.is_object {
+                        Expect::Key
+                    } else {
+                        Expect::Value
+                    };
+                    frame.cut = i;
+                    frame.after_comma = true;
+                }
+            }
+            _ if in_scalar && scalar_start.is_none() => scalar_start = Some(i),
+            _ => {}
+        }
+        i += 1;
+    }
+
+    let mut text = json.to_string();
+    let mut completions = Vec::new();
+
+    if let Some(start) = string_start {
+        let is_key = stack.last().is_some_and(|f| f.expect == Expect::Key);
+        text.truncate(start + complete_escapes(&json[start..]).len());
+        if is_key {
+            if let Some(frame) = stack.last_mut() {
+                frame.key = format!("{}\"", &text[start..]);
+                frame.expect = Expect::Colon;
+            }
+            text.push('"');
+        } else {
+            text.push('"');
+            completions.push(Completion::ClosedString);
+            if let Some(frame) = stack.last_mut() {
+                frame.expect = Expect::CommaOrEnd;
+            }
+        }
+    } else if let Some(start) = scalar_start {
+        let scalar = &json[start..];
+        let literal = ["true", "false", "null"]
+            .into_iter()
+            .find(|literal| literal.starts_with(scalar));
+        let prefix = (1..scalar.len())
+            .rev()
+            .map(|end| &scalar[..end])
+            .find(|prefix| is_json_number(prefix));
+
+        if let Some(literal) = literal {
+            if literal != scalar {
+                text.push_str(&literal[scalar.len()..]);
+                completions.push(Completion::CompletedLiteral(literal.to_string()));
+            }
+        } else if is_json_number(scalar) {
+            // complete as far as can be told
+        } else if let Some(prefix) = prefix.filter(|_| policy.keep_partial_numbers()) {
+            text.truncate(start + prefix.len());
+            completions.push(Completion::TrimmedNumber(prefix.to_string()));
+        } else {
+            if let Some(frame) = stack.last_mut() {
+                text.truncate(frame.cut);
+                frame.expect = Expect::CommaOrEnd;
+            }
+            completions.push(Completion::DroppedNumber(scalar.to_string()));
+        }
+        if let Some(frame) = stack.last_mut() {
+            frame.expect = Expect::CommaOrEnd;
+        }
+    }
+
+    if let Some(frame) = stack.last_mut() {
+        match frame.expect {
+            Expect::Colon | Expect::Value if frame.is_object => {
+                if policy.keep_dangling_keys() {
+                    if frame.expect == Expect::Colon {
+                        text.push(':');
+                    }
+                    text.push_str(" null");
+                    completions.push(Completion::NullValue(frame.key.clone()));
+                } else {
+                    text.truncate(frame.cut);
+                    completions.push(Completion::DroppedKey(frame.key.clone()));
+                }
+            }
+            Expect::Key | Expect::Value if frame.after_comma => {
+                text.truncate(frame.cut);
+                completions.push(Completion::DroppedTrailingComma);
+            }
+            _ => {}
+        }
+    }
+
+    for frame in stack.iter().rev() {
+        if frame.is_object {
+            text.push('}');
+            completions.push(Completion::ClosedObject);
+        } else {
+            text.push(']');
+            completions.push(Completion::ClosedArray);
+        }
+    }
+
+    let value = Parser::new(Tokenizer::new(Some(text), None))?.parse()?;
+    Ok(PartialValue { value, completions })
+}
+
+// the start of an unterminated string up to its last complete escape
+fn complete_escapes(string: &str) -> &str {
+    let bytes = string.as_bytes();
+    let mut end = 1;
+    let mut i = 1;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' {
+            let len = if bytes.get(i + 1) == Some(&b'u') {
+                6
+            } else {
+                2
+            };
+            if i + len > bytes.len() {
+                break;
+            }
+            i += len;
+        } else {
+            i += 1;
+        }
+        end = i;
+    }
+    &string[..end]
+}
+
+// -?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?
+fn is_json_number(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    let digits = |i: &mut usize| {
+        let start = *i;
+        while *i < bytes.len() && bytes[*i].is_ascii_digit() {
+            *i += 1;
+        }
+        *i > start
+    };
+
+    if bytes.first() == Some(&b'-') {
+        i += 1;
+    }
+    if bytes.get(i) == Some(&b'0') {
+        i += 1;
+    } else if !digits(&mut i) {
+        return false;
+    }
+    if bytes.get(i) == Some(&b'.') {
+        i += 1;
+        if !digits(&mut i) {
+            return false;
+        }
+    }
+    if matches!(bytes.get(i), Some(b'e' | b'E')) {
+        i += 1;
+        if matches!(bytes.get(i), Some(b'+' | b'-')) {
+            i += 1;
+        }
+        if !digits(&mut i) {
+            return false;
+        }
+    }
+    i == bytes.len()
+}
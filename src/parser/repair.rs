@@ -0,0 +1,456 @@
+use std::fmt;
+
+use crate::{core::span::Position, lexer::line_tracker::LineTracker};
+
+/// A kind of mistake `repair` corrected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixKind {
+    MissingComma,
+    /// A comma before a closing bracket or the end of the input.
+    TrailingComma,
+    /// A comma directly after another comma or an opening bracket.
+    ExtraComma,
+    MissingColon,
+    SingleQuotedString,
+    /// A string delimited by typographic quotes such as `“` and `”`.
+    SmartQuotes,
+    UnquotedKey,
+    /// A bare word in value position, other than a literal, was quoted.
+    UnquotedString,
+    /// `True`, `False` or `None`; holds the literal as written.
+    PythonLiteral(String),
+    /// A raw control character inside a string was escaped.
+    ControlCharacter,
+    UnclosedString,
+    /// A key at the end of the input was given a `null` value.
+    MissingValue,
+    /// Holds the bracket that was inserted.
+    MissingClosingBracket(char),
+    /// A `//` or `/* */` comment was removed.
+    Comment,
+    /// A character that cannot start a value, or an unmatched closing
+    /// bracket, was removed.
+    UnexpectedCharacter(char),
+}
+
+impl fmt::Display for FixKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixKind::MissingComma => write!(f, "inserted missing comma"),
+            FixKind::TrailingComma => write!(f, "removed trailing comma"),
+            FixKind::ExtraComma => write!(f, "removed extra comma"),
+            FixKind::MissingColon => write!(f, "inserted missing colon"),
+            FixKind::SingleQuotedString => write!(f, "replaced single quotes"),
+            FixKind::SmartQuotes => write!(f, "replaced smart quotes"),
+            FixKind::UnquotedKey => write!(f, "quoted key"),
+            FixKind::UnquotedString => write!(f, "quoted string"),
+            FixKind::PythonLiteral(literal) => write!(f, "replaced Python literal {}", literal),
+            FixKind::ControlCharacter => write!(f, "escaped control character"),
+            FixKind::UnclosedString => write!(f, "closed string"),
+            FixKind::MissingValue => write!(f, "inserted missing value"),
+            FixKind::MissingClosingBracket(c) => write!(f, "inserted missing '{}'", c),
+            FixKind::Comment => write!(f, "removed comment"),
+            FixKind::UnexpectedCharacter(c) => write!(f, "removed unexpected '{}'", c),
+        }
+    }
+}
+
+/// A correction made by `repair` and where in the input it was needed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    kind: FixKind,
+    position: Position,
+}
+
+impl Fix {
+    pub fn kind(&self) -> &FixKind {
+        &self.kind
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+}
+
+impl fmt::Display for Fix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at {}:{}",
+            self.kind,
+            self.position.line(),
+            self.position.column()
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Expect {
+    Key,
+    Colon,
+    Value,
+    CommaOrEnd,
+}
+
+struct Frame {
+    close: char,
+    expect: Expect,
+}
+
+struct Repairer<'a> {
+    input: &'a str,
+    offset: usize,
+    lines: LineTracker,
+    out: String,
+    fixes: Vec<Fix>,
+    stack: Vec<Frame>,
+    // what the top level expects once the stack is empty
+    root: Expect,
+    // where in `out` the last value ended, for inserting a missing comma
+    value_end: usize,
+    // the last comma written, if nothing but whitespace followed it yet
+    comma: Option<(usize, Position)>,
+}
+
+/// Turns hand-edited, slightly broken JSON into valid JSON.
+///
+/// Fixes missing and trailing commas, single-quoted and smart-quoted
+/// strings, unquoted keys, the Python literals `True`, `False` and `None`,
+/// raw control characters in strings and missing closing brackets, and
+/// removes `//` and `/* */` comments. Returns the repaired text and the fixes
+/// applied, in input order; valid JSON comes back unchanged with no fixes.
+/// Mistakes outside this list, such as a malformed number, are left for the
+/// parser to report.
+pub fn repair(input: &str) -> (String, Vec<Fix>) {
+    let mut repairer = Repairer {
+        input,
+        offset: 0,
+        lines: LineTracker::new(),
+        out: String::with_capacity(input.len()),
+        fixes: Vec::new(),
+        stack: Vec::new(),
+        root: Expect::Value,
+        value_end: 0,
+        comma: None,
+    };
+    repairer.run();
+    (repairer.out, repairer.fixes)
+}
+
+impl<'a> Repairer<'a> {
+    fn run(&mut self) {
+        while let Some(c) = self.peek() {
+            let position = self.position();
+            match c {
+                ' ' | '\t' | '\r' | '\n' => {
+                    self.bump();
+                    self.out.push(c);
+                }
+                '{' | '[' => {
+                    self.before_value(position);
+                    self.bump();
+                    self.out.push(c);
+                    let (close, expect) = if c == '{' {
+                        ('}', Expect::Key)
+                    } else {
+                        (']', Expect::Value)
+                    };
+                    self.stack.push(Frame { close, expect });
+                }
+                '}' | ']' => {
+                    self.bump();
+                    if !self.stack.iter().any(|frame| frame.close == c) {
+                        self.fix(FixKind::UnexpectedCharacter(c), position);
+                        continue;
+                    }
+                    self.drop_trailing_comma();
+                    while !self.stack.is_empty() {
+                        let close = self.close(position);
+                        if close == c {
+                            break;
+                        }
+                        self.fix(FixKind::MissingClosingBracket(close), position);
+                    }
+                }
+                ',' => {
+                    self.bump();
+                    if self.expect() == Expect::CommaOrEnd && !self.stack.is_empty() {
+                        self.out.push(',');
+                        self.comma = Some((self.out.len() - 1, position));
+                        let next = self.after_comma();
+                        self.set_expect(next);
+                    } else {
+                        self.fix(FixKind::ExtraComma, position);
+                    }
+                }
+                ':' => {
+                    self.bump();
+                    if self.expect() == Expect::Colon {
+                        self.out.push(':');
+                        self.set_expect(Expect::Value);
+                    } else {
+                        self.fix(FixKind::UnexpectedCharacter(':'), position);
+                    }
+                }
+                '"' | '\'' | '\u{201C}' | '\u{201D}' | '\u{2018}' | '\u{2019}' => {
+                    let is_key = self.before_value(position);
+                    self.string(c, position);
+                    self.end_string(is_key);
+                }
+                '/' if self.at_comment() => self.comment(position),
+                _ if c.is_alphanumeric() || matches!(c, '_' | '$' | '-' | '+' | '.') => {
+                    let is_key = self.before_value(position);
+                    let word = self.word();
+                    if is_key {
+                        self.fix(FixKind::UnquotedKey, position);
+                        self.push_quoted(word);
+                    } else {
+                        self.bare_value(word, position);
+                    }
+                    self.end_string(is_key);
+                }
+                _ => {
+                    self.bump();
+                    self.fix(FixKind::UnexpectedCharacter(c), position);
+                }
+            }
+        }
+
+        let position = self.position();
+        self.drop_trailing_comma();
+        while !self.stack.is_empty() {
+            let close = self.close(position);
+            self.fix(FixKind::MissingClosingBracket(close), position);
+        }
+    }
+
+    /// Closes the innermost container, returning its bracket.
+    fn close(&mut self, position: Position) -> char {
+        self.finish_member(position);
+        let close = self.stack.pop().map_or(']', |frame| frame.close);
+        self.out.push(close);
+        self.end_value();
+        close
+    }
+
+    /// Prepares for a value or key starting at `position`, inserting a
+    /// missing comma or colon. Returns whether an object key is expected.
+    fn before_value(&mut self, position: Position) -> bool {
+        match self.expect() {
+            Expect::CommaOrEnd if !self.stack.is_empty() => {
+                self.out.insert(self.value_end, ',');
+                self.fix(FixKind::MissingComma, position);
+                let next = self.after_comma();
+                self.set_expect(next);
+            }
+            Expect::Colon => {
+                self.out.insert(self.value_end, ':');
+                self.fix(FixKind::MissingColon, position);
+                self.set_expect(Expect::Value);
+            }
+            _ => {}
+        }
+        self.comma = None;
+        self.expect() == Expect::Key
+    }
+
+    // a key at the end of an object gets a `null` value
+    fn finish_member(&mut self, position: Position) {
+        if matches!(self.expect(), Expect::Colon | Expect::Value) && self.in_object() {
+            if self.expect() == Expect::Colon {
+                self.out.insert(self.value_end, ':');
+            }
+            self.out.push_str("null");
+            self.fix(FixKind::MissingValue, position);
+        }
+    }
+
+    fn end_string(&mut self, is_key: bool) {
+        if is_key {
+            self.value_end = self.out.len();
+            self.set_expect(Expect::Colon);
+        } else {
+            self.end_value();
+        }
+    }
+
+    fn end_value(&mut self) {
+        self.value_end = self.out.len();
+        self.set_expect(Expect::CommaOrEnd);
+    }
+
+    fn drop_trailing_comma(&mut self) {
+        if let Some((idx, position)) = self.comma.take() {
+            self.out.remove(idx);
+            self.fix(FixKind::TrailingComma, position);
+            self.set_expect(Expect::CommaOrEnd);
+        }
+    }
+
+    /// Copies a string delimited by `open` as a double-quoted JSON string.
+    fn string(&mut self, open: char, position: Position) {
+        let close: &[char] = match open {
+            '"' => &['"'],
+            '\'' => &['\''],
+            '\u{2018}' | '\u{2019}' => &['\u{2019}', '\u{2018}', '\''],
+            _ => &['\u{201D}', '\u{201C}', '"'],
+        };
+        match open {
+            '"' => {}
+            '\'' => self.fix(FixKind::SingleQuotedString, position),
+            _ => self.fix(FixKind::SmartQuotes, position),
+        }
+        self.bump();
+        self.out.push('"');
+
+        loop {
+            let position = self.position();
+            let Some(c) = self.peek() else {
+                self.fix(FixKind::UnclosedString, position);
+                break;
+            };
+            self.bump();
+            match c {
+                _ if close.contains(&c) => break,
+                '\\' => match self.peek() {
+                    Some('\'') if open != '"' => {
+                        self.bump();
+                        self.out.push('\'');
+                    }
+                    Some(next) => {
+                        self.bump();
+                        self.out.push('\\');
+                        self.out.push(next);
+                    }
+                    None => {}
+                },
+                '"' => self.out.push_str("\\\""),
+                '\n' => self.control(position, "\\n"),
+                '\r' => self.control(position, "\\r"),
+                '\t' => self.control(position, "\\t"),
+                _ if c.is_control() && (c as u32) < 0x20 => {
+                    self.control(position, &format!("\\u{:04x}", c as u32))
+                }
+                _ => self.out.push(c),
+            }
+        }
+        self.out.push('"');
+    }
+
+    fn at_comment(&self) -> bool {
+        let rest = &self.input[self.offset..];
+        rest.starts_with("//") || rest.starts_with("/*")
+    }
+
+    // a block comment between two tokens leaves a space so they stay apart
+    fn comment(&mut self, position: Position) {
+        let rest = &self.input[self.offset..];
+        let len = if rest.starts_with("//") {
+            rest.find(['\r', '\n']).unwrap_or(rest.len())
+        } else {
+            rest[2..].find("*/").map_or(rest.len(), |end| end + 4)
+        };
+        self.lines
+            .advance(&self.input.as_bytes()[self.offset..self.offset + len]);
+        self.offset += len;
+        self.fix(FixKind::Comment, position);
+
+        let spaced = |c: Option<char>| c.is_none_or(char::is_whitespace);
+        if !spaced(self.out.chars().next_back()) && !spaced(self.peek()) {
+            self.out.push(' ');
+        }
+    }
+
+    fn control(&mut self, position: Position, escaped: &str) {
+        self.out.push_str(escaped);
+        self.fix(FixKind::ControlCharacter, position);
+    }
+
+    fn bare_value(&mut self, word: &str, position: Position) {
+        let literal = match word {
+            "true" | "false" | "null" => Some(word),
+            "True" => Some("true"),
+            "False" => Some("false"),
+            "None" => Some("null"),
+            _ => None,
+        };
+        match literal {
+            Some(literal) => {
+                if literal != word {
+                    self.fix(FixKind::PythonLiteral(word.to_string()), position);
+                }
+                self.out.push_str(literal);
+            }
+            None if word.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.') => {
+                self.out.push_str(word)
+            }
+            None => {
+                self.fix(FixKind::UnquotedString, position);
+                self.push_quoted(word);
+            }
+        }
+    }
+
+    fn word(&mut self) -> &'a str {
+        let input = self.input;
+        let start = self.offset;
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || matches!(c, '_' | '$' | '-' | '+' | '.') {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        &input[start..self.offset]
+    }
+
+    fn push_quoted(&mut self, word: &str) {
+        self.out.push('"');
+        self.out.push_str(word);
+        self.out.push('"');
+    }
+
+    fn expect(&self) -> Expect {
+        self.stack.last().map_or(self.root, |frame| frame.expect)
+    }
+
+    fn set_expect(&mut self, expect: Expect) {
+        match self.stack.last_mut() {
+            Some(frame) => frame.expect = expect,
+            None => self.root = expect,
+        }
+    }
+
+    fn after_comma(&self) -> Expect {
+        if self.in_object() {
+            Expect::Key
+        } else {
+            Expect::Value
+        }
+    }
+
+    fn in_object(&self) -> bool {
+        self.stack.last().is_some_and(|frame| frame.close == '}')
+    }
+
+    fn fix(&mut self, kind: FixKind, position: Position) {
+        self.fixes.push(Fix { kind, position });
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.offset..].chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            let len = c.len_utf8();
+            self.lines
+                .advance(&self.input.as_bytes()[self.offset..self.offset + len]);
+            self.offset += len;
+        }
+    }
+
+    fn position(&self) -> Position {
+        Position::new(self.offset, self.lines.line(), self.lines.column())
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use crate::core::{
+    json_pointer::escape_pointer_token, span::Span, token::Token, tokentype::TokenType,
+};
+use crate::parser::parser::Parser;
+
+/// Where a value, and the key naming it if it is an object member, appears in
+/// the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    key: Option<Span>,
+    value: Span,
+}
+
+impl Location {
+    /// The span of the member name, including its quotes; `None` for array
+    /// elements and the root.
+    pub fn key(&self) -> Option<Span> {
+        self.key
+    }
+
+    /// The span of the value; objects and arrays run from the opening to the
+    /// closing bracket.
+    pub fn value(&self) -> Span {
+        self.value
+    }
+}
+
+/// Source locations of every value in a parsed document, keyed by JSON Pointer
+/// (`""` for the root), so errors found after parsing can point back into the
+/// input.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceMap {
+    locations: HashMap<String, Location>,
+}
+
+impl SourceMap {
+    pub fn get(&self, pointer: &str) -> Option<&Location> {
+        self.locations.get(pointer)
+    }
+
+    pub fn value_span(&self, pointer: &str) -> Option<Span> {
+        self.get(pointer).map(Location::value)
+    }
+
+    pub fn key_span(&self, pointer: &str) -> Option<Span> {
+        self.get(pointer).and_then(Location::key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.locations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+
+    /// Pointers in sorted order with their locations.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Location)> {
+        let mut entries: Vec<(&str, &Location)> = self
+            .locations
+            .iter()
+            .map(|(pointer, location)| (pointer.as_str(), location))
+            .collect();
+        entries.sort_by_key(|(pointer, _)| *pointer);
+        entries.into_iter()
+    }
+
+    /// Builds the map from the tokens of a document that has already parsed
+    /// successfully.
+    pub(crate) fn from_tokens(tokens: &[Token], json5: bool) -> Result<Self, String> {
+        let mut locations = HashMap::new();
+        let mut stack: Vec<Frame> = Vec::new();
+        // the span of a `-` that starts the number read next
+        let mut sign: Option<Span> = None;
+
+        for token in tokens {
+            match token.token_type() {
+                TokenType::LeftBrace | TokenType::LeftSquareBracket => {
+                    let (pointer, key) = Self::member(&stack);
+                    let kind = if token.token_type() == TokenType::LeftBrace {
+                        FrameKind::Object { pending_key: None }
+                    } else {
+                        FrameKind::Array { next_index: 0 }
+                    };
+                    stack.push(Frame {
+                        pointer,
+                        key,
+                        start: token.span(),
+                        kind,
+                    });
+                }
+                TokenType::RightBrace | TokenType::RightSquareBracket => {
+                    let frame = stack.pop().ok_or("Unbalanced brackets in source map")?;
+                    let value =
+                        Span::new(frame.start.start_position(), token.span().end_position());
+                    locations.insert(
+                        frame.pointer,
+                        Location {
+                            key: frame.key,
+                            value,
+                        },
+                    );
+                    Self::finish_member(&mut stack);
+                }
+                TokenType::Text
+                    if matches!(
+                        stack.last(),
+                        Some(Frame {
+                            kind: FrameKind::Object { pending_key: None },
+                            ..
+                        })
+                    ) =>
+                {
+                    let raw = token.value().ok_or("Token has no value")?;
+                    let key = Parser::unescape_string(raw, json5)?;
+                    if let Some(Frame {
+                        kind: FrameKind::Object { pending_key },
+                        ..
+                    }) = stack.last_mut()
+                    {
+                        *pending_key = Some((key, token.span()));
+                    }
+                }
+                TokenType::MinusSign => sign = Some(token.span()),
+                TokenType::Text | TokenType::Number | TokenType::Boolean | TokenType::Null => {
+                    let (pointer, key) = Self::member(&stack);
+                    let value = match sign.take() {
+                        Some(sign) => Span::new(sign.start_position(), token.span().end_position()),
+                        None => token.span(),
+                    };
+                    locations.insert(pointer, Location { key, value });
+                    Self::finish_member(&mut stack);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self { locations })
+    }
+
+    /// The pointer and key span of the value about to be read.
+    fn member(stack: &[Frame]) -> (String, Option<Span>) {
+        match stack.last() {
+            None => (String::new(), None),
+            Some(frame) => match &frame.kind {
+                FrameKind::Object { pending_key } => match pending_key {
+                    Some((key, span)) => (
+                        format!("{}/{}", frame.pointer, escape_pointer_token(key)),
+                        Some(*span),
+                    ),
+                    None => (frame.pointer.clone(), None),
+                },
+                FrameKind::Array { next_index } => {
+                    (format!("{}/{}", frame.pointer, next_index), None)
+                }
+            },
+        }
+    }
+
+    fn finish_member(stack: &mut [Frame]) {
+        match stack.last_mut().map(|frame| &mut frame.kind) {
+            Some(FrameKind::Object { pending_key }) => *pending_key = None,
+            Some(FrameKind::Array { next_index }) => *next_index += 1,
+            None => {}
+        }
+    }
+}
+
+struct Frame {
+    pointer: String,
+    key: Option<Span>,
+    start: Span,
+    kind: FrameKind,
+}
+
+enum FrameKind {
+    Object { pending_key: Option<(String, Span)> },
+    Array { next_index: usize },
+}
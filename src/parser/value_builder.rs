@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+
+use crate::{
+    core::{json_value::JsonValue, token::Token, tokentype::TokenType},
+    parser::parser::Parser,
+};
+
+/// Builds a single `JsonValue` from tokens fed to it one at a time, checking
+/// the grammar as it goes.
+///
+/// `Parser` feeds it a document tokenized up front and `TokenStream` feeds it
+/// straight from a tokenizer, so both accept the same input and report the
+/// same errors. Containers are kept on an explicit stack, so deeply nested
+/// input cannot overflow the call stack.
+pub(crate) struct ValueBuilder {
+    stack: Vec<Container>,
+    state: State,
+    root: Option<JsonValue>,
+    json5: bool,
+    // a `-` was read and the number it belongs to is next
+    negative: bool,
+}
+
+enum Container {
+    Object(HashMap<String, JsonValue>, Option<String>), // Map, Pending Key
+    Array(Vec<JsonValue>),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+#[allow(clippy::enum_variant_names)]
+enum State {
+    ExpectValue,
+    ExpectKey,
+    ExpectColon,
+    ExpectCommaOrEnd,
+}
+
+impl ValueBuilder {
+    pub(crate) fn new(json5: bool) -> Self {
+        Self {
+            stack: Vec::new(),
+            state: State::ExpectValue,
+            root: None,
+            json5,
+            negative: false,
+        }
+    }
+
+    /// Whether the whole value has been read.
+    pub(crate) fn is_complete(&self) -> bool {
+        self.stack.is_empty() && self.root.is_some()
+    }
+
+    /// The value read, once it is complete.
+    pub(crate) fn into_value(self) -> Option<JsonValue> {
+        self.root
+    }
+
+    /// Whether the next value belongs at one of `pointers`, each given as
+    /// its parsed reference tokens.
+    pub(crate) fn expects_value_at(&self, pointers: &[Vec<String>]) -> bool {
+        self.state == State::ExpectValue
+            && !self.negative
+            && pointers.iter().any(|pointer| {
+                pointer.len() == self.stack.len()
+                    && pointer
+                        .iter()
+                        .zip(&self.stack)
+                        .all(|(token, container)| match container {
+                            Container::Object(_, Some(key)) => token == key,
+                            Container::Object(_, None) => false,
+                            Container::Array(vec) => token.parse() == Ok(vec.len()),
+                        })
+            })
+    }
+
+    /// Adds a value read by the caller where the next value is expected.
+    pub(crate) fn insert(&mut self, value: JsonValue) -> Result<(), String> {
+        self.insert_value(value)?;
+        self.state = State::ExpectCommaOrEnd;
+        Ok(())
+    }
+
+    pub(crate) fn push(&mut self, token: &Token) -> Result<(), String> {
+        let token_type = token.token_type();
+        if self.negative && token_type != TokenType::Number {
+            return Err(format!("Expected number after '-', found {:?}", token_type));
+        }
+
+        match self.state {
+            State::ExpectValue => match token_type {
+                TokenType::LeftBrace => {
+                    self.stack.push(Container::Object(HashMap::new(), None));
+                    self.state = State::ExpectKey;
+                }
+                TokenType::LeftSquareBracket => {
+                    self.stack.push(Container::Array(Vec::new()));
+                }
+                TokenType::MinusSign if !self.negative => self.negative = true,
+                TokenType::Text | TokenType::Number | TokenType::Boolean | TokenType::Null => {
+                    let mut val = Self::scalar(token, self.json5)?;
+                    if let JsonValue::Number(n) = &mut val
+                        && self.negative
+                    {
+                        *n = -*n;
+                        self.negative = false;
+                    }
+                    self.insert(val)?;
+                }
+                TokenType::RightSquareBracket => {
+                    // Valid only if we are in an Array and it's empty "[]"
+                    match self.stack.last() {
+                        Some(Container::Array(vec)) if vec.is_empty() || self.json5 => {
+                            self.close()?
+                        }
+                        Some(Container::Array(_)) => {
+                            return Err("Trailing comma in array".to_string());
+                        }
+                        _ => return Err(format!("Unexpected token: {:?}", token_type)),
+                    }
+                }
+                _ => return Err(format!("Unexpected token: {:?}", token_type)),
+            },
+            State::ExpectKey => match token_type {
+                TokenType::Text => {
+                    let key = Self::string(token, self.json5)?;
+                    if let Some(Container::Object(_, pending_key)) = self.stack.last_mut() {
+                        *pending_key = Some(key);
+                    } else {
+                        return Err("Internal Error: ExpectKey but not in Object".to_string());
+                    }
+                    self.state = State::ExpectColon;
+                }
+                TokenType::RightBrace => {
+                    // Empty object or End of object
+                    match self.stack.last() {
+                        Some(Container::Object(map, _)) if map.is_empty() || self.json5 => {
+                            self.close()?
+                        }
+                        Some(Container::Object(_, _)) => {
+                            return Err("Trailing comma in object".to_string());
+                        }
+                        _ => return Err(format!("Unexpected token: {:?}", token_type)),
+                    }
+                }
+                _ => {
+                    return Err(format!(
+                        "Expected Object Key or '}}', found {:?}",
+                        token_type
+                    ));
+                }
+            },
+            State::ExpectColon => match token_type {
+                TokenType::Colon => self.state = State::ExpectValue,
+                _ => return Err(format!("Expected ':', found {:?}", token_type)),
+            },
+            State::ExpectCommaOrEnd => match token_type {
+                // If in Object -> ExpectKey.
+                // If in Array -> ExpectValue.
+                // If Root -> Error (Trailing comma not allowed at root, nor multiple values).
+                TokenType::Comma => match self.stack.last() {
+                    Some(Container::Object(_, _)) => self.state = State::ExpectKey,
+                    Some(Container::Array(_)) => self.state = State::ExpectValue,
+                    None => return Err("Unexpected comma at root".to_string()),
+                },
+                TokenType::RightBrace => match self.stack.last() {
+                    Some(Container::Object(_, _)) => self.close()?,
+                    _ => return Err(format!("Unexpected '}}', found {:?}", token_type)),
+                },
+                TokenType::RightSquareBracket => match self.stack.last() {
+                    Some(Container::Array(_)) => self.close()?,
+                    _ => return Err(format!("Unexpected ']', found {:?}", token_type)),
+                },
+                TokenType::EOF => return Err("Unexpected EOF".to_string()),
+                _ => {
+                    return Err(format!(
+                        "Expected ',' or '}}' or ']', found {:?}",
+                        token_type
+                    ));
+                }
+            },
+        }
+        Ok(())
+    }
+
+    /// The value of a string, number, boolean or null token.
+    pub(crate) fn scalar(token: &Token, json5: bool) -> Result<JsonValue, String> {
+        match token.token_type() {
+            TokenType::Text => Ok(JsonValue::String(Self::string(token, json5)?)),
+            TokenType::Number => {
+                let val_str = Self::text(token)?;
+                let num = if json5 {
+                    Parser::parse_json5_number(val_str)
+                } else {
+                    val_str.parse::<f64>().ok()
+                }
+                .ok_or_else(|| "Invalid number format".to_string())?;
+                Ok(JsonValue::Number(num))
+            }
+            TokenType::Boolean => {
+                let bool_val = Self::text(token)?
+                    .parse::<bool>()
+                    .map_err(|_| "Invalid boolean format".to_string())?;
+                Ok(JsonValue::Boolean(bool_val))
+            }
+            TokenType::Null => Ok(JsonValue::Null),
+            _ => Err(format!("Not a scalar token: {:?}", token.token_type())),
+        }
+    }
+
+    /// The decoded contents of a string token.
+    pub(crate) fn string(token: &Token, json5: bool) -> Result<String, String> {
+        Parser::unescape_string(Self::text(token)?, json5)
+    }
+
+    fn text(token: &Token) -> Result<&str, String> {
+        token
+            .value()
+            .map(String::as_str)
+            .ok_or_else(|| "Token has no value".to_string())
+    }
+
+    // ends the innermost container and adds it to its parent
+    fn close(&mut self) -> Result<(), String> {
+        let val = match self.stack.pop() {
+            Some(Container::Object(map, _)) => JsonValue::Object(map),
+            Some(Container::Array(vec)) => JsonValue::Array(vec),
+            None => return Err("Internal Error: no container to close".to_string()),
+        };
+        self.insert(val)
+    }
+
+    fn insert_value(&mut self, val: JsonValue) -> Result<(), String> {
+        if let Some(container) = self.stack.last_mut() {
+            match container {
+                Container::Object(map, pending_key) => {
+                    let key = pending_key
+                        .take()
+                        .ok_or("Missing key for object value".to_string())?;
+                    map.insert(key, val);
+                }
+                Container::Array(vec) => {
+                    vec.push(val);
+                }
+            }
+        } else {
+            self.root = Some(val);
+        }
+        Ok(())
+    }
+}
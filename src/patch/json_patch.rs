@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use crate::core::{json_pointer::child_path, json_value::JsonValue, lcs::lcs_lengths};
+
+/// A single RFC 6902 operation. Paths are JSON Pointers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchOperation {
+    Add { path: String, value: JsonValue },
+    Remove { path: String },
+    Replace { path: String, value: JsonValue },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: JsonValue },
+}
+
+/// An RFC 6902 JSON Patch document.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Patch {
+    operations: Vec<PatchOperation>,
+}
+
+impl Patch {
+    pub fn new(operations: Vec<PatchOperation>) -> Self {
+        Self { operations }
+    }
+
+    /// Reads a patch from its JSON form, an array of operation objects.
+    pub fn from_value(value: &JsonValue) -> Result<Self, String> {
+        let items = match value {
+            JsonValue::Array(items) => items,
+            _ => return Err("JSON Patch must be an array of operations".to_string()),
+        };
+        let operations = items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| {
+                Self::parse_operation(item).map_err(|e| format!("Patch operation {}: {}", idx, e))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self { operations })
+    }
+
+    pub fn to_value(&self) -> JsonValue {
+        JsonValue::Array(
+            self.operations
+                .iter()
+                .map(|operation| {
+                    let mut map = HashMap::new();
+                    let (op, path, from, value) = match operation {
+                        PatchOperation::Add { path, value } => ("add", path, None, Some(value)),
+                        PatchOperation::Remove { path } => ("remove", path, None, None),
+                        PatchOperation::Replace { path, value } => {
+                            ("replace", path, None, Some(value))
+                        }
+                        PatchOperation::Move { from, path } => ("move", path, Some(from), None),
+                        PatchOperation::Copy { from, path } => ("copy", path, Some(from), None),
+                        PatchOperation::Test { path, value } => ("test", path, None, Some(value)),
+                    };
+                    map.insert("op".to_string(), JsonValue::String(op.to_string()));
+                    map.insert("path".to_string(), JsonValue::String(path.clone()));
+                    if let Some(from) = from {
+                        map.insert("from".to_string(), JsonValue::String(from.clone()));
+                    }
+                    if let Some(value) = value {
+                        map.insert("value".to_string(), value.clone());
+                    }
+                    JsonValue::Object(map)
+                })
+                .collect(),
+        )
+    }
+
+    pub fn operations(&self) -> &[PatchOperation] {
+        &self.operations
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Applies every operation in order. The document is left untouched if any operation fails.
+    pub fn apply(&self, document: &mut JsonValue) -> Result<(), String> {
+        let mut working = document.clone();
+        for (idx, operation) in self.operations.iter().enumerate() {
+            Self::apply_operation(&mut working, operation)
+                .map_err(|e| format!("Patch operation {}: {}", idx, e))?;
+        }
+        *document = working;
+        Ok(())
+    }
+
+    fn apply_operation(document: &mut JsonValue, operation: &PatchOperation) -> Result<(), String> {
+        match operation {
+            PatchOperation::Add { path, value } => {
+                document.insert_at_pointer(path, value.clone())?;
+            }
+            PatchOperation::Remove { path } => {
+                document.remove_at_pointer(path)?;
+            }
+            PatchOperation::Replace { path, value } => {
+                let target = document
+                    .pointer_mut(path)
+                    .ok_or_else(|| format!("No value at JSON Pointer: {}", path))?;
+                *target = value.clone();
+            }
+            PatchOperation::Move { from, path } => {
+                if from == path {
+                    return Ok(());
+                }
+                if path.starts_with(&format!("{}/", from)) {
+                    return Err(format!("Cannot move {} into its own child {}", from, path));
+                }
+                let value = document.remove_at_pointer(from)?;
+                document.insert_at_pointer(path, value)?;
+            }
+            PatchOperation::Copy { from, path } => {
+                let value = document
+                    .pointer(from)
+                    .cloned()
+                    .ok_or_else(|| format!("No value at JSON Pointer: {}", from))?;
+                document.insert_at_pointer(path, value)?;
+            }
+            PatchOperation::Test { path, value } => {
+                let actual = document
+                    .pointer(path)
+                    .ok_or_else(|| format!("No value at JSON Pointer: {}", path))?;
+                if actual != value {
+                    return Err(format!("Test failed at {}", path));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_operation(item: &JsonValue) -> Result<PatchOperation, String> {
+        let map = match item {
+            JsonValue::Object(map) => map,
+            _ => return Err("operation must be an object".to_string()),
+        };
+        let string_member = |name: &str| -> Result<String, String> {
+            match map.get(name) {
+                Some(JsonValue::String(s)) => Ok(s.clone()),
+                Some(_) => Err(format!("'{}' must be a string", name)),
+                None => Err(format!("missing '{}'", name)),
+            }
+        };
+        let value_member = || -> Result<JsonValue, String> {
+            map.get("value")
+                .cloned()
+                .ok_or_else(|| "missing 'value'".to_string())
+        };
+
+        let path = string_member("path")?;
+        match string_member("op")?.as_str() {
+            "add" => Ok(PatchOperation::Add {
+                path,
+                value: value_member()?,
+            }),
+            "remove" => Ok(PatchOperation::Remove { path }),
+            "replace" => Ok(PatchOperation::Replace {
+                path,
+                value: value_member()?,
+            }),
+            "move" => Ok(PatchOperation::Move {
+                from: string_member("from")?,
+                path,
+            }),
+            "copy" => Ok(PatchOperation::Copy {
+                from: string_member("from")?,
+                path,
+            }),
+            "test" => Ok(PatchOperation::Test {
+                path,
+                value: value_member()?,
+            }),
+            other => Err(format!("unknown op '{}'", other)),
+        }
+    }
+}
+
+/// Generates a patch that turns `a` into `b`.
+///
+/// Objects are compared member by member and arrays by their longest common
+/// subsequence, so elements that only shifted position produce no operations.
+pub fn diff(a: &JsonValue, b: &JsonValue) -> Patch {
+    let mut operations = Vec::new();
+    diff_into(a, b, String::new(), &mut operations);
+    Patch::new(operations)
+}
+
+fn diff_into(a: &JsonValue, b: &JsonValue, path: String, operations: &mut Vec<PatchOperation>) {
+    if a == b {
+        return;
+    }
+    match (a, b) {
+        (JsonValue::Object(a_map), JsonValue::Object(b_map)) => {
+            let mut removed: Vec<&String> =
+                a_map.keys().filter(|k| !b_map.contains_key(*k)).collect();
+            removed.sort();
+            for key in removed {
+                operations.push(PatchOperation::Remove {
+                    path: child_path(&path, key),
+                });
+            }
+
+            let mut keys: Vec<&String> = b_map.keys().collect();
+            keys.sort();
+            for key in keys {
+                match a_map.get(key) {
+                    Some(a_val) => {
+                        diff_into(a_val, &b_map[key], child_path(&path, key), operations)
+                    }
+                    None => operations.push(PatchOperation::Add {
+                        path: child_path(&path, key),
+                        value: b_map[key].clone(),
+                    }),
+                }
+            }
+        }
+        (JsonValue::Array(a_vec), JsonValue::Array(b_vec)) => {
+            let prefix = a_vec
+                .iter()
+                .zip(b_vec.iter())
+                .take_while(|(x, y)| x == y)
+                .count();
+            let suffix = a_vec[prefix..]
+                .iter()
+                .rev()
+                .zip(b_vec[prefix..].iter().rev())
+                .take_while(|(x, y)| x == y)
+                .count();
+            let a_mid = &a_vec[prefix..a_vec.len() - suffix];
+            let b_mid = &b_vec[prefix..b_vec.len() - suffix];
+            diff_array(a_mid, b_mid, &path, prefix, operations);
+        }
+        _ => operations.push(PatchOperation::Replace {
+            path,
+            value: b.clone(),
+        }),
+    }
+}
+
+// Walks the LCS of `a` and `b`, pairing the unmatched runs between common
+// elements positionally. `index` tracks where the next element of `a` sits in
+// the array as already patched.
+fn diff_array(
+    a: &[JsonValue],
+    b: &[JsonValue],
+    path: &str,
+    mut index: usize,
+    operations: &mut Vec<PatchOperation>,
+) {
+    let lengths = lcs_lengths(a, b);
+    let (mut i, mut j) = (0, 0);
+    let (mut a_gap, mut b_gap) = (Vec::new(), Vec::new());
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            flush_gap(&a_gap, &b_gap, path, &mut index, operations);
+            a_gap.clear();
+            b_gap.clear();
+            index += 1;
+            i += 1;
+            j += 1;
+        } else if j < b.len() && (i == a.len() || lengths[i][j + 1] >= lengths[i + 1][j]) {
+            b_gap.push(&b[j]);
+            j += 1;
+        } else {
+            a_gap.push(&a[i]);
+            i += 1;
+        }
+    }
+    flush_gap(&a_gap, &b_gap, path, &mut index, operations);
+}
+
+fn flush_gap(
+    a_gap: &[&JsonValue],
+    b_gap: &[&JsonValue],
+    path: &str,
+    index: &mut usize,
+    operations: &mut Vec<PatchOperation>,
+) {
+    let common = a_gap.len().min(b_gap.len());
+    for k in 0..common {
+        diff_into(
+            a_gap[k],
+            b_gap[k],
+            child_path(path, &index.to_string()),
+            operations,
+        );
+        *index += 1;
+    }
+    // Each removal shifts the remaining elements down onto the same index
+    for _ in common..a_gap.len() {
+        operations.push(PatchOperation::Remove {
+            path: child_path(path, &index.to_string()),
+        });
+    }
+    for value in &b_gap[common..] {
+        operations.push(PatchOperation::Add {
+            path: child_path(path, &index.to_string()),
+            value: (*value).clone(),
+        });
+        *index += 1;
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use crate::core::json_value::JsonValue;
+
+impl JsonValue {
+    /// Applies an RFC 7396 JSON Merge Patch in place.
+    ///
+    /// Object patches merge recursively and a `null` member deletes the key;
+    /// any other patch value replaces the target outright.
+    pub fn merge_patch(&mut self, patch: &JsonValue) {
+        let patch_map = match patch {
+            JsonValue::Object(map) => map,
+            _ => {
+                *self = patch.clone();
+                return;
+            }
+        };
+        if !matches!(self, JsonValue::Object(_)) {
+            *self = JsonValue::Object(HashMap::new());
+        }
+        if let JsonValue::Object(target) = self {
+            for (key, value) in patch_map {
+                if *value == JsonValue::Null {
+                    target.remove(key);
+                } else {
+                    target
+                        .entry(key.clone())
+                        .or_insert(JsonValue::Null)
+                        .merge_patch(value);
+                }
+            }
+        }
+    }
+}
+
+/// Generates a merge patch that turns `a` into `b`.
+///
+/// Merge patches cannot express setting a member to `null`, so such members of `b`
+/// come out as deletions.
+pub fn merge_patch_diff(a: &JsonValue, b: &JsonValue) -> JsonValue {
+    match (a, b) {
+        (JsonValue::Object(a_map), JsonValue::Object(b_map)) => {
+            let mut patch = HashMap::new();
+            for key in a_map.keys() {
+                if !b_map.contains_key(key) {
+                    patch.insert(key.clone(), JsonValue::Null);
+                }
+            }
+            for (key, b_val) in b_map {
+                match a_map.get(key) {
+                    Some(a_val) if a_val == b_val => {}
+                    Some(a_val) => {
+                        patch.insert(key.clone(), merge_patch_diff(a_val, b_val));
+                    }
+                    None => {
+                        patch.insert(key.clone(), b_val.clone());
+                    }
+                }
+            }
+            JsonValue::Object(patch)
+        }
+        _ => b.clone(),
+    }
+}
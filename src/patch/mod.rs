@@ -0,0 +1,2 @@
+pub mod json_patch;
+pub mod merge_patch;
@@ -0,0 +1,164 @@
+/// Checks a string against a `format` keyword value.
+///
+/// Returns `None` for formats that are not checked; per draft 2020-12 those are
+/// annotations only and always pass.
+pub fn check_format(format: &str, value: &str) -> Option<bool> {
+    match format {
+        "date-time" => Some(is_date_time(value)),
+        "date" => Some(is_date(value)),
+        "time" => Some(is_time(value)),
+        "email" => Some(is_email(value)),
+        "uri" => Some(is_uri(value)),
+        "uuid" => Some(is_uuid(value)),
+        _ => None,
+    }
+}
+
+fn digits(s: &str, len: usize) -> Option<u32> {
+    if s.len() == len && s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// RFC 3339 `full-date`: `YYYY-MM-DD` with a valid day for the month.
+pub fn is_date(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    let [year, month, day] = parts[..] else {
+        return false;
+    };
+    let (Some(year), Some(month), Some(day)) = (digits(year, 4), digits(month, 2), digits(day, 2))
+    else {
+        return false;
+    };
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days_in_month).contains(&day)
+}
+
+/// RFC 3339 `full-time`: `HH:MM:SS[.frac](Z|+HH:MM|-HH:MM)`.
+pub fn is_time(value: &str) -> bool {
+    let value = value.to_ascii_uppercase();
+    let (time, offset) = if let Some(time) = value.strip_suffix('Z') {
+        (time, None)
+    } else {
+        match value.rfind(['+', '-']) {
+            Some(idx) => (&value[..idx], Some(&value[idx + 1..])),
+            None => return false,
+        }
+    };
+
+    if let Some(offset) = offset {
+        let Some((hours, minutes)) = offset.split_once(':') else {
+            return false;
+        };
+        match (digits(hours, 2), digits(minutes, 2)) {
+            (Some(h), Some(m)) if h <= 23 && m <= 59 => {}
+            _ => return false,
+        }
+    }
+
+    let (time, fraction) = match time.split_once('.') {
+        Some((time, fraction)) => (time, Some(fraction)),
+        None => (time, None),
+    };
+    if fraction.is_some_and(|f| f.is_empty() || !f.bytes().all(|b| b.is_ascii_digit())) {
+        return false;
+    }
+    let parts: Vec<&str> = time.split(':').collect();
+    let [hours, minutes, seconds] = parts[..] else {
+        return false;
+    };
+    // second 60 is allowed for leap seconds
+    matches!(
+        (digits(hours, 2), digits(minutes, 2), digits(seconds, 2)),
+        (Some(h), Some(m), Some(s)) if h <= 23 && m <= 59 && s <= 60
+    )
+}
+
+/// RFC 3339 `date-time`: a full date and full time separated by `T`.
+pub fn is_date_time(value: &str) -> bool {
+    match value.find(['T', 't']) {
+        Some(idx) => is_date(&value[..idx]) && is_time(&value[idx + 1..]),
+        None => false,
+    }
+}
+
+/// A pragmatic RFC 5321 mailbox check: `local@domain` with a dot-atom local
+/// part and a hostname domain.
+pub fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.rsplit_once('@') else {
+        return false;
+    };
+    let local_ok = !local.is_empty()
+        && local.len() <= 64
+        && local.split('.').all(|atom| {
+            !atom.is_empty()
+                && atom
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~-".contains(c))
+        });
+    local_ok && is_hostname(domain)
+}
+
+fn is_hostname(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 253
+        && value.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+/// RFC 3986 absolute URI: a scheme followed by characters allowed in a URI,
+/// with well-formed percent-encodings.
+pub fn is_uri(value: &str) -> bool {
+    let Some((scheme, rest)) = value.split_once(':') else {
+        return false;
+    };
+    let mut scheme_chars = scheme.chars();
+    let scheme_ok = scheme_chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && scheme_chars.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+    if !scheme_ok {
+        return false;
+    }
+
+    let bytes = rest.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if b == b'%' {
+            let hex = bytes.get(i + 1..i + 3);
+            if !hex.is_some_and(|h| h.iter().all(u8::is_ascii_hexdigit)) {
+                return false;
+            }
+            i += 3;
+            continue;
+        }
+        if !(b.is_ascii_alphanumeric() || b"-._~:/?#[]@!$&'()*+,;=".contains(&b)) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// RFC 4122 textual form: `8-4-4-4-12` hexadecimal digits.
+pub fn is_uuid(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && group.bytes().all(|b| b.is_ascii_hexdigit()))
+}
@@ -0,0 +1,181 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::core::json_value::JsonValue;
+
+const DRAFT_2020_12: &str = "https://json-schema.org/draft/2020-12/schema";
+
+// Order in which inferred types are listed, matching the validator's keyword docs
+const TYPES: [&str; 7] = [
+    "null", "boolean", "integer", "number", "string", "array", "object",
+];
+
+// Strings become an enum when there are at most this many distinct values...
+const MAX_ENUM_VALUES: usize = 8;
+// ...and each distinct value was seen at least this many times on average
+const MIN_ENUM_REPEATS: usize = 2;
+
+/// Infers a JSON Schema (draft 2020-12) describing every sample document.
+///
+/// Properties present in every sampled object are `required`, number fields
+/// that only ever held whole numbers are `integer`, and string fields with a
+/// few frequently repeated values are described with `enum`.
+pub fn infer_schema<'a>(values: impl Iterator<Item = &'a JsonValue>) -> JsonValue {
+    let mut shape = Shape::default();
+    for value in values {
+        shape.observe(value);
+    }
+
+    let mut schema = match shape.to_schema() {
+        JsonValue::Object(map) => map,
+        _ => HashMap::new(),
+    };
+    schema.insert(
+        "$schema".to_string(),
+        JsonValue::String(DRAFT_2020_12.to_string()),
+    );
+    JsonValue::Object(schema)
+}
+
+/// Everything seen at one location across the sample documents.
+#[derive(Default)]
+struct Shape {
+    count: usize,
+    types: [bool; 7],
+    strings: usize,
+    // None once there are too many distinct values to form an enum
+    distinct_strings: Option<BTreeMap<String, usize>>,
+    objects: usize,
+    properties: BTreeMap<String, Shape>,
+    items: Option<Box<Shape>>,
+}
+
+impl Shape {
+    fn observe(&mut self, value: &JsonValue) {
+        if self.count == 0 {
+            self.distinct_strings = Some(BTreeMap::new());
+        }
+        self.count += 1;
+
+        let type_name = match value {
+            JsonValue::Null => "null",
+            JsonValue::Boolean(_) => "boolean",
+            JsonValue::Number(n) if n.fract() == 0.0 => "integer",
+            JsonValue::Number(_) => "number",
+            JsonValue::String(s) => {
+                self.strings += 1;
+                if let Some(distinct) = &mut self.distinct_strings {
+                    *distinct.entry(s.clone()).or_insert(0) += 1;
+                    if distinct.len() > MAX_ENUM_VALUES {
+                        self.distinct_strings = None;
+                    }
+                }
+                "string"
+            }
+            JsonValue::Array(items) => {
+                let shape = self.items.get_or_insert_with(Box::default);
+                for item in items {
+                    shape.observe(item);
+                }
+                "array"
+            }
+            JsonValue::Object(members) => {
+                self.objects += 1;
+                for (key, member) in members {
+                    self.properties
+                        .entry(key.clone())
+                        .or_default()
+                        .observe(member);
+                }
+                "object"
+            }
+            JsonValue::Raw(raw) => {
+                // observed as the value it holds
+                self.count -= 1;
+                if let Ok(value) = raw.parse_with(raw.options()) {
+                    self.observe(&value);
+                }
+                return;
+            }
+        };
+        let index = TYPES.iter().position(|t| *t == type_name).unwrap();
+        self.types[index] = true;
+    }
+
+    fn to_schema(&self) -> JsonValue {
+        let mut schema = HashMap::new();
+
+        let mut types: Vec<&str> = TYPES
+            .iter()
+            .zip(self.types)
+            .filter_map(|(name, seen)| seen.then_some(*name))
+            .collect();
+        // every integer is also a number
+        if types.contains(&"number") {
+            types.retain(|t| *t != "integer");
+        }
+        match types.as_slice() {
+            [] => return JsonValue::Object(schema),
+            [single] => {
+                schema.insert("type".to_string(), JsonValue::String(single.to_string()));
+            }
+            _ => {
+                let names = types
+                    .iter()
+                    .map(|t| JsonValue::String(t.to_string()))
+                    .collect();
+                schema.insert("type".to_string(), JsonValue::Array(names));
+            }
+        }
+
+        if let Some(values) = self.enum_values(&types) {
+            schema.insert("enum".to_string(), JsonValue::Array(values));
+        }
+
+        if self.objects > 0 {
+            let properties = self
+                .properties
+                .iter()
+                .map(|(key, shape)| (key.clone(), shape.to_schema()))
+                .collect();
+            schema.insert("properties".to_string(), JsonValue::Object(properties));
+
+            let required: Vec<JsonValue> = self
+                .properties
+                .iter()
+                .filter(|(_, shape)| shape.count == self.objects)
+                .map(|(key, _)| JsonValue::String(key.clone()))
+                .collect();
+            if !required.is_empty() {
+                schema.insert("required".to_string(), JsonValue::Array(required));
+            }
+        }
+
+        if let Some(items) = &self.items
+            && items.count > 0
+        {
+            schema.insert("items".to_string(), items.to_schema());
+        }
+
+        JsonValue::Object(schema)
+    }
+
+    /// The allowed values when this location only held low-cardinality
+    /// strings, plus `null` if it was also seen.
+    fn enum_values(&self, types: &[&str]) -> Option<Vec<JsonValue>> {
+        let distinct = self.distinct_strings.as_ref()?;
+        let only_strings = types.iter().all(|t| *t == "string" || *t == "null");
+        if !only_strings || distinct.is_empty() || self.strings < distinct.len() * MIN_ENUM_REPEATS
+        {
+            return None;
+        }
+
+        let mut values: Vec<JsonValue> = distinct
+            .keys()
+            .map(|value| JsonValue::String(value.clone()))
+            .collect();
+        if types.contains(&"null") {
+            values.push(JsonValue::Null);
+        }
+        Some(values)
+    }
+}
@@ -0,0 +1,731 @@
+use std::collections::HashMap;
+
+use crate::{
+    convert::from_json::type_name,
+    core::{json_pointer::escape_pointer_token, json_value::JsonValue},
+    schema::{format::check_format, pattern::Pattern, validation_error::ValidationError},
+};
+
+const TYPES: [&str; 7] = [
+    "null", "boolean", "integer", "number", "string", "array", "object",
+];
+
+// Keywords whose value is an object of subschemas
+const SCHEMA_MAPS: [&str; 4] = ["properties", "patternProperties", "$defs", "definitions"];
+
+// Keywords whose value is a single subschema
+const SCHEMA_VALUES: [&str; 3] = ["additionalProperties", "items", "not"];
+
+// Keywords whose value is a non-empty array of subschemas
+const SCHEMA_ARRAYS: [&str; 4] = ["prefixItems", "allOf", "anyOf", "oneOf"];
+
+const NON_NEGATIVE_INTEGERS: [&str; 6] = [
+    "minLength",
+    "maxLength",
+    "minItems",
+    "maxItems",
+    "minProperties",
+    "maxProperties",
+];
+
+const NUMBERS: [&str; 5] = [
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "multipleOf",
+];
+
+/// A JSON Schema (draft 2020-12) compiled for validating instances.
+///
+/// Compiling checks the schema's own structure, pre-compiles every `pattern`
+/// and resolves every `$ref`, so `validate` never fails on a bad schema.
+/// References must be local: `#`, `#/json/pointer` or `#anchor` for a `$anchor`.
+#[derive(Debug, Clone)]
+pub struct JsonSchema {
+    root: JsonValue,
+    patterns: HashMap<String, Pattern>,
+    anchors: HashMap<String, String>,
+}
+
+impl JsonSchema {
+    pub fn compile(schema: &JsonValue) -> Result<Self, String> {
+        let mut compiled = Self {
+            root: schema.clone(),
+            patterns: HashMap::new(),
+            anchors: HashMap::new(),
+        };
+        compiled.collect_anchors(schema, "")?;
+        compiled.check(schema, "")?;
+        Ok(compiled)
+    }
+
+    pub fn schema(&self) -> &JsonValue {
+        &self.root
+    }
+
+    /// Validates `instance`, returning every violation found.
+    pub fn validate(&self, instance: &JsonValue) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        let mut active_refs = Vec::new();
+        self.validate_node(&self.root, "", instance, "", &mut errors, &mut active_refs);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn is_valid(&self, instance: &JsonValue) -> bool {
+        self.validate(instance).is_ok()
+    }
+
+    fn collect_anchors(&mut self, schema: &JsonValue, path: &str) -> Result<(), String> {
+        let JsonValue::Object(map) = schema else {
+            return Ok(());
+        };
+        if let Some(anchor) = map.get("$anchor") {
+            let anchor = anchor
+                .as_str()
+                .ok_or_else(|| invalid(path, "$anchor", "expected a string"))?;
+            if self
+                .anchors
+                .insert(anchor.to_string(), path.to_string())
+                .is_some()
+            {
+                return Err(invalid(path, "$anchor", "duplicate anchor"));
+            }
+        }
+        for (sub_path, subschema) in subschemas(map, path) {
+            self.collect_anchors(subschema, &sub_path)?;
+        }
+        Ok(())
+    }
+
+    fn check(&mut self, schema: &JsonValue, path: &str) -> Result<(), String> {
+        let map = match schema {
+            JsonValue::Boolean(_) => return Ok(()),
+            JsonValue::Object(map) => map,
+            _ => {
+                return Err(format!(
+                    "Invalid schema at #{}: expected an object or boolean",
+                    path
+                ));
+            }
+        };
+
+        if let Some(types) = map.get("type") {
+            let names = match types {
+                JsonValue::String(_) => std::slice::from_ref(types),
+                JsonValue::Array(items) => items.as_slice(),
+                _ => return Err(invalid(path, "type", "expected a string or array")),
+            };
+            for name in names {
+                match name.as_str() {
+                    Some(name) if TYPES.contains(&name) => {}
+                    Some(name) => {
+                        return Err(invalid(path, "type", &format!("unknown type '{}'", name)));
+                    }
+                    None => return Err(invalid(path, "type", "expected strings")),
+                }
+            }
+        }
+        if map.get("enum").is_some_and(|value| !value.is_array()) {
+            return Err(invalid(path, "enum", "expected an array"));
+        }
+        if let Some(required) = map.get("required")
+            && !required
+                .as_array()
+                .is_some_and(|items| items.iter().all(JsonValue::is_string))
+        {
+            return Err(invalid(path, "required", "expected an array of strings"));
+        }
+        for keyword in NUMBERS {
+            if let Some(value) = map.get(keyword) {
+                match value.as_f64() {
+                    Some(n) if keyword != "multipleOf" || n > 0.0 => {}
+                    _ => return Err(invalid(path, keyword, "expected a number")),
+                }
+            }
+        }
+        for keyword in NON_NEGATIVE_INTEGERS {
+            if let Some(value) = map.get(keyword)
+                && value.as_i64().is_none_or(|n| n < 0)
+            {
+                return Err(invalid(path, keyword, "expected a non-negative integer"));
+            }
+        }
+        if map
+            .get("uniqueItems")
+            .is_some_and(|value| !value.is_boolean())
+        {
+            return Err(invalid(path, "uniqueItems", "expected a boolean"));
+        }
+        if map.get("format").is_some_and(|value| !value.is_string()) {
+            return Err(invalid(path, "format", "expected a string"));
+        }
+        if let Some(pattern) = map.get("pattern") {
+            let pattern = pattern
+                .as_str()
+                .ok_or_else(|| invalid(path, "pattern", "expected a string"))?;
+            self.compile_pattern(pattern)
+                .map_err(|e| invalid(path, "pattern", &e))?;
+        }
+        if let Some(JsonValue::Object(patterns)) = map.get("patternProperties") {
+            for pattern in patterns.keys() {
+                self.compile_pattern(pattern)
+                    .map_err(|e| invalid(path, "patternProperties", &e))?;
+            }
+        }
+        if let Some(reference) = map.get("$ref") {
+            let reference = reference
+                .as_str()
+                .ok_or_else(|| invalid(path, "$ref", "expected a string"))?;
+            self.resolve(reference)
+                .ok_or_else(|| invalid(path, "$ref", &format!("cannot resolve '{}'", reference)))?;
+        }
+
+        for keyword in SCHEMA_MAPS {
+            if map.get(keyword).is_some_and(|value| !value.is_object()) {
+                return Err(invalid(path, keyword, "expected an object"));
+            }
+        }
+        for keyword in SCHEMA_ARRAYS {
+            if map
+                .get(keyword)
+                .is_some_and(|value| value.as_array().is_none_or(|items| items.is_empty()))
+            {
+                return Err(invalid(path, keyword, "expected a non-empty array"));
+            }
+        }
+        for (sub_path, subschema) in subschemas(map, path) {
+            self.check(subschema, &sub_path)?;
+        }
+        Ok(())
+    }
+
+    fn compile_pattern(&mut self, source: &str) -> Result<(), String> {
+        if !self.patterns.contains_key(source) {
+            self.patterns
+                .insert(source.to_string(), Pattern::new(source)?);
+        }
+        Ok(())
+    }
+
+    fn pattern(&self, source: &str) -> &Pattern {
+        &self.patterns[source]
+    }
+
+    /// Resolves a local reference to the schema path and subschema it names.
+    fn resolve(&self, reference: &str) -> Option<(String, &JsonValue)> {
+        let fragment = reference.strip_prefix('#')?;
+        let fragment = percent_decode(fragment)?;
+        if fragment.is_empty() || fragment.starts_with('/') {
+            Some((fragment.clone(), self.root.pointer(&fragment)?))
+        } else {
+            let path = self.anchors.get(&fragment)?;
+            Some((path.clone(), self.root.pointer(path)?))
+        }
+    }
+
+    fn validate_node(
+        &self,
+        schema: &JsonValue,
+        schema_path: &str,
+        instance: &JsonValue,
+        instance_path: &str,
+        errors: &mut Vec<ValidationError>,
+        active_refs: &mut Vec<(String, String)>,
+    ) {
+        let map = match schema {
+            JsonValue::Object(map) => map,
+            JsonValue::Boolean(false) => {
+                errors.push(ValidationError::new(
+                    instance_path,
+                    schema_path,
+                    "No value is allowed by a false schema",
+                ));
+                return;
+            }
+            _ => return,
+        };
+        let error = |keyword: &str, message: String| {
+            ValidationError::new(
+                instance_path,
+                format!("{}/{}", schema_path, keyword),
+                message,
+            )
+        };
+
+        if let Some(JsonValue::String(reference)) = map.get("$ref")
+            && let Some((target_path, target)) = self.resolve(reference)
+        {
+            // a reference cycle that consumes no instance data would never terminate
+            let key = (target_path.clone(), instance_path.to_string());
+            if !active_refs.contains(&key) {
+                active_refs.push(key);
+                self.validate_node(
+                    target,
+                    &target_path,
+                    instance,
+                    instance_path,
+                    errors,
+                    active_refs,
+                );
+                active_refs.pop();
+            }
+        }
+
+        if let Some(types) = map.get("type") {
+            let names: Vec<&str> = match types {
+                JsonValue::String(name) => vec![name],
+                JsonValue::Array(items) => items.iter().filter_map(JsonValue::as_str).collect(),
+                _ => Vec::new(),
+            };
+            if !names.iter().any(|name| type_matches(name, instance)) {
+                errors.push(error(
+                    "type",
+                    format!(
+                        "Expected type {}, found {}",
+                        names.join(" or "),
+                        type_name(instance)
+                    ),
+                ));
+            }
+        }
+        if let Some(JsonValue::Array(allowed)) = map.get("enum")
+            && !allowed.contains(instance)
+        {
+            errors.push(error(
+                "enum",
+                "Value is not one of the allowed values".to_string(),
+            ));
+        }
+        if let Some(expected) = map.get("const")
+            && expected != instance
+        {
+            errors.push(error(
+                "const",
+                format!("Value does not equal {}", expected.stringify()),
+            ));
+        }
+
+        match instance {
+            JsonValue::Number(n) => self.validate_number(map, *n, &error, errors),
+            JsonValue::String(s) => self.validate_string(map, s, &error, errors),
+            JsonValue::Array(items) => self.validate_array(
+                map,
+                schema_path,
+                items,
+                instance_path,
+                &error,
+                errors,
+                active_refs,
+            ),
+            JsonValue::Object(members) => self.validate_object(
+                map,
+                schema_path,
+                members,
+                instance_path,
+                &error,
+                errors,
+                active_refs,
+            ),
+            _ => {}
+        }
+
+        if let Some(JsonValue::Array(schemas)) = map.get("allOf") {
+            for (i, subschema) in schemas.iter().enumerate() {
+                let sub_path = format!("{}/allOf/{}", schema_path, i);
+                self.validate_node(
+                    subschema,
+                    &sub_path,
+                    instance,
+                    instance_path,
+                    errors,
+                    active_refs,
+                );
+            }
+        }
+        if let Some(JsonValue::Array(schemas)) = map.get("anyOf") {
+            let matched = (0..schemas.len()).any(|i| {
+                let sub_path = format!("{}/anyOf/{}", schema_path, i);
+                self.matches(&schemas[i], &sub_path, instance, instance_path, active_refs)
+            });
+            if !matched {
+                errors.push(error(
+                    "anyOf",
+                    "Value does not match any schema in anyOf".to_string(),
+                ));
+            }
+        }
+        if let Some(JsonValue::Array(schemas)) = map.get("oneOf") {
+            let matched = (0..schemas.len())
+                .filter(|i| {
+                    let sub_path = format!("{}/oneOf/{}", schema_path, i);
+                    self.matches(
+                        &schemas[*i],
+                        &sub_path,
+                        instance,
+                        instance_path,
+                        active_refs,
+                    )
+                })
+                .count();
+            if matched != 1 {
+                errors.push(error(
+                    "oneOf",
+                    format!(
+                        "Value matches {} schemas in oneOf, expected exactly one",
+                        matched
+                    ),
+                ));
+            }
+        }
+        if let Some(subschema) = map.get("not") {
+            let sub_path = format!("{}/not", schema_path);
+            if self.matches(subschema, &sub_path, instance, instance_path, active_refs) {
+                errors.push(error(
+                    "not",
+                    "Value must not match the schema in not".to_string(),
+                ));
+            }
+        }
+    }
+
+    fn matches(
+        &self,
+        schema: &JsonValue,
+        schema_path: &str,
+        instance: &JsonValue,
+        instance_path: &str,
+        active_refs: &mut Vec<(String, String)>,
+    ) -> bool {
+        let mut errors = Vec::new();
+        self.validate_node(
+            schema,
+            schema_path,
+            instance,
+            instance_path,
+            &mut errors,
+            active_refs,
+        );
+        errors.is_empty()
+    }
+
+    fn validate_number(
+        &self,
+        map: &HashMap<String, JsonValue>,
+        n: f64,
+        error: &dyn Fn(&str, String) -> ValidationError,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let bound = |keyword: &str| map.get(keyword).and_then(JsonValue::as_f64);
+        if let Some(min) = bound("minimum")
+            && n < min
+        {
+            errors.push(error(
+                "minimum",
+                format!("{} is less than the minimum of {}", n, min),
+            ));
+        }
+        if let Some(max) = bound("maximum")
+            && n > max
+        {
+            errors.push(error(
+                "maximum",
+                format!("{} is greater than the maximum of {}", n, max),
+            ));
+        }
+        if let Some(min) = bound("exclusiveMinimum")
+            && n <= min
+        {
+            errors.push(error(
+                "exclusiveMinimum",
+                format!("{} is not greater than the exclusive minimum of {}", n, min),
+            ));
+        }
+        if let Some(max) = bound("exclusiveMaximum")
+            && n >= max
+        {
+            errors.push(error(
+                "exclusiveMaximum",
+                format!("{} is not less than the exclusive maximum of {}", n, max),
+            ));
+        }
+        if let Some(divisor) = bound("multipleOf") {
+            // tolerate the rounding error of decimal divisors like 0.1
+            let quotient = n / divisor;
+            if (quotient - quotient.round()).abs() > 1e-9 {
+                errors.push(error(
+                    "multipleOf",
+                    format!("{} is not a multiple of {}", n, divisor),
+                ));
+            }
+        }
+    }
+
+    fn validate_string(
+        &self,
+        map: &HashMap<String, JsonValue>,
+        s: &str,
+        error: &dyn Fn(&str, String) -> ValidationError,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let length = s.chars().count() as i64;
+        if let Some(min) = map.get("minLength").and_then(JsonValue::as_i64)
+            && length < min
+        {
+            errors.push(error(
+                "minLength",
+                format!("String is shorter than {} characters", min),
+            ));
+        }
+        if let Some(max) = map.get("maxLength").and_then(JsonValue::as_i64)
+            && length > max
+        {
+            errors.push(error(
+                "maxLength",
+                format!("String is longer than {} characters", max),
+            ));
+        }
+        if let Some(JsonValue::String(pattern)) = map.get("pattern")
+            && !self.pattern(pattern).is_match(s)
+        {
+            errors.push(error(
+                "pattern",
+                format!("String does not match pattern '{}'", pattern),
+            ));
+        }
+        if let Some(JsonValue::String(format)) = map.get("format")
+            && check_format(format, s) == Some(false)
+        {
+            errors.push(error("format", format!("String is not a valid {}", format)));
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn validate_array(
+        &self,
+        map: &HashMap<String, JsonValue>,
+        schema_path: &str,
+        items: &[JsonValue],
+        instance_path: &str,
+        error: &dyn Fn(&str, String) -> ValidationError,
+        errors: &mut Vec<ValidationError>,
+        active_refs: &mut Vec<(String, String)>,
+    ) {
+        let len = items.len() as i64;
+        if let Some(min) = map.get("minItems").and_then(JsonValue::as_i64)
+            && len < min
+        {
+            errors.push(error(
+                "minItems",
+                format!("Array has fewer than {} items", min),
+            ));
+        }
+        if let Some(max) = map.get("maxItems").and_then(JsonValue::as_i64)
+            && len > max
+        {
+            errors.push(error(
+                "maxItems",
+                format!("Array has more than {} items", max),
+            ));
+        }
+        if map.get("uniqueItems") == Some(&JsonValue::Boolean(true)) {
+            let duplicate = (0..items.len()).find_map(|i| {
+                ((i + 1)..items.len())
+                    .find(|j| items[i] == items[*j])
+                    .map(|j| (i, j))
+            });
+            if let Some((i, j)) = duplicate {
+                errors.push(error(
+                    "uniqueItems",
+                    format!("Items {} and {} are equal", i, j),
+                ));
+            }
+        }
+
+        let prefix = match map.get("prefixItems") {
+            Some(JsonValue::Array(schemas)) => schemas.as_slice(),
+            _ => &[],
+        };
+        for (i, item) in items.iter().enumerate() {
+            let (subschema, sub_path) = match prefix.get(i) {
+                Some(subschema) => (subschema, format!("{}/prefixItems/{}", schema_path, i)),
+                None => match map.get("items") {
+                    Some(subschema) => (subschema, format!("{}/items", schema_path)),
+                    None => break,
+                },
+            };
+            self.validate_node(
+                subschema,
+                &sub_path,
+                item,
+                &format!("{}/{}", instance_path, i),
+                errors,
+                active_refs,
+            );
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn validate_object(
+        &self,
+        map: &HashMap<String, JsonValue>,
+        schema_path: &str,
+        members: &HashMap<String, JsonValue>,
+        instance_path: &str,
+        error: &dyn Fn(&str, String) -> ValidationError,
+        errors: &mut Vec<ValidationError>,
+        active_refs: &mut Vec<(String, String)>,
+    ) {
+        let len = members.len() as i64;
+        if let Some(min) = map.get("minProperties").and_then(JsonValue::as_i64)
+            && len < min
+        {
+            errors.push(error(
+                "minProperties",
+                format!("Object has fewer than {} properties", min),
+            ));
+        }
+        if let Some(max) = map.get("maxProperties").and_then(JsonValue::as_i64)
+            && len > max
+        {
+            errors.push(error(
+                "maxProperties",
+                format!("Object has more than {} properties", max),
+            ));
+        }
+        if let Some(JsonValue::Array(required)) = map.get("required") {
+            for name in required.iter().filter_map(JsonValue::as_str) {
+                if !members.contains_key(name) {
+                    errors.push(error(
+                        "required",
+                        format!("Missing required property '{}'", name),
+                    ));
+                }
+            }
+        }
+
+        let properties = map.get("properties").and_then(JsonValue::as_object);
+        let pattern_properties = map.get("patternProperties").and_then(JsonValue::as_object);
+        let mut keys: Vec<&String> = members.keys().collect();
+        keys.sort();
+
+        for key in keys {
+            let value = &members[key];
+            let member_path = format!("{}/{}", instance_path, escape_pointer_token(key));
+            let mut evaluated = false;
+
+            if let Some(subschema) = properties.and_then(|properties| properties.get(key)) {
+                evaluated = true;
+                let sub_path = format!("{}/properties/{}", schema_path, escape_pointer_token(key));
+                self.validate_node(
+                    subschema,
+                    &sub_path,
+                    value,
+                    &member_path,
+                    errors,
+                    active_refs,
+                );
+            }
+            if let Some(pattern_properties) = pattern_properties {
+                let mut patterns: Vec<&String> = pattern_properties.keys().collect();
+                patterns.sort();
+                for pattern in patterns {
+                    if self.pattern(pattern).is_match(key) {
+                        evaluated = true;
+                        let sub_path = format!(
+                            "{}/patternProperties/{}",
+                            schema_path,
+                            escape_pointer_token(pattern)
+                        );
+                        self.validate_node(
+                            &pattern_properties[pattern],
+                            &sub_path,
+                            value,
+                            &member_path,
+                            errors,
+                            active_refs,
+                        );
+                    }
+                }
+            }
+            if !evaluated && let Some(subschema) = map.get("additionalProperties") {
+                let sub_path = format!("{}/additionalProperties", schema_path);
+                if subschema == &JsonValue::Boolean(false) {
+                    errors.push(ValidationError::new(
+                        member_path,
+                        sub_path,
+                        format!("Additional property '{}' is not allowed", key),
+                    ));
+                } else {
+                    self.validate_node(
+                        subschema,
+                        &sub_path,
+                        value,
+                        &member_path,
+                        errors,
+                        active_refs,
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn invalid(path: &str, keyword: &str, message: &str) -> String {
+    format!("Invalid schema at #{}/{}: {}", path, keyword, message)
+}
+
+fn type_matches(name: &str, instance: &JsonValue) -> bool {
+    match (name, instance) {
+        ("integer", JsonValue::Number(n)) => n.fract() == 0.0,
+        _ => name == type_name(instance),
+    }
+}
+
+/// Every direct subschema of a schema object with its schema path.
+fn subschemas<'a>(map: &'a HashMap<String, JsonValue>, path: &str) -> Vec<(String, &'a JsonValue)> {
+    let mut found = Vec::new();
+    for keyword in SCHEMA_MAPS {
+        if let Some(JsonValue::Object(entries)) = map.get(keyword) {
+            for (name, subschema) in entries {
+                let sub_path = format!("{}/{}/{}", path, keyword, escape_pointer_token(name));
+                found.push((sub_path, subschema));
+            }
+        }
+    }
+    for keyword in SCHEMA_VALUES {
+        if let Some(subschema) = map.get(keyword) {
+            found.push((format!("{}/{}", path, keyword), subschema));
+        }
+    }
+    for keyword in SCHEMA_ARRAYS {
+        if let Some(JsonValue::Array(items)) = map.get(keyword) {
+            for (i, subschema) in items.iter().enumerate() {
+                found.push((format!("{}/{}/{}", path, keyword, i), subschema));
+            }
+        }
+    }
+    found
+}
+
+// URI fragments may percent-encode pointer characters
+fn percent_decode(fragment: &str) -> Option<String> {
+    let bytes = fragment.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
@@ -0,0 +1,5 @@
+pub mod format;
+pub mod infer;
+pub mod json_schema;
+pub mod pattern;
+pub mod validation_error;
@@ -0,0 +1,532 @@
+// counted repetition copies its sub-program, so nested counts multiply
+const MAX_PROGRAM_SIZE: usize = 100_000;
+
+/// A compiled regular expression for the `pattern` and `patternProperties`
+/// keywords.
+///
+/// Supports the commonly used ECMA-262 subset: literals, `.`, character classes
+/// with ranges and `\d \w \s` (and their negations), anchors `^ $ \b \B`,
+/// groups `( )` and `(?: )`, alternation, and the quantifiers `* + ? {n} {n,} {n,m}`
+/// with their lazy forms. Matching is unanchored, as JSON Schema requires.
+/// Patterns whose counted repetitions would compile to an oversized program,
+/// such as `(a{1,1000}){1,1000}`, are rejected.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    source: String,
+    program: Vec<Inst>,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Empty,
+    Char(char),
+    Any,
+    Class(Class),
+    Start,
+    End,
+    WordBoundary(bool),
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct Class {
+    negated: bool,
+    items: Vec<ClassItem>,
+}
+
+#[derive(Debug, Clone)]
+enum ClassItem {
+    Range(char, char),
+    Digit(bool),
+    Word(bool),
+    Space(bool),
+}
+
+impl Pattern {
+    pub fn new(source: &str) -> Result<Self, String> {
+        let mut parser = PatternParser {
+            chars: source.chars().collect(),
+            pos: 0,
+        };
+        let node = parser
+            .parse_alternation()
+            .and_then(|node| match parser.peek() {
+                None => Ok(node),
+                Some(_) => Err(format!("Unmatched ')' at position {}", parser.pos)),
+            })
+            .and_then(|node| match program_size(&node) {
+                size if size > MAX_PROGRAM_SIZE => Err(format!(
+                    "pattern compiles to more than {} instructions",
+                    MAX_PROGRAM_SIZE
+                )),
+                _ => Ok(node),
+            })
+            .map_err(|e| format!("Invalid pattern '{}': {}", source, e))?;
+        let mut program = Vec::new();
+        compile(&node, &mut program);
+        program.push(Inst::Match);
+        Ok(Self {
+            source: source.to_string(),
+            program,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns true if the pattern matches anywhere in `text`.
+    ///
+    /// Runs every possible match in step over the input, so the time taken
+    /// grows linearly with the length of `text` and never backtracks.
+    pub fn is_match(&self, text: &str) -> bool {
+        let input: Vec<char> = text.chars().collect();
+        let mut seen = vec![usize::MAX; self.program.len()];
+        let mut threads = Vec::new();
+        for pos in 0..=input.len() {
+            // a match may start at any position
+            if add_thread(&self.program, &input, pos, 0, &mut threads, &mut seen) {
+                return true;
+            }
+            let mut next = Vec::new();
+            for pc in threads {
+                let consumed = match (&self.program[pc], input.get(pos)) {
+                    (Inst::Char(expected), Some(c)) => c == expected,
+                    (Inst::Any, Some(c)) => *c != '\n' && *c != '\r',
+                    (Inst::Class(class), Some(c)) => class.matches(*c),
+                    _ => false,
+                };
+                if consumed
+                    && add_thread(&self.program, &input, pos + 1, pc + 1, &mut next, &mut seen)
+                {
+                    return true;
+                }
+            }
+            threads = next;
+        }
+        false
+    }
+}
+
+fn is_word(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn is_space(c: char) -> bool {
+    c.is_whitespace() || c == '\u{feff}'
+}
+
+impl Class {
+    fn matches(&self, c: char) -> bool {
+        let found = self.items.iter().any(|item| match item {
+            ClassItem::Range(low, high) => (*low..=*high).contains(&c),
+            ClassItem::Digit(negated) => c.is_ascii_digit() != *negated,
+            ClassItem::Word(negated) => is_word(c) != *negated,
+            ClassItem::Space(negated) => is_space(c) != *negated,
+        });
+        found != self.negated
+    }
+}
+
+// One step of the compiled program. `Split` continues at both targets and
+// `Jump` at its target; the assertions check the current position without
+// consuming input.
+#[derive(Debug, Clone)]
+enum Inst {
+    Char(char),
+    Any,
+    Class(Class),
+    Start,
+    End,
+    WordBoundary(bool),
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+// The number of instructions `compile` emits for `node`, saturating rather
+// than overflowing. An empty repeated node counts as one instruction per copy
+// so that huge counts are rejected even though they emit nothing.
+fn program_size(node: &Node) -> usize {
+    match node {
+        Node::Empty => 0,
+        Node::Concat(nodes) => nodes
+            .iter()
+            .fold(0, |size, node| size.saturating_add(program_size(node))),
+        Node::Alternation(options) => options.iter().fold(0, |size, option| {
+            size.saturating_add(program_size(option)).saturating_add(2)
+        }),
+        Node::Repeat { node, min, max } => {
+            let size = program_size(node).max(1);
+            let optional = match max {
+                None => size.saturating_add(2),
+                Some(max) => (max - min).saturating_mul(size.saturating_add(1)),
+            };
+            min.saturating_mul(size).saturating_add(optional)
+        }
+        _ => 1,
+    }
+}
+
+fn compile(node: &Node, program: &mut Vec<Inst>) {
+    match node {
+        Node::Empty => {}
+        Node::Char(c) => program.push(Inst::Char(*c)),
+        Node::Any => program.push(Inst::Any),
+        Node::Class(class) => program.push(Inst::Class(class.clone())),
+        Node::Start => program.push(Inst::Start),
+        Node::End => program.push(Inst::End),
+        Node::WordBoundary(expected) => program.push(Inst::WordBoundary(*expected)),
+        Node::Concat(nodes) => nodes.iter().for_each(|node| compile(node, program)),
+        Node::Alternation(options) => {
+            let mut jumps = Vec::new();
+            for (i, option) in options.iter().enumerate() {
+                if i + 1 == options.len() {
+                    compile(option, program);
+                    break;
+                }
+                let split = program.len();
+                program.push(Inst::Split(split + 1, 0));
+                compile(option, program);
+                jumps.push(program.len());
+                program.push(Inst::Jump(0));
+                program[split] = Inst::Split(split + 1, program.len());
+            }
+            let end = program.len();
+            for jump in jumps {
+                program[jump] = Inst::Jump(end);
+            }
+        }
+        Node::Repeat { node, min, max } => {
+            for _ in 0..*min {
+                compile(node, program);
+            }
+            match max {
+                None => {
+                    let split = program.len();
+                    program.push(Inst::Split(split + 1, 0));
+                    compile(node, program);
+                    program.push(Inst::Jump(split));
+                    program[split] = Inst::Split(split + 1, program.len());
+                }
+                Some(max) => {
+                    let splits: Vec<usize> = (*min..*max)
+                        .map(|_| {
+                            let split = program.len();
+                            program.push(Inst::Split(split + 1, 0));
+                            compile(node, program);
+                            split
+                        })
+                        .collect();
+                    let end = program.len();
+                    for split in splits {
+                        program[split] = Inst::Split(split + 1, end);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Follows the jumps, splits and assertions from `pc` at `pos`, adding every
+// instruction that consumes a character to `threads`. Returns true once the
+// match instruction is reached.
+fn add_thread(
+    program: &[Inst],
+    input: &[char],
+    pos: usize,
+    pc: usize,
+    threads: &mut Vec<usize>,
+    seen: &mut [usize],
+) -> bool {
+    let mut stack = vec![pc];
+    while let Some(pc) = stack.pop() {
+        // each instruction is visited once per position, which also stops
+        // repeats of something that can match nothing from looping
+        if seen[pc] == pos {
+            continue;
+        }
+        seen[pc] = pos;
+        match &program[pc] {
+            Inst::Match => return true,
+            Inst::Jump(target) => stack.push(*target),
+            Inst::Split(first, second) => {
+                stack.push(*second);
+                stack.push(*first);
+            }
+            Inst::Start => {
+                if pos == 0 {
+                    stack.push(pc + 1);
+                }
+            }
+            Inst::End => {
+                if pos == input.len() {
+                    stack.push(pc + 1);
+                }
+            }
+            Inst::WordBoundary(expected) => {
+                let before = pos > 0 && is_word(input[pos - 1]);
+                let after = pos < input.len() && is_word(input[pos]);
+                if (before != after) == *expected {
+                    stack.push(pc + 1);
+                }
+            }
+            Inst::Char(_) | Inst::Any | Inst::Class(_) => threads.push(pc),
+        }
+    }
+    false
+}
+
+struct PatternParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl PatternParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.pos += 1;
+        c
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_alternation(&mut self) -> Result<Node, String> {
+        let mut options = vec![self.parse_concat()?];
+        while self.eat('|') {
+            options.push(self.parse_concat()?);
+        }
+        Ok(if options.len() == 1 {
+            options.pop().unwrap_or(Node::Empty)
+        } else {
+            Node::Alternation(options)
+        })
+    }
+
+    fn parse_concat(&mut self) -> Result<Node, String> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.parse_atom()?;
+            nodes.push(self.parse_quantifier(atom)?);
+        }
+        Ok(match nodes.len() {
+            0 => Node::Empty,
+            1 => nodes.pop().unwrap_or(Node::Empty),
+            _ => Node::Concat(nodes),
+        })
+    }
+
+    fn parse_quantifier(&mut self, atom: Node) -> Result<Node, String> {
+        let start = self.pos;
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => match self.parse_braces() {
+                Some(bounds) => bounds,
+                // not a valid quantifier, so `{` is a literal
+                None => {
+                    self.pos = start;
+                    return Ok(atom);
+                }
+            },
+            _ => return Ok(atom),
+        };
+        if self.chars[start] != '{' {
+            self.pos += 1;
+        }
+        if let Some(max) = max
+            && max < min
+        {
+            return Err(format!(
+                "Quantifier range out of order at position {}",
+                start
+            ));
+        }
+        if matches!(atom, Node::Start | Node::End | Node::WordBoundary(_)) {
+            return Err(format!("Nothing to repeat at position {}", start));
+        }
+        // a lazy quantifier matches the same strings, only preferring fewer
+        // repetitions, which makes no difference to whether there is a match
+        self.eat('?');
+        Ok(Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+        })
+    }
+
+    // `{n}`, `{n,}` or `{n,m}`; leaves the position after `}` on success
+    fn parse_braces(&mut self) -> Option<(usize, Option<usize>)> {
+        self.bump();
+        let min = self.parse_number()?;
+        let max = if self.eat(',') {
+            if self.peek() == Some('}') {
+                None
+            } else {
+                Some(self.parse_number()?)
+            }
+        } else {
+            Some(min)
+        };
+        self.eat('}').then_some((min, max))
+    }
+
+    fn parse_number(&mut self) -> Option<usize> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .ok()
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, String> {
+        let start = self.pos;
+        match self.bump() {
+            Some('(') => {
+                if self.eat('?') && !self.eat(':') {
+                    return Err(format!("Unsupported group syntax at position {}", start));
+                }
+                let node = self.parse_alternation()?;
+                if !self.eat(')') {
+                    return Err(format!("Unclosed group at position {}", start));
+                }
+                Ok(node)
+            }
+            Some('.') => Ok(Node::Any),
+            Some('^') => Ok(Node::Start),
+            Some('$') => Ok(Node::End),
+            Some('[') => self.parse_class(start),
+            Some('\\') => self.parse_escape(),
+            Some('*' | '+' | '?') => Err(format!("Nothing to repeat at position {}", start)),
+            Some(c) => Ok(Node::Char(c)),
+            None => Err("Unexpected end of pattern".to_string()),
+        }
+    }
+
+    fn parse_escape(&mut self) -> Result<Node, String> {
+        let start = self.pos - 1;
+        match self.peek() {
+            Some('b') => {
+                self.pos += 1;
+                Ok(Node::WordBoundary(true))
+            }
+            Some('B') => {
+                self.pos += 1;
+                Ok(Node::WordBoundary(false))
+            }
+            Some('1'..='9') => Err(format!(
+                "Backreferences are not supported at position {}",
+                start
+            )),
+            _ => match self.parse_class_escape()? {
+                ClassItem::Range(low, high) if low == high => Ok(Node::Char(low)),
+                item => Ok(Node::Class(Class {
+                    negated: false,
+                    items: vec![item],
+                })),
+            },
+        }
+    }
+
+    // The escape after a `\`, as usable inside or outside a class
+    fn parse_class_escape(&mut self) -> Result<ClassItem, String> {
+        let start = self.pos;
+        let c = self
+            .bump()
+            .ok_or_else(|| "Pattern ends with '\\'".to_string())?;
+        let literal = match c {
+            'd' => return Ok(ClassItem::Digit(false)),
+            'D' => return Ok(ClassItem::Digit(true)),
+            'w' => return Ok(ClassItem::Word(false)),
+            'W' => return Ok(ClassItem::Word(true)),
+            's' => return Ok(ClassItem::Space(false)),
+            'S' => return Ok(ClassItem::Space(true)),
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'f' => '\x0c',
+            'v' => '\x0b',
+            '0' => '\0',
+            'x' => self.parse_hex(2, start)?,
+            'u' => self.parse_hex(4, start)?,
+            other => other,
+        };
+        Ok(ClassItem::Range(literal, literal))
+    }
+
+    fn parse_hex(&mut self, digits: usize, start: usize) -> Result<char, String> {
+        let end = self.pos + digits;
+        let hex: String = self
+            .chars
+            .get(self.pos..end)
+            .unwrap_or_default()
+            .iter()
+            .collect();
+        let code = u32::from_str_radix(&hex, 16)
+            .ok()
+            .filter(|_| hex.len() == digits)
+            .ok_or_else(|| format!("Invalid hex escape at position {}", start))?;
+        self.pos = end;
+        char::from_u32(code).ok_or_else(|| format!("Invalid hex escape at position {}", start))
+    }
+
+    fn parse_class(&mut self, start: usize) -> Result<Node, String> {
+        let negated = self.eat('^');
+        let mut items = Vec::new();
+        loop {
+            let item = match self.bump() {
+                None => return Err(format!("Unclosed character class at position {}", start)),
+                Some(']') => break,
+                Some('\\') => self.parse_class_escape()?,
+                Some(c) => ClassItem::Range(c, c),
+            };
+            // `a-z` ranges; a `-` before `]` is a literal
+            if let ClassItem::Range(low, _) = item
+                && self.peek() == Some('-')
+                && self.chars.get(self.pos + 1).is_some_and(|c| *c != ']')
+            {
+                self.pos += 1;
+                let high = match self.bump() {
+                    Some('\\') => match self.parse_class_escape()? {
+                        ClassItem::Range(high, _) => high,
+                        _ => return Err(format!("Invalid class range at position {}", start)),
+                    },
+                    Some(c) => c,
+                    None => return Err(format!("Unclosed character class at position {}", start)),
+                };
+                if high < low {
+                    return Err(format!("Class range out of order at position {}", start));
+                }
+                items.push(ClassItem::Range(low, high));
+            } else {
+                items.push(item);
+            }
+        }
+        Ok(Node::Class(Class { negated, items }))
+    }
+}
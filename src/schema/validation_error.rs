@@ -0,0 +1,52 @@
+/// A single schema violation found by `JsonSchema::validate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    instance_path: String,
+    schema_path: String,
+    message: String,
+}
+
+impl ValidationError {
+    pub fn new(
+        instance_path: impl Into<String>,
+        schema_path: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            instance_path: instance_path.into(),
+            schema_path: schema_path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// JSON Pointer to the offending value in the instance; `""` for the root.
+    pub fn instance_path(&self) -> &str {
+        &self.instance_path
+    }
+
+    /// JSON Pointer to the failing keyword in the schema, after resolving `$ref`.
+    pub fn schema_path(&self) -> &str {
+        &self.schema_path
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let instance_path = if self.instance_path.is_empty() {
+            "/"
+        } else {
+            &self.instance_path
+        };
+        write!(
+            f,
+            "{} at {} (schema: #{})",
+            self.message, instance_path, self.schema_path
+        )
+    }
+}
+
+impl std::error::Error for ValidationError {}
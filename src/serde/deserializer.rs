@@ -0,0 +1,315 @@
+use std::path::Path;
+
+use ::serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor, value::StringDeserializer,
+};
+
+use crate::{
+    core::{token::Token, tokentype::TokenType},
+    lexer::tokenizer::Tokenizer,
+    serde::error::SerdeError,
+    stream::token_stream::{self, TokenStream},
+};
+
+/// A serde `Deserializer` that pulls tokens straight from a `Tokenizer`,
+/// without building an intermediate `JsonValue`. Tokens are released as they
+/// are read, so memory does not grow with the input.
+pub struct Deserializer {
+    stream: TokenStream,
+}
+
+/// Deserializes a `T` from a JSON string.
+pub fn from_str<T: DeserializeOwned>(json: &str) -> Result<T, SerdeError> {
+    from_tokenizer(Tokenizer::new(Some(json.to_string()), None))
+}
+
+/// Deserializes a `T` from a JSON file, streaming it through the buffered file reader.
+pub fn from_file<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T, SerdeError> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return Err(SerdeError::new(format!(
+            "File not found: {}",
+            path.display()
+        )));
+    }
+    from_tokenizer(Tokenizer::new(
+        None,
+        Some(path.to_string_lossy().into_owned()),
+    ))
+}
+
+/// Deserializes a `T` from the tokens of `tokenizer`, which must hold exactly one value.
+pub fn from_tokenizer<T: DeserializeOwned>(tokenizer: Tokenizer) -> Result<T, SerdeError> {
+    let mut deserializer = Deserializer::new(tokenizer);
+    let value = T::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+impl Deserializer {
+    pub fn new(tokenizer: Tokenizer) -> Self {
+        Self {
+            stream: TokenStream::new(tokenizer),
+        }
+    }
+
+    /// Checks that nothing but whitespace follows the value read so far.
+    pub fn end(&mut self) -> Result<(), SerdeError> {
+        let token = self.next()?;
+        if token.token_type() == TokenType::EOF {
+            Ok(())
+        } else {
+            Err(unexpected(&token, "end of input"))
+        }
+    }
+
+    /// The tokenizer being read, which keeps only the tokens not yet consumed.
+    pub fn tokenizer(&self) -> &Tokenizer {
+        self.stream.tokenizer()
+    }
+
+    fn peek(&mut self) -> Result<TokenType, SerdeError> {
+        Ok(self.stream.peek()?)
+    }
+
+    fn next(&mut self) -> Result<Token, SerdeError> {
+        Ok(self.stream.next()?)
+    }
+
+    fn expect(&mut self, token_type: TokenType, expected: &str) -> Result<Token, SerdeError> {
+        Ok(self.stream.expect(token_type, expected)?)
+    }
+
+    fn string(token: &Token) -> Result<String, SerdeError> {
+        Ok(TokenStream::string(token)?)
+    }
+
+    fn visit_number<'de, V: Visitor<'de>>(
+        token: &Token,
+        negative: bool,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        let raw = token.value().map(String::as_str).unwrap_or_default();
+        let text = if negative {
+            format!("-{}", raw)
+        } else {
+            raw.to_string()
+        };
+
+        // Integers keep full 64-bit precision; everything else goes through f64
+        if !text.contains(['.', 'e', 'E']) {
+            if let Ok(n) = text.parse::<u64>() {
+                return visitor.visit_u64(n);
+            }
+            if let Ok(n) = text.parse::<i64>() {
+                return visitor.visit_i64(n);
+            }
+        }
+        match text.parse::<f64>() {
+            Ok(n) => visitor.visit_f64(n),
+            Err(_) => Err(SerdeError::new(format!(
+                "Invalid number '{}' at position {}",
+                text,
+                token.start_pos()
+            ))),
+        }
+    }
+}
+
+fn unexpected(token: &Token, expected: &str) -> SerdeError {
+    SerdeError::new(token_stream::unexpected(token, expected))
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        let token = self.next()?;
+        match token.token_type() {
+            TokenType::Null => visitor.visit_unit(),
+            TokenType::Boolean => visitor.visit_bool(token.value().is_some_and(|v| v == "true")),
+            TokenType::Number => Deserializer::visit_number(&token, false, visitor),
+            TokenType::MinusSign => {
+                let number = self.expect(TokenType::Number, "number")?;
+                Deserializer::visit_number(&number, true, visitor)
+            }
+            TokenType::Text => visitor.visit_string(Deserializer::string(&token)?),
+            TokenType::LeftSquareBracket => {
+                let value = visitor.visit_seq(Elements {
+                    de: self,
+                    first: true,
+                })?;
+                self.expect(TokenType::RightSquareBracket, "',' or ']'")?;
+                Ok(value)
+            }
+            TokenType::LeftBrace => {
+                let value = visitor.visit_map(Members {
+                    de: self,
+                    first: true,
+                })?;
+                self.expect(TokenType::RightBrace, "',' or '}'")?;
+                Ok(value)
+            }
+            _ => Err(unexpected(&token, "value")),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        if self.peek()? == TokenType::Null {
+            self.next()?;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    // Unit variants are plain strings, all others `{"Variant": content}`
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        match self.peek()? {
+            TokenType::Text => {
+                let token = self.next()?;
+                visitor.visit_enum(Deserializer::string(&token)?.into_deserializer())
+            }
+            TokenType::LeftBrace => {
+                self.next()?;
+                let value = visitor.visit_enum(Variant { de: self })?;
+                self.expect(TokenType::RightBrace, "'}'")?;
+                Ok(value)
+            }
+            _ => {
+                let token = self.next()?;
+                Err(unexpected(&token, "string or object"))
+            }
+        }
+    }
+
+    ::serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct Elements<'a> {
+    de: &'a mut Deserializer,
+    first: bool,
+}
+
+impl<'de> SeqAccess<'de> for Elements<'_> {
+    type Error = SerdeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, SerdeError> {
+        if self.de.peek()? == TokenType::RightSquareBracket {
+            return Ok(None);
+        }
+        if !self.first {
+            self.de.expect(TokenType::Comma, "',' or ']'")?;
+        }
+        self.first = false;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+struct Members<'a> {
+    de: &'a mut Deserializer,
+    first: bool,
+}
+
+impl<'de> MapAccess<'de> for Members<'_> {
+    type Error = SerdeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, SerdeError> {
+        if self.de.peek()? == TokenType::RightBrace {
+            return Ok(None);
+        }
+        if !self.first {
+            self.de.expect(TokenType::Comma, "',' or '}'")?;
+        }
+        self.first = false;
+        let key = self.de.expect(TokenType::Text, "object key")?;
+        let key = seed.deserialize(StringDeserializer::<SerdeError>::new(Deserializer::string(
+            &key,
+        )?))?;
+        self.de.expect(TokenType::Colon, "':'")?;
+        Ok(Some(key))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, SerdeError> {
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+struct Variant<'a> {
+    de: &'a mut Deserializer,
+}
+
+impl<'de> EnumAccess<'de> for Variant<'_> {
+    type Error = SerdeError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), SerdeError> {
+        let name = self.de.expect(TokenType::Text, "variant name")?;
+        let name = seed.deserialize(StringDeserializer::<SerdeError>::new(
+            Deserializer::string(&name)?,
+        ))?;
+        self.de.expect(TokenType::Colon, "':'")?;
+        Ok((name, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Variant<'_> {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> Result<(), SerdeError> {
+        de::Deserialize::deserialize(self.de)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, SerdeError> {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        de::Deserializer::deserialize_seq(self.de, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        de::Deserializer::deserialize_map(self.de, visitor)
+    }
+}
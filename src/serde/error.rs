@@ -0,0 +1,51 @@
+use std::fmt::Display;
+
+/// Error raised by the serde `Serializer` and `Deserializer`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerdeError {
+    message: String,
+}
+
+impl SerdeError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for SerdeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for SerdeError {}
+
+impl ::serde::ser::Error for SerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::new(msg.to_string())
+    }
+}
+
+impl ::serde::de::Error for SerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::new(msg.to_string())
+    }
+}
+
+impl From<String> for SerdeError {
+    fn from(message: String) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<SerdeError> for String {
+    fn from(error: SerdeError) -> Self {
+        error.message
+    }
+}
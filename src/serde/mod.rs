@@ -0,0 +1,4 @@
+pub mod deserializer;
+pub mod error;
+pub mod json_value_impls;
+pub mod serializer;
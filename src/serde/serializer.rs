@@ -0,0 +1,579 @@
+use ::serde::ser::{self, Impossible, Serialize};
+
+use crate::{
+    core::{json_value::JsonValue, stringify_options::StringifyOptions},
+    serde::error::SerdeError,
+};
+
+/// A serde `Serializer` writing compact JSON through the crate's string escaper.
+#[derive(Default)]
+pub struct Serializer {
+    output: String,
+    options: StringifyOptions,
+}
+
+/// Serializes `value` as compact JSON.
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, SerdeError> {
+    to_string_with(value, &StringifyOptions::default())
+}
+
+/// Serializes `value` as compact JSON, escaping strings according to `options`.
+pub fn to_string_with<T: Serialize + ?Sized>(
+    value: &T,
+    options: &StringifyOptions,
+) -> Result<String, SerdeError> {
+    let mut serializer = Serializer::new().with_options(*options);
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_inner())
+}
+
+impl Serializer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(mut self, options: StringifyOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn into_inner(self) -> String {
+        self.output
+    }
+
+    fn write_str(&mut self, s: &str) {
+        JsonValue::write_string(s, &mut self.output, &self.options);
+    }
+
+    fn write_key(&mut self, key: &str) {
+        JsonValue::write_key(key, &mut self.output, &self.options);
+    }
+
+    // Non-finite floats have no JSON representation and are written as null,
+    // except in JSON5 output
+    fn write_float(&mut self, n: f64) {
+        if n.is_finite() || self.options.json5() {
+            JsonValue::write_number(n, &mut self.output, &self.options);
+        } else {
+            self.output.push_str("null");
+        }
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = SerdeError;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), SerdeError> {
+        self.output.push_str(if v { "true" } else { "false" });
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), SerdeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), SerdeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), SerdeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), SerdeError> {
+        self.output.push_str(&v.to_string());
+        Ok(())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), SerdeError> {
+        self.output.push_str(&v.to_string());
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), SerdeError> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), SerdeError> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), SerdeError> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), SerdeError> {
+        self.output.push_str(&v.to_string());
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), SerdeError> {
+        self.output.push_str(&v.to_string());
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), SerdeError> {
+        self.write_float(v as f64);
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), SerdeError> {
+        self.write_float(v);
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), SerdeError> {
+        self.write_str(v.encode_utf8(&mut [0; 4]));
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), SerdeError> {
+        self.write_str(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), SerdeError> {
+        v.serialize(self)
+    }
+
+    fn serialize_none(self) -> Result<(), SerdeError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SerdeError> {
+        self.output.push_str("null");
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerdeError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), SerdeError> {
+        self.write_str(variant);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        self.output.push('{');
+        self.write_str(variant);
+        self.output.push(':');
+        value.serialize(&mut *self)?;
+        self.output.push('}');
+        Ok(())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a>, SerdeError> {
+        self.output.push('[');
+        Ok(Compound::new(self, "]"))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'a>, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Compound<'a>, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, SerdeError> {
+        self.output.push('{');
+        self.write_str(variant);
+        self.output.push_str(":[");
+        Ok(Compound::new(self, "]}"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a>, SerdeError> {
+        self.output.push('{');
+        Ok(Compound::new(self, "}"))
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Compound<'a>, SerdeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, SerdeError> {
+        self.output.push('{');
+        self.write_str(variant);
+        self.output.push_str(":{");
+        Ok(Compound::new(self, "}}"))
+    }
+}
+
+/// Writes the items of an array or the members of an object, then `close`.
+pub struct Compound<'a> {
+    ser: &'a mut Serializer,
+    first: bool,
+    close: &'static str,
+}
+
+impl<'a> Compound<'a> {
+    fn new(ser: &'a mut Serializer, close: &'static str) -> Self {
+        Self {
+            ser,
+            first: true,
+            close,
+        }
+    }
+
+    fn separator(&mut self) {
+        if !self.first {
+            self.ser.output.push(',');
+        }
+        self.first = false;
+    }
+
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.separator();
+        value.serialize(&mut *self.ser)
+    }
+
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), SerdeError> {
+        self.separator();
+        self.ser.write_str(key);
+        self.ser.output.push(':');
+        value.serialize(&mut *self.ser)
+    }
+
+    fn close(self) -> Result<(), SerdeError> {
+        self.ser.output.push_str(self.close);
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        self.close()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerdeError> {
+        self.separator();
+        key.serialize(KeySerializer { ser: self.ser })?;
+        self.ser.output.push(':');
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        self.close()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        self.close()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        self.close()
+    }
+}
+
+// Object keys must be strings; scalars are accepted and written quoted
+struct KeySerializer<'a> {
+    ser: &'a mut Serializer,
+}
+
+fn key_must_be_string() -> SerdeError {
+    SerdeError::new("Object keys must be strings")
+}
+
+impl KeySerializer<'_> {
+    fn quoted(self, key: impl ToString) -> Result<(), SerdeError> {
+        self.ser.write_key(&key.to_string());
+        Ok(())
+    }
+}
+
+impl ser::Serializer for KeySerializer<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+    type SerializeSeq = Impossible<(), SerdeError>;
+    type SerializeTuple = Impossible<(), SerdeError>;
+    type SerializeTupleStruct = Impossible<(), SerdeError>;
+    type SerializeTupleVariant = Impossible<(), SerdeError>;
+    type SerializeMap = Impossible<(), SerdeError>;
+    type SerializeStruct = Impossible<(), SerdeError>;
+    type SerializeStructVariant = Impossible<(), SerdeError>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), SerdeError> {
+        self.quoted(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), SerdeError> {
+        self.quoted(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), SerdeError> {
+        self.quoted(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), SerdeError> {
+        self.quoted(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), SerdeError> {
+        self.quoted(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), SerdeError> {
+        self.quoted(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), SerdeError> {
+        self.quoted(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), SerdeError> {
+        self.quoted(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), SerdeError> {
+        self.quoted(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), SerdeError> {
+        self.quoted(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), SerdeError> {
+        self.quoted(v)
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<(), SerdeError> {
+        Err(key_must_be_string())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<(), SerdeError> {
+        Err(key_must_be_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), SerdeError> {
+        self.quoted(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), SerdeError> {
+        self.ser.write_key(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), SerdeError> {
+        Err(key_must_be_string())
+    }
+
+    fn serialize_none(self) -> Result<(), SerdeError> {
+        Err(key_must_be_string())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<(), SerdeError> {
+        Err(key_must_be_string())
+    }
+
+    fn serialize_unit(self) -> Result<(), SerdeError> {
+        Err(key_must_be_string())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerdeError> {
+        Err(key_must_be_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), SerdeError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), SerdeError> {
+        Err(key_must_be_string())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Err(key_must_be_string())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        Err(key_must_be_string())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        Err(key_must_be_string())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        Err(key_must_be_string())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        Err(key_must_be_string())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, SerdeError> {
+        Err(key_must_be_string())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SerdeError> {
+        Err(key_must_be_string())
+    }
+}
@@ -0,0 +1,110 @@
+use std::mem;
+
+use crate::{
+    core::{
+        json_pointer::{parse_index, parse_pointer},
+        json_value::JsonValue,
+        tokentype::TokenType,
+    },
+    lexer::{byte_reader::ByteReader, tokenizer::Tokenizer},
+    stream::token_stream::TokenStream,
+};
+
+/// Iterates over the elements of an array in a document streamed from a
+/// reader, building one element at a time so memory is bounded by the
+/// largest element rather than the array.
+///
+/// Values before the array are checked and skipped without being built;
+/// nothing after its closing bracket is read. Iteration ends after the
+/// first error.
+pub struct ArrayStream {
+    stream: TokenStream,
+    first: bool,
+    done: bool,
+}
+
+impl ArrayStream {
+    /// Reads `reader` up to the opening bracket of the array at `pointer`,
+    /// `""` for the root.
+    pub fn open(reader: impl ByteReader + 'static, pointer: &str) -> Result<Self, String> {
+        let mut stream = TokenStream::new(Tokenizer::from_reader(reader));
+        for token in parse_pointer(pointer)? {
+            if !Self::descend(&mut stream, &token)? {
+                return Err(format!("No value at JSON Pointer: {}", pointer));
+            }
+        }
+        if stream.peek()? != TokenType::LeftSquareBracket {
+            return Err(format!(
+                "Value at JSON Pointer {:?} is not an array",
+                pointer
+            ));
+        }
+        stream.next()?;
+
+        Ok(Self {
+            stream,
+            first: true,
+            done: false,
+        })
+    }
+
+    // moves to the member or element named by `token`, if it exists
+    fn descend(stream: &mut TokenStream, token: &str) -> Result<bool, String> {
+        match stream.peek()? {
+            TokenType::LeftBrace => {
+                stream.next()?;
+                let mut first = true;
+                while let Some(key) = stream.next_key(first)? {
+                    first = false;
+                    if key == token {
+                        return Ok(true);
+                    }
+                    stream.skip_value()?;
+                }
+                Ok(false)
+            }
+            TokenType::LeftSquareBracket => {
+                stream.next()?;
+                let Some(index) = parse_index(token) else {
+                    return Ok(false);
+                };
+                let mut current = 0;
+                while stream.next_entry(current == 0, TokenType::RightSquareBracket)? {
+                    if current == index {
+                        return Ok(true);
+                    }
+                    stream.skip_value()?;
+                    current += 1;
+                }
+                Ok(false)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn next_element(&mut self) -> Result<Option<JsonValue>, String> {
+        let first = mem::replace(&mut self.first, false);
+        if !self
+            .stream
+            .next_entry(first, TokenType::RightSquareBracket)?
+        {
+            return Ok(None);
+        }
+        self.stream.read_value().map(Some)
+    }
+}
+
+impl Iterator for ArrayStream {
+    type Item = Result<JsonValue, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let element = self.next_element();
+        if !matches!(element, Ok(Some(_))) {
+            self.done = true;
+        }
+        element.transpose()
+    }
+}
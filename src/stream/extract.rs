@@ -0,0 +1,125 @@
+use crate::{
+    core::{
+        json_pointer::{build_pointer, parse_pointer},
+        json_value::JsonValue,
+        tokentype::TokenType,
+    },
+    lexer::{byte_reader::ByteReader, tokenizer::Tokenizer},
+    stream::token_stream::TokenStream,
+};
+
+/// Reads the values at `pointers` from a document streamed from `reader`,
+/// without building the rest of it.
+///
+/// A `*` segment matches every member or element at its level, so
+/// `/items/*/id` finds the `id` of each item. Matches are returned in
+/// document order, each with its concrete pointer; a pointer inside a value
+/// that already matched is not reported separately. Everything off the
+/// requested paths is checked and skipped without being built, and once
+/// every pointer has matched, if none has a wildcard, the rest of the input
+/// is not read.
+pub fn extract(
+    reader: impl ByteReader + 'static,
+    pointers: &[&str],
+) -> Result<Vec<(String, JsonValue)>, String> {
+    let patterns = pointers
+        .iter()
+        .map(|pointer| parse_pointer(pointer))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut extractor = Extractor {
+        stream: TokenStream::new(Tokenizer::from_reader(reader)),
+        found: vec![false; patterns.len()],
+        wildcard: patterns.iter().flatten().any(|token| token == "*"),
+        patterns,
+        path: Vec::new(),
+        matches: Vec::new(),
+    };
+
+    extractor.walk()?;
+    if !extractor.is_done() {
+        extractor.stream.expect(TokenType::EOF, "end of input")?;
+    }
+    Ok(extractor.matches)
+}
+
+struct Extractor {
+    stream: TokenStream,
+    patterns: Vec<Vec<String>>,
+    found: Vec<bool>,
+    wildcard: bool,
+    // the reference tokens leading to the value about to be read
+    path: Vec<String>,
+    matches: Vec<(String, JsonValue)>,
+}
+
+impl Extractor {
+    fn walk(&mut self) -> Result<(), String> {
+        let mut matched = false;
+        for (pattern, found) in self.patterns.iter().zip(&mut self.found) {
+            if pattern.len() == self.path.len() && Self::on_path(pattern, &self.path) {
+                *found = true;
+                matched = true;
+            }
+        }
+        if matched {
+            let value = self.stream.read_value()?;
+            self.matches.push((build_pointer(&self.path), value));
+            return Ok(());
+        }
+
+        let leads_somewhere = self
+            .patterns
+            .iter()
+            .any(|pattern| pattern.len() > self.path.len() && Self::on_path(pattern, &self.path));
+        if !leads_somewhere {
+            return self.stream.skip_value();
+        }
+
+        match self.stream.peek()? {
+            TokenType::LeftBrace => {
+                self.stream.next()?;
+                let mut first = true;
+                while let Some(key) = self.stream.next_key(first)? {
+                    first = false;
+                    self.path.push(key);
+                    self.walk()?;
+                    self.path.pop();
+                    if self.is_done() {
+                        break;
+                    }
+                }
+                Ok(())
+            }
+            TokenType::LeftSquareBracket => {
+                self.stream.next()?;
+                let mut index = 0;
+                while self
+                    .stream
+                    .next_entry(index == 0, TokenType::RightSquareBracket)?
+                {
+                    self.path.push(index.to_string());
+                    self.walk()?;
+                    self.path.pop();
+                    if self.is_done() {
+                        break;
+                    }
+                    index += 1;
+                }
+                Ok(())
+            }
+            _ => self.stream.skip_value(),
+        }
+    }
+
+    // whether `path` is a prefix of the paths `pattern` matches
+    fn on_path(pattern: &[String], path: &[String]) -> bool {
+        pattern
+            .iter()
+            .zip(path)
+            .all(|(expected, token)| expected == "*" || expected == token)
+    }
+
+    fn is_done(&self) -> bool {
+        !self.wildcard && self.found.iter().all(|found| *found)
+    }
+}
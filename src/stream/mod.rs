@@ -0,0 +1,3 @@
+pub mod array_stream;
+pub mod extract;
+pub mod token_stream;
@@ -0,0 +1,149 @@
+use crate::{
+    core::{json_value::JsonValue, token::Token, tokentype::TokenType},
+    lexer::tokenizer::Tokenizer,
+    parser::{parser::Parser, value_builder::ValueBuilder},
+};
+
+/// Reads values straight off a `Tokenizer` one token at a time, releasing
+/// each token once it has been read so memory stays bounded by the value
+/// being built rather than the whole input.
+pub(crate) struct TokenStream {
+    tokenizer: Tokenizer,
+    peeked: Option<Token>,
+}
+
+impl TokenStream {
+    pub(crate) fn new(tokenizer: Tokenizer) -> Self {
+        Self {
+            tokenizer,
+            peeked: None,
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn tokenizer(&self) -> &Tokenizer {
+        &self.tokenizer
+    }
+
+    pub(crate) fn next(&mut self) -> Result<Token, String> {
+        if let Some(token) = self.peeked.take() {
+            return Ok(token);
+        }
+        let token = self.tokenizer.next_token()?;
+        self.tokenizer.release_tokens();
+        Ok(token)
+    }
+
+    pub(crate) fn peek(&mut self) -> Result<TokenType, String> {
+        let token = match self.peeked.take() {
+            Some(token) => token,
+            None => self.next()?,
+        };
+        let token_type = token.token_type();
+        self.peeked = Some(token);
+        Ok(token_type)
+    }
+
+    pub(crate) fn expect(
+        &mut self,
+        token_type: TokenType,
+        expected: &str,
+    ) -> Result<Token, String> {
+        let token = self.next()?;
+        if token.token_type() == token_type {
+            Ok(token)
+        } else {
+            Err(unexpected(&token, expected))
+        }
+    }
+
+    /// Moves to the next entry of the container whose opening bracket, or
+    /// previous entry, was just read; `false` once `close` is reached.
+    pub(crate) fn next_entry(&mut self, first: bool, close: TokenType) -> Result<bool, String> {
+        if first {
+            if self.peek()? == close {
+                self.next()?;
+                return Ok(false);
+            }
+            return Ok(true);
+        }
+        let token = self.next()?;
+        match token.token_type() {
+            TokenType::Comma => Ok(true),
+            token_type if token_type == close => Ok(false),
+            _ => Err(unexpected(&token, "',' or closing bracket")),
+        }
+    }
+
+    /// Reads the key and colon of the next object member, or `None` at the
+    /// closing brace.
+    pub(crate) fn next_key(&mut self, first: bool) -> Result<Option<String>, String> {
+        if !self.next_entry(first, TokenType::RightBrace)? {
+            return Ok(None);
+        }
+        let key = self.expect(TokenType::Text, "object key")?;
+        self.expect(TokenType::Colon, "':'")?;
+        Ok(Some(Self::string(&key)?))
+    }
+
+    pub(crate) fn read_value(&mut self) -> Result<JsonValue, String> {
+        let mut builder = ValueBuilder::new(self.tokenizer.options().json5());
+        while !builder.is_complete() {
+            builder.push(&self.next()?)?;
+        }
+        builder
+            .into_value()
+            .ok_or_else(|| "No JSON value found".to_string())
+    }
+
+    /// Checks the structure of the next value and moves past it without
+    /// building it; the tokenizer does not even keep the text of its strings
+    /// and numbers.
+    pub(crate) fn skip_value(&mut self) -> Result<(), String> {
+        self.tokenizer.set_keep_values(false);
+        let skipped = self.skip_next_value();
+        self.tokenizer.set_keep_values(true);
+        skipped
+    }
+
+    fn skip_next_value(&mut self) -> Result<(), String> {
+        let token = self.next()?;
+        match token.token_type() {
+            TokenType::LeftBrace => {
+                let mut first = true;
+                while self.next_entry(first, TokenType::RightBrace)? {
+                    first = false;
+                    self.expect(TokenType::Text, "object key")?;
+                    self.expect(TokenType::Colon, "':'")?;
+                    self.skip_next_value()?;
+                }
+                Ok(())
+            }
+            TokenType::LeftSquareBracket => {
+                let mut first = true;
+                while self.next_entry(first, TokenType::RightSquareBracket)? {
+                    first = false;
+                    self.skip_next_value()?;
+                }
+                Ok(())
+            }
+            TokenType::MinusSign => self.expect(TokenType::Number, "number").map(|_| ()),
+            TokenType::Text | TokenType::Number | TokenType::Boolean | TokenType::Null => Ok(()),
+            _ => Err(unexpected(&token, "value")),
+        }
+    }
+
+    pub(crate) fn string(token: &Token) -> Result<String, String> {
+        let raw = token.value().map(String::as_str).unwrap_or_default();
+        Parser::unescape_string(raw, false)
+    }
+}
+
+pub(crate) fn unexpected(token: &Token, expected: &str) -> String {
+    format!(
+        "Expected {}, found {} at position {}",
+        expected,
+        token.token_type(),
+        token.start_pos()
+    )
+}
@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+
+use parse_light::json;
+use parse_light::lexer::buffered_file_reader::BufferedFileReader;
+use parse_light::lexer::buffered_string_reader::BufferedStringReader;
+use parse_light::stream::array_stream::ArrayStream;
+
+fn open(json: &str, pointer: &str) -> Result<ArrayStream, String> {
+    ArrayStream::open(BufferedStringReader::new(json.as_bytes().to_vec()), pointer)
+}
+
+#[test]
+fn test_streams_array_from_file() {
+    let reader =
+        BufferedFileReader::new(PathBuf::from("tests/data/sample1.json")).with_chunk_size(64);
+    let flights = ArrayStream::open(reader, "/flights")
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(flights.len(), 10);
+    assert_eq!(flights[0]["flight_number"], json!("DL8509"));
+    assert_eq!(flights[9]["flight_number"], json!("SA9026"));
+}
+
+#[test]
+fn test_root_and_nested_arrays() {
+    let elements: Vec<_> = open(r#"[1, "two", {"three": [3]}, []]"#, "")
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(
+        elements,
+        vec![json!(1), json!("two"), json!({"three": [3]}), json!([])]
+    );
+
+    let json = r#"{"skip": {"deep": [1, 2]}, "pages": [{"rows": [4]}, {"rows": [5, 6]}]}"#;
+    let rows: Vec<_> = open(json, "/pages/1/rows")
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(rows, vec![json!(5), json!(6)]);
+
+    assert_eq!(open("[]", "").unwrap().count(), 0);
+}
+
+#[test]
+fn test_open_errors() {
+    let json = r#"{"items": {"a": 1}, "list": [1]}"#;
+    assert!(open(json, "/missing").is_err());
+    assert!(open(json, "/list/3").is_err());
+    assert!(open(json, "/items").is_err());
+    assert!(open(json, "items").is_err());
+}
+
+#[test]
+fn test_stops_after_first_error() {
+    let mut elements = open(r#"[1, {"a" 2}, 3]"#, "").unwrap();
+    assert_eq!(elements.next(), Some(Ok(json!(1))));
+    assert!(elements.next().unwrap().is_err());
+    assert_eq!(elements.next(), None);
+
+    // nothing after the array is read
+    let elements: Vec<_> = open(r#"{"a": [1, 2], "b": oops"#, "/a").unwrap().collect();
+    assert_eq!(elements, vec![Ok(json!(1)), Ok(json!(2))]);
+}
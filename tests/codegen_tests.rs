@@ -0,0 +1,197 @@
+use std::path::PathBuf;
+
+use parse_light::codegen::rust_generator::RustGenerator;
+use parse_light::codegen::type_model::{FieldType, TypeModel};
+use parse_light::codegen::typescript_generator::TypeScriptGenerator;
+use parse_light::core::json_value::JsonValue;
+use parse_light::{json, parse};
+
+fn data_path(file_name: &str) -> String {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join(file_name)
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn samples() -> Vec<JsonValue> {
+    vec![
+        json!({
+            "orderId": 1,
+            "type": "retail",
+            "customer": {"name": "Ann", "email": "ann@example.com"},
+            "items": [{"sku": "A-1", "qty": 2, "price": 9.5}],
+            "shipTo": {"city": "Oslo", "zip-code": "0150"},
+            "billTo": {"city": "Oslo", "zip-code": "0150"},
+            "notes": null
+        }),
+        json!({
+            "orderId": 2,
+            "type": "retail",
+            "customer": {"name": "Bob"},
+            "items": [],
+            "shipTo": {"city": "Rome", "zip-code": "00100"},
+            "billTo": {"city": "Rome", "zip-code": "00100"},
+            "notes": "leave at door",
+            "self": true
+        }),
+    ]
+}
+
+#[test]
+fn test_generate_rust_structs() {
+    let model = TypeModel::from_samples("order", samples().iter());
+    assert_eq!(
+        RustGenerator::new().generate(&model),
+        r#"use parse_light::{FromJson, ToJson};
+
+#[derive(Debug, Clone, PartialEq, FromJson, ToJson)]
+pub struct Order {
+    #[json(rename = "billTo")]
+    pub bill_to: BillTo,
+    pub customer: Customer,
+    pub items: Vec<Item>,
+    pub notes: Option<String>,
+    #[json(rename = "orderId")]
+    pub order_id: i64,
+    #[json(rename = "self")]
+    pub self_: Option<bool>,
+    #[json(rename = "shipTo")]
+    pub ship_to: BillTo,
+    pub r#type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, FromJson, ToJson)]
+pub struct BillTo {
+    pub city: String,
+    #[json(rename = "zip-code")]
+    pub zip_code: String,
+}
+
+#[derive(Debug, Clone, PartialEq, FromJson, ToJson)]
+pub struct Customer {
+    pub email: Option<String>,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, FromJson, ToJson)]
+pub struct Item {
+    pub price: f64,
+    pub qty: i64,
+    pub sku: String,
+}
+"#
+    );
+
+    let serde = RustGenerator::new()
+        .with_derives(&["Debug", "Serialize", "Deserialize"])
+        .with_attribute("serde")
+        .generate(&model);
+    assert!(serde.starts_with(
+        "use serde::{Deserialize, Serialize};\n\n#[derive(Debug, Serialize, Deserialize)]\npub struct Order {\n"
+    ));
+    assert!(serde.contains("    #[serde(rename = \"orderId\")]\n"));
+}
+
+#[test]
+fn test_generate_typescript_interfaces() {
+    let model = TypeModel::from_samples("order", samples().iter());
+    assert_eq!(
+        TypeScriptGenerator::new().generate(&model),
+        r#"export interface Order {
+  billTo: BillTo;
+  customer: Customer;
+  items: Item[];
+  notes: string | null;
+  orderId: number;
+  self?: boolean;
+  shipTo: BillTo;
+  type: "retail";
+}
+
+export interface BillTo {
+  city: string;
+  "zip-code": string;
+}
+
+export interface Customer {
+  email?: string;
+  name: string;
+}
+
+export interface Item {
+  price: number;
+  qty: number;
+  sku: string;
+}
+"#
+    );
+}
+
+#[test]
+fn test_root_arrays_and_name_collisions() {
+    let documents = [json!([
+        {"id": 1, "meta": {"tags": ["a", 1]}, "owner": {"meta": {"active": true}}},
+        {"id": 2, "meta": {"tags": []}, "owner": {"meta": {"active": false}}}
+    ])];
+    let model = TypeModel::from_samples("Records", documents.iter());
+    let names: Vec<&str> = model.types().iter().map(|t| t.name()).collect();
+    assert_eq!(names, vec!["Record", "Meta", "Owner", "Meta2"]);
+    assert_eq!(
+        model.root(),
+        &FieldType::Array(Box::new(FieldType::Named("Record".to_string())))
+    );
+
+    let rust = RustGenerator::new().with_derives(&[]).generate(&model);
+    assert!(rust.starts_with(
+        "use parse_light::core::json_value::JsonValue;\n\npub type Records = Vec<Record>;\n\npub struct Record {\n"
+    ));
+    assert!(rust.contains("    pub tags: Vec<JsonValue>,\n"));
+
+    let typescript = TypeScriptGenerator::new()
+        .with_export(false)
+        .generate(&model);
+    assert!(typescript.starts_with("type Records = Record[];\n\ninterface Record {\n"));
+    assert!(typescript.contains("  tags: (number | string)[];\n"));
+}
+
+#[test]
+fn test_generate_from_sample_file() {
+    let document = parse(&data_path("sample1.json")).unwrap();
+    let model = TypeModel::from_samples("FlightData", [document].iter());
+    let rust = RustGenerator::new().generate(&model);
+    assert!(rust.contains("pub struct FlightData {\n    pub flights: Vec<Flight>,\n}\n"));
+    assert!(rust.contains("    pub duration_hours: f64,\n"));
+
+    let typescript = TypeScriptGenerator::new().generate(&model);
+    assert!(typescript.contains("export interface Flight {\n  airline_name: string;\n"));
+}
+
+#[test]
+fn test_colliding_field_names() {
+    let sample = json!({"fooBar": 1, "foo_bar": "x", "é": 1, "ü": 2, "type": true, "Type": null});
+    let model = TypeModel::from_samples("Row", [sample].iter());
+    assert_eq!(
+        RustGenerator::new().generate(&model),
+        r#"use parse_light::core::json_value::JsonValue;
+use parse_light::{FromJson, ToJson};
+
+#[derive(Debug, Clone, PartialEq, FromJson, ToJson)]
+pub struct Row {
+    #[json(rename = "Type")]
+    pub r#type: Option<JsonValue>,
+    #[json(rename = "fooBar")]
+    pub foo_bar: i64,
+    #[json(rename = "foo_bar")]
+    pub foo_bar_2: String,
+    #[json(rename = "type")]
+    pub type_2: bool,
+    #[json(rename = "é")]
+    pub field: i64,
+    #[json(rename = "ü")]
+    pub field_2: i64,
+}
+"#
+    );
+}
@@ -0,0 +1,72 @@
+use std::collections::{BTreeMap, HashMap};
+
+use parse_light::convert::from_json::{FromJson, FromJsonError, from_json_field};
+use parse_light::convert::to_json::ToJson;
+use parse_light::core::json_value::JsonValue;
+use parse_light::{json, stringify};
+
+#[test]
+fn test_to_json_primitives_and_collections() {
+    assert_eq!(true.to_json(), JsonValue::Boolean(true));
+    assert_eq!(7u8.to_json(), JsonValue::Number(7.0));
+    assert_eq!('x'.to_json(), JsonValue::String("x".to_string()));
+    assert_eq!(None::<i32>.to_json(), JsonValue::Null);
+    assert_eq!(stringify(&vec![Some(1), None].to_json()), "[1,null]");
+    assert_eq!(stringify(&(1, "a", false).to_json()), r#"[1,"a",false]"#);
+
+    let mut map = BTreeMap::new();
+    map.insert("b".to_string(), [1.5, 2.0]);
+    assert_eq!(stringify(&map.to_json()), r#"{"b":[1.5,2]}"#);
+}
+
+#[test]
+fn test_from_json_primitives_and_collections() {
+    assert_eq!(u16::from_json(&json!(65535)), Ok(65535));
+    assert_eq!(f32::from_json(&json!(0.5)), Ok(0.5));
+    assert_eq!(char::from_json(&json!("z")), Ok('z'));
+    assert_eq!(Option::<bool>::from_json(&json!(null)), Ok(None));
+    assert_eq!(
+        <(String, i64)>::from_json(&json!(["a", 3])),
+        Ok(("a".to_string(), 3))
+    );
+
+    let map: HashMap<String, Vec<u8>> = FromJson::from_json(&json!({"k": [1, 2]})).unwrap();
+    assert_eq!(map["k"], vec![1, 2]);
+}
+
+#[test]
+fn test_from_json_errors() {
+    let err = u8::from_json(&json!(256)).unwrap_err();
+    assert_eq!(err.message(), "Number 256 does not fit in u8");
+    assert_eq!(
+        i32::from_json(&json!(1.5)).unwrap_err().message(),
+        "Number 1.5 does not fit in i32"
+    );
+    assert_eq!(
+        bool::from_json(&json!("yes")).unwrap_err().message(),
+        "Expected boolean, found string"
+    );
+
+    let err = Vec::<HashMap<String, u32>>::from_json(&json!([{"a/b": 1}, {"c": "x"}])).unwrap_err();
+    assert_eq!(err.path(), "/1/c");
+    assert_eq!(err.to_string(), "Expected integer, found string at /1/c");
+
+    let nested = json!({"a/b": {"c~d": true}});
+    let err = HashMap::<String, HashMap<String, String>>::from_json(&nested).unwrap_err();
+    assert_eq!(err.path(), "/a~1b/c~0d");
+
+    let message: String = FromJsonError::new("boom").within("x").into();
+    assert_eq!(message, "boom at /x");
+}
+
+#[test]
+fn test_from_json_field() {
+    let object = json!({"n": 1});
+    let object = object.as_object().unwrap();
+    assert_eq!(from_json_field::<u8>(object, "n"), Ok(1));
+    assert_eq!(from_json_field::<Option<u8>>(object, "m"), Ok(None));
+    assert_eq!(
+        from_json_field::<u8>(object, "m").unwrap_err().message(),
+        "Missing field 'm'"
+    );
+}
@@ -0,0 +1,101 @@
+use std::fs;
+
+use parse_light::core::parse_options::ParseOptions;
+use parse_light::core::tokentype::TokenType;
+use parse_light::cst::syntax_node::NodeKind;
+use parse_light::cst::syntax_tree::SyntaxTree;
+use parse_light::lexer::tokenizer::Tokenizer;
+use parse_light::{json, parse_with};
+
+#[test]
+fn test_round_trips_byte_for_byte() {
+    let inputs = [
+        "{}",
+        "  [ ]\n",
+        "{\r\n\t\"a\" :  1 ,\"b\":[true,\tnull , \"x\\\"y\"]\r\n}\n\n",
+        "[1.5e3,   {\"nested\":{ }}  ]",
+        "\"just a string\"  ",
+    ];
+    for input in inputs {
+        let tree = SyntaxTree::parse(input, &ParseOptions::default()).unwrap();
+        assert_eq!(tree.to_string(), input);
+    }
+}
+
+#[test]
+fn test_comments_are_preserved() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/tsconfig.jsonc");
+    let source = fs::read_to_string(path).unwrap();
+    let options = ParseOptions::new().with_allow_comments(true);
+    let tree = SyntaxTree::parse(&source, &options).unwrap();
+    assert_eq!(tree.to_string(), source);
+
+    let comments = tree
+        .root()
+        .tokens()
+        .into_iter()
+        .filter(|token| token.token_type() == TokenType::Comment)
+        .count();
+    assert!(comments > 0);
+
+    let json5 = "// settings\n{\n  port: 0x1F, /* hex */\n  'name': 'x',\n}\n";
+    let tree = SyntaxTree::parse(json5, &ParseOptions::new().with_json5(true)).unwrap();
+    assert_eq!(tree.to_string(), json5);
+}
+
+#[test]
+fn test_tree_structure() {
+    let source = "{ \"a\" : [1, 2], // two\n \"b\": null }";
+    let options = ParseOptions::new().with_allow_comments(true);
+    let tree = SyntaxTree::parse(source, &options).unwrap();
+
+    let object = tree.value().unwrap();
+    assert_eq!(object.kind(), NodeKind::Object);
+    let members: Vec<_> = object.members().collect();
+    assert_eq!(members.len(), 2);
+    assert_eq!(members[0].key().unwrap().text(), "\"a\"");
+    assert_eq!(members[0].to_string(), "\"a\" : [1, 2]");
+
+    let array = members[0].value().unwrap();
+    let elements: Vec<String> = array.elements().map(|e| e.to_string()).collect();
+    assert_eq!(elements, vec!["1", "2"]);
+    assert_eq!(members[1].value().unwrap().kind(), NodeKind::Scalar);
+
+    let span = members[1].span().unwrap();
+    assert_eq!((span.start_line(), span.start_col()), (2, 2));
+}
+
+#[test]
+fn test_invalid_input_is_rejected() {
+    assert!(SyntaxTree::parse("{\"a\": }", &ParseOptions::default()).is_err());
+    assert!(SyntaxTree::parse("[1, // no\n 2]", &ParseOptions::default()).is_err());
+}
+
+#[test]
+fn test_tokenizer_emits_trivia() {
+    let options = ParseOptions::new()
+        .with_allow_comments(true)
+        .with_emit_trivia(true);
+    let tokenizer =
+        Tokenizer::new(Some("[1,\r\n\t /**/2]".to_string()), None).with_options(options);
+    let types: Vec<TokenType> = tokenizer.map(|t| t.unwrap().token_type()).collect();
+    assert_eq!(
+        types,
+        vec![
+            TokenType::LeftSquareBracket,
+            TokenType::Number,
+            TokenType::Comma,
+            TokenType::CarriageReturn,
+            TokenType::NewLine,
+            TokenType::Tab,
+            TokenType::WhiteSpace,
+            TokenType::Comment,
+            TokenType::Number,
+            TokenType::RightSquareBracket,
+            TokenType::EOF,
+        ]
+    );
+
+    // the parser ignores trivia
+    assert_eq!(parse_with("[ 1 , 2 ]", &options).unwrap(), json!([1, 2]));
+}
@@ -0,0 +1,159 @@
+use parse_light::core::parse_options::ParseOptions;
+use parse_light::cst::editable_document::EditableDocument;
+use parse_light::json;
+
+const PACKAGE: &str = r#"{
+    "name": "deploy-tool",
+    "version": "1.4.2",
+    "scripts": {
+        "build": "cargo build"
+    },
+    "keywords": ["json", "cli"]
+}
+"#;
+
+fn jsonc() -> ParseOptions {
+    ParseOptions::new().with_allow_comments(true)
+}
+
+#[test]
+fn test_set_rewrites_only_the_value() {
+    let mut doc = EditableDocument::parse(PACKAGE, &ParseOptions::default()).unwrap();
+    doc.set("/version", &json!("1.4.3")).unwrap();
+    assert_eq!(doc.to_string(), PACKAGE.replace("1.4.2", "1.4.3"));
+
+    doc.set("/keywords/1", &json!(false)).unwrap();
+    assert!(doc.to_string().contains(r#"["json", false]"#));
+    assert_eq!(doc.to_value().unwrap()["keywords"], json!(["json", false]));
+}
+
+#[test]
+fn test_insert_follows_indentation() {
+    let mut doc = EditableDocument::parse(PACKAGE, &ParseOptions::default()).unwrap();
+    doc.insert("/scripts", "test", &json!("cargo test"))
+        .unwrap();
+    doc.set("/engines", &json!({"node": ">=18", "npm": 9}))
+        .unwrap();
+    assert_eq!(
+        doc.to_string(),
+        r#"{
+    "name": "deploy-tool",
+    "version": "1.4.2",
+    "scripts": {
+        "build": "cargo build",
+        "test": "cargo test"
+    },
+    "keywords": ["json", "cli"],
+    "engines": {
+        "node": ">=18",
+        "npm": 9
+    }
+}
+"#
+    );
+    assert!(doc.insert("", "name", &json!("x")).is_err());
+}
+
+#[test]
+fn test_array_inserts() {
+    let mut doc = EditableDocument::parse("[1, 2,\n 3]", &ParseOptions::default()).unwrap();
+    doc.insert("", "0", &json!(0)).unwrap();
+    doc.insert("", "-", &json!(4)).unwrap();
+    doc.set("/5", &json!(5)).unwrap();
+    assert_eq!(doc.to_string(), "[0, 1, 2,\n 3,\n 4,\n 5]");
+
+    let mut doc = EditableDocument::parse("{\"a\":[]}", &ParseOptions::default()).unwrap();
+    doc.insert("/a", "-", &json!({"b": [true, null]})).unwrap();
+    doc.insert("", "c", &json!(1)).unwrap();
+    assert_eq!(doc.to_string(), "{\"a\":[{\"b\":[true,null]}],\"c\":1}");
+}
+
+#[test]
+fn test_inserts_after_a_sole_element() {
+    let mut doc = EditableDocument::parse("{}", &ParseOptions::default()).unwrap();
+    doc.set("/a", &json!(1)).unwrap();
+    doc.set("/b", &json!([1, 2])).unwrap();
+    assert_eq!(doc.to_string(), r#"{"a": 1, "b": [1, 2]}"#);
+
+    let mut doc = EditableDocument::parse("[1]", &ParseOptions::default()).unwrap();
+    doc.insert("", "0", &json!(0)).unwrap();
+    assert_eq!(doc.to_string(), "[0, 1]");
+
+    let source = "{\n  \"x\": {\"a\": 1}\n}";
+    let mut doc = EditableDocument::parse(source, &ParseOptions::default()).unwrap();
+    doc.set("/x/b", &json!(2)).unwrap();
+    doc.set("/y", &json!(3)).unwrap();
+    assert_eq!(
+        doc.to_string(),
+        "{\n  \"x\": {\"a\": 1, \"b\": 2},\n  \"y\": 3\n}"
+    );
+}
+
+#[test]
+fn test_remove_keeps_comments_and_commas_valid() {
+    let source = "{\n  // the name\n  \"name\": \"x\", // trailing\n  \"a\": 1, // about a\n  /* b */\n  \"b\": 2 // about b\n}";
+    let mut doc = EditableDocument::parse(source, &jsonc()).unwrap();
+    doc.remove("/a").unwrap();
+    assert_eq!(
+        doc.to_string(),
+        "{\n  // the name\n  \"name\": \"x\", // trailing\n  /* b */\n  \"b\": 2 // about b\n}"
+    );
+    doc.remove("/b").unwrap();
+    assert_eq!(
+        doc.to_string(),
+        "{\n  // the name\n  \"name\": \"x\" // trailing\n}"
+    );
+    doc.remove("/name").unwrap();
+    assert_eq!(doc.to_string(), "{}");
+
+    let mut doc = EditableDocument::parse("[1, 2, 3]", &ParseOptions::default()).unwrap();
+    doc.remove("/1").unwrap();
+    assert_eq!(doc.to_string(), "[1, 3]");
+    doc.remove("/1").unwrap();
+    assert_eq!(doc.to_string(), "[1]");
+    assert!(doc.remove("/5").is_err());
+    assert!(doc.remove("").is_err());
+}
+
+#[test]
+fn test_edits_replace_the_sign_of_negative_numbers() {
+    let source = "{\"a\": -1, \"b\": - 2.5, \"c\": -3}";
+    let mut doc = EditableDocument::parse(source, &ParseOptions::default()).unwrap();
+    doc.set("/a", &json!(5)).unwrap();
+    doc.set("/b", &json!("x")).unwrap();
+    assert_eq!(doc.to_string(), "{\"a\": 5, \"b\": \"x\", \"c\": -3}");
+    doc.set("/c", &json!(-4)).unwrap();
+    doc.remove("/a").unwrap();
+    assert_eq!(doc.to_string(), "{\"b\": \"x\", \"c\": -4}");
+    assert_eq!(doc.to_value().unwrap(), json!({"b": "x", "c": -4}));
+}
+
+#[test]
+fn test_rename_key() {
+    let mut doc = EditableDocument::parse(PACKAGE, &ParseOptions::default()).unwrap();
+    doc.rename_key("/scripts/build", "compile").unwrap();
+    assert_eq!(
+        doc.to_string(),
+        PACKAGE.replace("\"build\":", "\"compile\":")
+    );
+    assert!(doc.rename_key("/name", "version").is_err());
+    assert!(doc.rename_key("/keywords/0", "x").is_err());
+
+    let json5 = "{unquoted: 1, 'quoted': 2,}";
+    let mut doc = EditableDocument::parse(json5, &ParseOptions::new().with_json5(true)).unwrap();
+    doc.rename_key("/unquoted", "plain").unwrap();
+    doc.rename_key("/quoted", "has space").unwrap();
+    doc.insert("", "c", &json!(3)).unwrap();
+    assert_eq!(doc.to_string(), "{plain: 1, 'has space': 2, \"c\": 3,}");
+}
+
+#[test]
+fn test_crlf_and_tabs_are_reused() {
+    let source = "{\r\n\t\"a\": {\r\n\t\t\"b\": 1\r\n\t}\r\n}\r\n";
+    let mut doc = EditableDocument::parse(source, &ParseOptions::default()).unwrap();
+    doc.insert("/a", "c", &json!([2])).unwrap();
+    assert_eq!(
+        doc.to_string(),
+        "{\r\n\t\"a\": {\r\n\t\t\"b\": 1,\r\n\t\t\"c\": [\r\n\t\t\t2\r\n\t\t]\r\n\t}\r\n}\r\n"
+    );
+}
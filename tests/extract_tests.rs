@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+
+use parse_light::core::json_value::JsonValue;
+use parse_light::json;
+use parse_light::lexer::buffered_file_reader::BufferedFileReader;
+use parse_light::lexer::buffered_string_reader::BufferedStringReader;
+use parse_light::stream::extract::extract;
+
+fn from_str(json: &str, pointers: &[&str]) -> Result<Vec<(String, JsonValue)>, String> {
+    extract(
+        BufferedStringReader::new(json.as_bytes().to_vec()),
+        pointers,
+    )
+}
+
+#[test]
+fn test_extracts_from_file() {
+    let reader = BufferedFileReader::new(PathBuf::from("tests/data/nested.json"));
+    let matches = extract(
+        reader,
+        &["/metadata/version", "/departments/*/employees/*/id"],
+    )
+    .unwrap();
+    assert_eq!(
+        matches,
+        vec![
+            ("/departments/0/employees/0/id".to_string(), json!(1)),
+            ("/departments/0/employees/1/id".to_string(), json!(2)),
+            ("/departments/1/employees/0/id".to_string(), json!(3)),
+            ("/metadata/version".to_string(), json!(2)),
+        ]
+    );
+}
+
+#[test]
+fn test_extracts_whole_subtrees() {
+    let reader =
+        BufferedFileReader::new(PathBuf::from("tests/data/sample1.json")).with_chunk_size(64);
+    let matches = extract(reader, &["/flights/9/flight_number", "/flights/0"]).unwrap();
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].0, "/flights/0");
+    assert_eq!(matches[0].1["flight_number"], json!("DL8509"));
+    assert_eq!(matches[0].1["duration_hours"], json!(20.95));
+    assert_eq!(
+        matches[1],
+        ("/flights/9/flight_number".to_string(), json!("SA9026"))
+    );
+}
+
+#[test]
+fn test_missing_paths_and_escaped_keys() {
+    let json = r#"{"a/b": {"~": [1.5, "x\ny"]}, "c": null}"#;
+    let matches = from_str(json, &["/a~1b/~0", "/missing", "/c/0"]).unwrap();
+    assert_eq!(
+        matches,
+        vec![("/a~1b/~0".to_string(), json!([1.5, "x\ny"]))]
+    );
+
+    assert_eq!(
+        from_str("[1, 2]", &[""]).unwrap(),
+        vec![(String::new(), json!([1, 2]))]
+    );
+    assert!(from_str("[1, 2]", &["no-slash"]).is_err());
+}
+
+#[test]
+fn test_skipped_values_are_still_checked() {
+    assert!(from_str(r#"{"skip": [1, }, "b": 2}"#, &["/b/*"]).is_err());
+    assert!(from_str(r#"{"skip": {"k" 1}, "b": 2}"#, &["/b/*"]).is_err());
+    assert!(from_str(r#"{"b": 2} {"#, &["/b/*"]).is_err());
+    assert!(from_str(r#"{"skip": ["\q"], "b": 2}"#, &["/b/*"]).is_err());
+    assert!(from_str(r#"{"skip": [1.2.3], "b": 2}"#, &["/b/*"]).is_err());
+}
+
+#[test]
+fn test_extracts_negative_numbers() {
+    let matches = from_str(r#"{"a": -1, "b": {"c": -2.5}}"#, &["/a", "/b"]).unwrap();
+    assert_eq!(
+        matches,
+        vec![
+            ("/a".to_string(), json!(-1)),
+            ("/b".to_string(), json!({"c": -2.5})),
+        ]
+    );
+}
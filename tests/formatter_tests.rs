@@ -0,0 +1,148 @@
+use std::fs;
+
+use parse_light::core::parse_options::ParseOptions;
+use parse_light::core::stringify_options::StringifyOptions;
+use parse_light::cst::formatter::Formatter;
+use parse_light::parse_with;
+
+fn data(name: &str) -> String {
+    fs::read_to_string(format!(
+        "{}/tests/data/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_normalizes_indentation() {
+    let formatted = Formatter::new()
+        .format(
+            "{\"name\":\"x\",   \"tags\":[ \"a\",\"b\" ],\"empty\":{ },\"nested\":{\"n\":1.50}}",
+        )
+        .unwrap();
+    assert_eq!(
+        formatted,
+        "{\n  \"name\": \"x\",\n  \"tags\": [\"a\", \"b\"],\n  \"empty\": {},\n  \"nested\": {\n    \"n\": 1.50\n  }\n}\n"
+    );
+
+    let tabs = Formatter::new()
+        .with_indent("\t")
+        .format("[{\"a\": [1]}]")
+        .unwrap();
+    assert_eq!(tabs, "[\n\t{\n\t\t\"a\": [1]\n\t}\n]\n");
+}
+
+#[test]
+fn test_wraps_arrays_at_print_width() {
+    let formatter = Formatter::new().with_print_width(20);
+    assert_eq!(
+        formatter.format("{\"short\": [1, 2, 3]}").unwrap(),
+        "{\n  \"short\": [1, 2, 3]\n}\n"
+    );
+    assert_eq!(
+        formatter
+            .format("{\"long\": [100, 200, 300, 400]}")
+            .unwrap(),
+        "{\n  \"long\": [\n    100,\n    200,\n    300,\n    400\n  ]\n}\n"
+    );
+    assert_eq!(
+        formatter.format("[[1, 2], [3, [4]]]").unwrap(),
+        "[[1, 2], [3, [4]]]\n"
+    );
+}
+
+#[test]
+fn test_preserves_comments_and_blank_lines() {
+    let source = "{\"a\": 1, // one\n\n\n  // group two\n  \"b\": [ /* none */ ],\n  \"c\" /* key */ : 3\n  // the end\n}";
+    let formatter =
+        Formatter::new().with_parse_options(ParseOptions::new().with_allow_comments(true));
+    assert_eq!(
+        formatter.format(source).unwrap(),
+        "{\n  \"a\": 1, // one\n\n  // group two\n  \"b\": [\n    /* none */\n  ],\n  \"c\": /* key */ 3\n  // the end\n}\n"
+    );
+}
+
+#[test]
+fn test_formatting_is_idempotent() {
+    let jsonc = ParseOptions::new().with_allow_comments(true);
+    let json5 = ParseOptions::new().with_json5(true);
+    let inputs = [
+        (data("tsconfig.jsonc"), jsonc),
+        (data("config.json5"), json5),
+        (data("nested.json"), ParseOptions::default()),
+        (data("sample1.json"), ParseOptions::default()),
+        (data("special_strings.json"), ParseOptions::default()),
+        (
+            "{\"a\": // c\n {\"b\":1}, \"d\": /* e */ [1, {}]}".to_string(),
+            jsonc,
+        ),
+    ];
+    for (source, options) in inputs {
+        for width in [10, 40, 80] {
+            let formatter = Formatter::new()
+                .with_parse_options(options)
+                .with_print_width(width);
+            let once = formatter.format(&source).unwrap();
+            assert_eq!(formatter.format(&once).unwrap(), once);
+            assert!(formatter.check(&once).unwrap());
+            // compared as text since NaN is not equal to itself
+            let text = StringifyOptions::new().with_json5(true);
+            assert_eq!(
+                parse_with(&once, &options).unwrap().stringify_with(&text),
+                parse_with(&source, &options).unwrap().stringify_with(&text)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_comment_between_key_and_container() {
+    let jsonc = ParseOptions::new().with_allow_comments(true);
+    let formatted = Formatter::new()
+        .with_parse_options(jsonc)
+        .format("{\"a\": // c\n {\"b\":1}}")
+        .unwrap();
+    assert_eq!(
+        formatted,
+        "{\n  \"a\": // c\n    {\n      \"b\": 1\n    }\n}\n"
+    );
+}
+
+#[test]
+fn test_keeps_the_sign_of_negative_numbers() {
+    let formatter = Formatter::new();
+    let formatted = formatter
+        .format("{\"a\": -1, \"b\": - 1.5, \"c\": {\"d\": -2e3}}")
+        .unwrap();
+    assert_eq!(
+        formatted,
+        "{\n  \"a\": -1,\n  \"b\": -1.5,\n  \"c\": {\n    \"d\": -2e3\n  }\n}\n"
+    );
+    assert!(formatter.check(&formatted).unwrap());
+    assert!(!formatter.check("{\n  \"a\": - 1\n}\n").unwrap());
+
+    // negative array elements are only accepted in JSON5
+    let json5 = Formatter::new().with_parse_options(ParseOptions::new().with_json5(true));
+    assert_eq!(json5.format("[-1, 2, -.5]").unwrap(), "[-1, 2, -.5]\n");
+    assert_eq!(
+        json5.format("{a: [-1, {b: -2}]}").unwrap(),
+        "{\n  a: [\n    -1,\n    {\n      b: -2\n    }\n  ]\n}\n"
+    );
+}
+
+#[test]
+fn test_check_mode() {
+    let formatter = Formatter::new();
+    assert!(formatter.check("{\n  \"a\": [1, 2]\n}\n").unwrap());
+    assert!(!formatter.check("{\"a\": [1, 2]}").unwrap());
+    assert!(!formatter.check("{\n  \"a\": [1, 2]\n}").unwrap());
+    assert!(formatter.check("{\"a\": }").is_err());
+
+    let path = format!("{}/tests/data/nested.json", env!("CARGO_MANIFEST_DIR"));
+    let formatted = formatter.check_file(&path).unwrap();
+    assert_eq!(
+        formatted,
+        formatter.format(&data("nested.json")).unwrap() == data("nested.json")
+    );
+}
@@ -0,0 +1,132 @@
+use std::path::PathBuf;
+
+use parse_light::core::json_value::JsonValue;
+use parse_light::core::parse_options::ParseOptions;
+use parse_light::core::stringify_options::StringifyOptions;
+use parse_light::{json, parse, parse_with, stringify_with};
+
+fn json5() -> ParseOptions {
+    ParseOptions::new().with_json5(true)
+}
+
+fn parse5(text: &str) -> Result<JsonValue, String> {
+    parse_with(text, &json5())
+}
+
+#[test]
+fn test_parse_json5_file() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join("config.json5");
+    let value = parse5(&path.to_string_lossy()).unwrap();
+
+    assert_eq!(value["unquoted"], json!("and you can quote me on that"));
+    assert_eq!(
+        value["singleQuotes"],
+        json!("I can use \"double quotes\" here")
+    );
+    assert_eq!(value["lineBreaks"], json!("Look, Mom! No \\n's!"));
+    assert_eq!(value["hexadecimal"], json!(912559));
+    assert_eq!(value["leadingDecimalPoint"], json!(0.8675309));
+    assert_eq!(value["andTrailing"], json!(8675309));
+    assert_eq!(value["positiveSign"], json!(1));
+    assert_eq!(value["andIn"], json!(["arrays"]));
+    assert_eq!(value["backwardsCompatible"], json!("with JSON"));
+
+    let nested = value["$special_key"]["nested"].as_array().unwrap();
+    assert_eq!(nested.len(), 4);
+    assert_eq!(nested[0], json!(f64::NEG_INFINITY));
+    assert!(nested[1].as_f64().unwrap().is_nan());
+    assert_eq!(nested[2], json!(-16));
+    assert_eq!(nested[3], JsonValue::Null);
+
+    assert!(parse(&path.to_string_lossy()).is_err());
+}
+
+#[test]
+fn test_json5_strings_and_escapes() {
+    assert_eq!(
+        parse5(
+            r#"['it\'s', "tab\there", '\x41\v\0', '\q', 'a\
+b', "crlf\
+end"]"#
+        )
+        .unwrap(),
+        json!(["it's", "tab\there", "A\u{b}\0", "q", "ab", "crlfend"])
+    );
+    assert_eq!(parse5("['\\x4']").unwrap_err(), "Invalid hex escape: \\x4");
+    assert!(parse5(r"['\1']").is_err());
+}
+
+#[test]
+fn test_json5_identifiers() {
+    assert_eq!(
+        parse5("{null: 1, true: 2, Infinity: 3, _a$1: 4, café: 5}").unwrap(),
+        json!({"null": 1, "true": 2, "Infinity": 3, "_a$1": 4, "café": 5})
+    );
+    assert_eq!(
+        parse5("{a: undefined}").unwrap_err(),
+        "Unexpected identifier 'undefined' at position 4"
+    );
+    assert!(parse5("[+Infinity, -NaN, 1e3, -.5]").is_ok());
+    assert!(parse5("[0x]").is_err());
+    assert!(parse5("[infinity]").is_err());
+}
+
+#[test]
+fn test_json5_top_level_scalars() {
+    assert_eq!(parse5("'abc'").unwrap(), json!("abc"));
+    assert_eq!(parse5("+1").unwrap(), json!(1));
+    assert_eq!(parse5("-1").unwrap(), json!(-1));
+    assert_eq!(parse5(" .5 ").unwrap(), json!(0.5));
+    assert_eq!(parse5("0x1F").unwrap(), json!(31));
+    assert_eq!(parse5("1").unwrap(), json!(1));
+    assert_eq!(parse5("0").unwrap(), json!(0));
+    assert_eq!(parse5("5 ").unwrap(), json!(5));
+    assert_eq!(parse5("Infinity").unwrap(), json!(f64::INFINITY));
+    assert!(parse5("NaN").unwrap().as_f64().unwrap().is_nan());
+    assert_eq!(parse5("null").unwrap(), json!(null));
+    assert_eq!(parse5("true // yes").unwrap(), json!(true));
+    assert!(parse5("undefined").is_err());
+}
+
+#[test]
+fn test_strict_mode_is_unchanged() {
+    for text in [
+        "{a: 1}",
+        "{'a': 1}",
+        "[1,]",
+        "{\"a\": 1,}",
+        "[0x10]",
+        "[.5]",
+        "[\"\\x41\"]",
+        "[1 /* c */]",
+        "'abc'",
+        "+1",
+        "NaN",
+    ] {
+        assert!(parse(text).is_err(), "{} should be rejected", text);
+    }
+}
+
+#[test]
+fn test_stringify_json5() {
+    let options = StringifyOptions::new().with_json5(true);
+    let value = json!({
+        "name": "it's",
+        "plain": "x\"y",
+        "both": "'\"'",
+        "not-an-identifier": [1.5, f64::INFINITY, f64::NEG_INFINITY],
+        "_ok$": null
+    });
+    let text = stringify_with(&value, &options);
+    assert_eq!(
+        text,
+        r#"{_ok$:null,both:"'\"'",name:"it's",'not-an-identifier':[1.5,Infinity,-Infinity],plain:'x"y'}"#
+    );
+    assert_eq!(parse5(&text).unwrap(), value);
+
+    let nan = stringify_with(&json!([f64::NAN]), &options);
+    assert_eq!(nan, "[NaN]");
+}
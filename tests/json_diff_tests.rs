@@ -0,0 +1,96 @@
+use parse_light::core::json_value::JsonValue;
+use parse_light::diff::json_diff::{
+    ArrayDiffMode, DiffKind, DiffOptions, json_diff, json_diff_with, render_diff,
+};
+use parse_light::parse;
+
+fn summary(entries: &[parse_light::diff::json_diff::DiffEntry]) -> Vec<(DiffKind, &str)> {
+    entries.iter().map(|e| (e.kind(), e.path())).collect()
+}
+
+#[test]
+fn test_diff_ignores_formatting_and_key_order() {
+    let a = parse(r#"{"a": 1, "b": [1, 2]}"#).unwrap();
+    let b = parse("{\n  \"b\" : [1,2],\n  \"a\" : 1.0\n}").unwrap();
+    assert!(json_diff(&a, &b).is_empty());
+}
+
+#[test]
+fn test_diff_objects() {
+    let a =
+        parse(r#"{"db": {"host": "localhost", "port": 5432}, "debug": true, "a/b": 1}"#).unwrap();
+    let b =
+        parse(r#"{"db": {"host": "db.internal", "port": 5432}, "replicas": 2, "a/b": 1}"#).unwrap();
+    let entries = json_diff(&a, &b);
+    assert_eq!(
+        summary(&entries),
+        vec![
+            (DiffKind::Changed, "/db/host"),
+            (DiffKind::Removed, "/debug"),
+            (DiffKind::Added, "/replicas"),
+        ]
+    );
+    assert_eq!(
+        entries[0].old_value(),
+        Some(&JsonValue::String("localhost".to_string()))
+    );
+    assert_eq!(
+        entries[0].new_value(),
+        Some(&JsonValue::String("db.internal".to_string()))
+    );
+    assert_eq!(entries[1].new_value(), None);
+}
+
+#[test]
+fn test_diff_arrays_by_index_and_lcs() {
+    let a = parse(r#"[1, 2, 3]"#).unwrap();
+    let b = parse(r#"[0, 1, 2, 3]"#).unwrap();
+
+    assert_eq!(json_diff(&a, &b).len(), 4);
+
+    let lcs = DiffOptions::new().with_array_mode(ArrayDiffMode::Lcs);
+    assert_eq!(
+        summary(&json_diff_with(&a, &b, &lcs)),
+        vec![(DiffKind::Added, "/0")]
+    );
+    assert_eq!(
+        summary(&json_diff_with(&b, &a, &lcs)),
+        vec![(DiffKind::Removed, "/0")]
+    );
+
+    let c = parse(r#"[1, {"x": 1}, 3]"#).unwrap();
+    let d = parse(r#"[1, {"x": 2}, 3, 4]"#).unwrap();
+    assert_eq!(
+        summary(&json_diff_with(&c, &d, &lcs)),
+        vec![(DiffKind::Changed, "/1/x"), (DiffKind::Added, "/3")]
+    );
+}
+
+#[test]
+fn test_diff_arrays_by_key() {
+    let a = parse(r#"[{"id": 1, "v": "a"}, {"id": 2, "v": "b"}, {"id": 3, "v": "c"}]"#).unwrap();
+    let b = parse(r#"[{"id": 3, "v": "c"}, {"id": 1, "v": "z"}, {"id": 4, "v": "d"}]"#).unwrap();
+    let options = DiffOptions::new().with_array_mode(ArrayDiffMode::ByKey("id".to_string()));
+    assert_eq!(
+        summary(&json_diff_with(&a, &b, &options)),
+        vec![
+            (DiffKind::Changed, "/1/v"),
+            (DiffKind::Removed, "/1"),
+            (DiffKind::Added, "/2"),
+        ]
+    );
+}
+
+#[test]
+fn test_render_diff() {
+    let a = parse(r#"{"name": "old", "gone": [1]}"#).unwrap();
+    let b = parse(r#"{"name": "new"}"#).unwrap();
+    assert_eq!(
+        render_diff(&json_diff(&a, &b)),
+        "--- a\n+++ b\n@@ /gone @@\n-[1]\n@@ /name @@\n-\"old\"\n+\"new\"\n"
+    );
+    assert_eq!(
+        render_diff(&json_diff(&a, &JsonValue::Null)),
+        "--- a\n+++ b\n@@ / @@\n-{\"gone\":[1],\"name\":\"old\"}\n+null\n"
+    );
+}
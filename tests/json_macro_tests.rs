@@ -0,0 +1,53 @@
+use parse_light::core::json_value::JsonValue;
+use parse_light::{json, parse, stringify};
+
+#[test]
+fn test_json_macro_literals() {
+    assert_eq!(json!(null), JsonValue::Null);
+    assert_eq!(json!(true), JsonValue::Boolean(true));
+    assert_eq!(json!(-1.5), JsonValue::Number(-1.5));
+    assert_eq!(json!("text"), JsonValue::String("text".to_string()));
+    assert_eq!(json!([]), JsonValue::Array(vec![]));
+    assert_eq!(stringify(&json!({})), "{}");
+}
+
+#[test]
+fn test_json_macro_nested() {
+    let value = json!({
+        "name": "parse_light",
+        "version": [0, 1, 1],
+        "meta": {"active": true, "owner": null, "tags": []},
+        "items": [{"id": 1}, [null, false], "x",],
+    });
+    let expected = parse(
+        r#"{
+            "name": "parse_light",
+            "version": [0, 1, 1],
+            "meta": {"active": true, "owner": null, "tags": []},
+            "items": [{"id": 1}, [null, false], "x"]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn test_json_macro_interpolation() {
+    let name = String::from("Alice");
+    let tags_var = vec!["b", "c"];
+    let count: u32 = 2;
+    let key = "dynamic";
+    let missing: Option<i32> = None;
+
+    let value = json!({
+        "name": name,
+        "tags": ["a", tags_var],
+        "n": count + 1,
+        (key): missing,
+        "neg": -(count as i64),
+    });
+    assert_eq!(
+        stringify(&value),
+        r#"{"dynamic":null,"n":3,"name":"Alice","neg":-2,"tags":["a",["b","c"]]}"#
+    );
+}
@@ -0,0 +1,169 @@
+use parse_light::core::json_value::JsonValue;
+use parse_light::patch::json_patch::{Patch, PatchOperation, diff};
+use parse_light::{parse, stringify};
+
+fn patch(json: &str) -> Patch {
+    Patch::from_value(&parse(json).unwrap()).expect("Invalid patch")
+}
+
+#[test]
+fn test_apply_all_operations() {
+    let mut doc = parse(r#"{"foo": ["bar", "baz"], "obj": {"a": 1}}"#).unwrap();
+    patch(
+        r#"[
+            {"op": "add", "path": "/foo/1", "value": "qux"},
+            {"op": "remove", "path": "/foo/0"},
+            {"op": "replace", "path": "/obj/a", "value": 2},
+            {"op": "copy", "from": "/obj", "path": "/copied"},
+            {"op": "move", "from": "/obj/a", "path": "/moved"},
+            {"op": "test", "path": "/copied/a", "value": 2}
+        ]"#,
+    )
+    .apply(&mut doc)
+    .unwrap();
+    assert_eq!(
+        stringify(&doc),
+        r#"{"copied":{"a":2},"foo":["qux","baz"],"moved":2,"obj":{}}"#
+    );
+}
+
+#[test]
+fn test_apply_is_atomic() {
+    let original = parse(r#"{"a": 1}"#).unwrap();
+    let mut doc = original.clone();
+    let res = patch(
+        r#"[
+            {"op": "add", "path": "/b", "value": 2},
+            {"op": "test", "path": "/a", "value": "not one"}
+        ]"#,
+    )
+    .apply(&mut doc);
+    assert!(res.unwrap_err().contains("Patch operation 1"));
+    assert_eq!(doc, original);
+}
+
+#[test]
+fn test_invalid_operations() {
+    let mut doc = parse(r#"{"a": {"b": 1}}"#).unwrap();
+    assert!(
+        patch(r#"[{"op": "replace", "path": "/missing", "value": 1}]"#)
+            .apply(&mut doc)
+            .is_err()
+    );
+    assert!(
+        patch(r#"[{"op": "move", "from": "/a", "path": "/a/b/c"}]"#)
+            .apply(&mut doc)
+            .is_err()
+    );
+
+    assert!(Patch::from_value(&parse(r#"[{"op": "jump", "path": "/a"}]"#).unwrap()).is_err());
+    assert!(Patch::from_value(&parse(r#"[{"op": "add", "path": "/a"}]"#).unwrap()).is_err());
+    assert!(Patch::from_value(&parse(r#"{"op": "remove"}"#).unwrap()).is_err());
+}
+
+#[test]
+fn test_to_value_roundtrip() {
+    let p = Patch::new(vec![
+        PatchOperation::Add {
+            path: "/a".to_string(),
+            value: JsonValue::Null,
+        },
+        PatchOperation::Move {
+            from: "/a".to_string(),
+            path: "/b".to_string(),
+        },
+    ]);
+    assert_eq!(Patch::from_value(&p.to_value()).unwrap(), p);
+}
+
+#[test]
+fn test_diff_generates_minimal_patch() {
+    let a =
+        parse(r#"{"keep": 1, "old": true, "list": [1, 2, 3, 4], "nested": {"x": "y"}}"#).unwrap();
+    let b =
+        parse(r#"{"keep": 1, "new": null, "list": [1, 3, 4, 5], "nested": {"x": "z"}}"#).unwrap();
+    let p = diff(&a, &b);
+
+    assert_eq!(
+        p.operations(),
+        &[
+            PatchOperation::Remove {
+                path: "/old".to_string()
+            },
+            PatchOperation::Remove {
+                path: "/list/1".to_string()
+            },
+            PatchOperation::Add {
+                path: "/list/3".to_string(),
+                value: JsonValue::Number(5.0)
+            },
+            PatchOperation::Replace {
+                path: "/nested/x".to_string(),
+                value: JsonValue::String("z".to_string())
+            },
+            PatchOperation::Add {
+                path: "/new".to_string(),
+                value: JsonValue::Null
+            },
+        ]
+    );
+
+    let mut patched = a.clone();
+    p.apply(&mut patched).unwrap();
+    assert_eq!(patched, b);
+}
+
+#[test]
+fn test_diff_array_insert_and_remove() {
+    let a = parse(r#"[1, 2, 3]"#).unwrap();
+    let b = parse(r#"[0, 1, 2, 3]"#).unwrap();
+    assert_eq!(
+        diff(&a, &b).operations(),
+        &[PatchOperation::Add {
+            path: "/0".to_string(),
+            value: JsonValue::Number(0.0)
+        }]
+    );
+    assert_eq!(
+        diff(&b, &a).operations(),
+        &[PatchOperation::Remove {
+            path: "/0".to_string()
+        }]
+    );
+
+    let c = parse(r#"{"a/b": [1, 2, 3, 4, 5]}"#).unwrap();
+    let d = parse(r#"{"a/b": [1, 5]}"#).unwrap();
+    let p = diff(&c, &d);
+    assert_eq!(p.operations().len(), 3);
+    let mut patched = c.clone();
+    p.apply(&mut patched).unwrap();
+    assert_eq!(patched, d);
+
+    assert!(diff(&a, &a).is_empty());
+}
+
+#[test]
+fn test_diff_array_uses_common_subsequence() {
+    let a = parse(r#"[1, 2, 3]"#).unwrap();
+    let b = parse(r#"[2, 3, 4]"#).unwrap();
+    assert_eq!(
+        diff(&a, &b).operations(),
+        &[
+            PatchOperation::Remove {
+                path: "/0".to_string()
+            },
+            PatchOperation::Add {
+                path: "/2".to_string(),
+                value: JsonValue::Number(4.0)
+            },
+        ]
+    );
+
+    let c = parse(r#"[{"id": 1}, 7, "x", 8, {"id": 2}, 9]"#).unwrap();
+    let d = parse(r#"[7, {"id": 3}, 8, 10, 11, 9, "y"]"#).unwrap();
+    for (from, to) in [(&a, &b), (&b, &a), (&c, &d), (&d, &c)] {
+        let mut patched = from.clone();
+        diff(from, to).apply(&mut patched).unwrap();
+        assert_eq!(&patched, to);
+    }
+}
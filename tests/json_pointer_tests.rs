@@ -0,0 +1,89 @@
+use parse_light::core::json_pointer::{build_pointer, parse_pointer};
+use parse_light::core::json_value::JsonValue;
+use parse_light::parse;
+use std::path::PathBuf;
+
+fn sample1() -> JsonValue {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join("sample1.json");
+    parse(&path.to_string_lossy()).expect("Failed to parse sample1.json")
+}
+
+#[test]
+fn test_pointer_lookup_in_file() {
+    let value = sample1();
+    assert_eq!(
+        value.pointer("/flights/0/flight_number"),
+        Some(&JsonValue::String("DL8509".to_string()))
+    );
+    assert_eq!(
+        value.pointer("/flights/0/duration_hours"),
+        Some(&JsonValue::Number(20.95))
+    );
+    assert_eq!(value.pointer(""), Some(&value));
+    assert_eq!(value.pointer("/flights/10"), None);
+    assert_eq!(value.pointer("/flights/01"), None);
+    assert_eq!(value.pointer("/flights/-"), None);
+    assert_eq!(value.pointer("flights"), None);
+}
+
+#[test]
+fn test_pointer_escapes() {
+    let value = parse(r#"{"a/b": 1, "m~n": 2, "": 3}"#).unwrap();
+    assert_eq!(value.pointer("/a~1b"), Some(&JsonValue::Number(1.0)));
+    assert_eq!(value.pointer("/m~0n"), Some(&JsonValue::Number(2.0)));
+    assert_eq!(value.pointer("/"), Some(&JsonValue::Number(3.0)));
+
+    assert!(parse_pointer("/bad~2").is_err());
+    assert_eq!(build_pointer(&["a/b", "m~n"]), "/a~1b/m~0n");
+}
+
+#[test]
+fn test_pointer_mut() {
+    let mut value = parse(r#"{"a": [1, 2]}"#).unwrap();
+    *value.pointer_mut("/a/1").unwrap() = JsonValue::Boolean(true);
+    assert_eq!(parse_light::stringify(&value), r#"{"a":[1,true]}"#);
+}
+
+#[test]
+fn test_insert_at_pointer() {
+    let mut value = parse(r#"{"a": [1, 2]}"#).unwrap();
+    value
+        .insert_at_pointer("/a/0", JsonValue::Number(0.0))
+        .unwrap();
+    value
+        .insert_at_pointer("/a/-", JsonValue::Number(3.0))
+        .unwrap();
+    value.insert_at_pointer("/b", JsonValue::Null).unwrap();
+    let replaced = value
+        .insert_at_pointer("/b", JsonValue::Boolean(false))
+        .unwrap();
+    assert_eq!(replaced, Some(JsonValue::Null));
+    assert_eq!(
+        parse_light::stringify(&value),
+        r#"{"a":[0,1,2,3],"b":false}"#
+    );
+
+    assert!(value.insert_at_pointer("/a/9", JsonValue::Null).is_err());
+    assert!(
+        value
+            .insert_at_pointer("/missing/x", JsonValue::Null)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_remove_at_pointer() {
+    let mut value = parse(r#"{"a": [1, 2], "b": {"c": null}}"#).unwrap();
+    assert_eq!(
+        value.remove_at_pointer("/a/0").unwrap(),
+        JsonValue::Number(1.0)
+    );
+    assert_eq!(value.remove_at_pointer("/b/c").unwrap(), JsonValue::Null);
+    assert_eq!(parse_light::stringify(&value), r#"{"a":[2],"b":{}}"#);
+
+    assert!(value.remove_at_pointer("/b/c").is_err());
+    assert!(value.remove_at_pointer("").is_err());
+}
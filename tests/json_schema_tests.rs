@@ -0,0 +1,303 @@
+use parse_light::core::json_value::JsonValue;
+use parse_light::json;
+use parse_light::schema::format::check_format;
+use parse_light::schema::json_schema::JsonSchema;
+use parse_light::schema::pattern::Pattern;
+
+fn schema(value: JsonValue) -> JsonSchema {
+    JsonSchema::compile(&value).expect("Invalid schema")
+}
+
+fn messages(schema: &JsonSchema, instance: &JsonValue) -> Vec<String> {
+    match schema.validate(instance) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.iter().map(|e| e.to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_type_enum_const() {
+    let s = schema(json!({"type": ["integer", "null"]}));
+    assert!(s.is_valid(&json!(3)));
+    assert!(s.is_valid(&json!(3.0)));
+    assert!(s.is_valid(&JsonValue::Null));
+    assert_eq!(
+        messages(&s, &json!(1.5)),
+        vec!["Expected type integer or null, found number at / (schema: #/type)"]
+    );
+
+    let s = schema(json!({"enum": ["a", 1, [true]]}));
+    assert!(s.is_valid(&json!([true])));
+    assert!(!s.is_valid(&json!("b")));
+
+    let s = schema(json!({"const": {"x": 1}}));
+    assert!(s.is_valid(&json!({"x": 1.0})));
+    assert!(!s.is_valid(&json!({"x": 2})));
+
+    assert!(schema(json!(true)).is_valid(&json!("anything")));
+    assert!(!schema(json!(false)).is_valid(&json!("anything")));
+}
+
+#[test]
+fn test_object_keywords_report_every_violation() {
+    let s = schema(json!({
+        "type": "object",
+        "properties": {
+            "name": {"type": "string", "minLength": 2},
+            "age": {"type": "integer", "minimum": 0, "maximum": 150},
+            "email": {"format": "email"}
+        },
+        "patternProperties": {"^x-": {"type": "boolean"}},
+        "required": ["name", "email"],
+        "additionalProperties": false
+    }));
+    assert!(s.is_valid(&json!({"name": "Al", "email": "a@b.c", "x-debug": true})));
+
+    let errors = s
+        .validate(&json!({"name": "A", "age": 200, "x-debug": 1, "extra": null}))
+        .unwrap_err();
+    let found: Vec<(&str, &str)> = errors
+        .iter()
+        .map(|e| (e.instance_path(), e.schema_path()))
+        .collect();
+    assert_eq!(errors.len(), 5, "{:?}", errors);
+    assert!(found.contains(&("", "/required")));
+    assert!(found.contains(&("/name", "/properties/name/minLength")));
+    assert!(found.contains(&("/age", "/properties/age/maximum")));
+    assert!(found.contains(&("/x-debug", "/patternProperties/^x-/type")));
+    assert!(found.contains(&("/extra", "/additionalProperties")));
+    assert!(
+        errors
+            .iter()
+            .any(|e| e.message() == "Missing required property 'email'")
+    );
+    assert!(
+        errors
+            .iter()
+            .any(|e| e.message() == "Additional property 'extra' is not allowed")
+    );
+}
+
+#[test]
+fn test_array_keywords() {
+    let s = schema(json!({
+        "type": "array",
+        "prefixItems": [{"type": "string"}, {"type": "number"}],
+        "items": {"type": "boolean"},
+        "minItems": 2,
+        "uniqueItems": true
+    }));
+    assert!(s.is_valid(&json!(["a", 1, true, false])));
+    assert_eq!(
+        messages(&s, &json!([1])),
+        vec![
+            "Array has fewer than 2 items at / (schema: #/minItems)",
+            "Expected type string, found number at /0 (schema: #/prefixItems/0/type)",
+        ]
+    );
+    assert_eq!(
+        messages(&s, &json!(["a", 1, true, true])),
+        vec!["Items 2 and 3 are equal at / (schema: #/uniqueItems)"]
+    );
+    assert_eq!(
+        messages(&s, &json!(["a", 1, "c"])),
+        vec!["Expected type boolean, found string at /2 (schema: #/items/type)"]
+    );
+}
+
+#[test]
+fn test_numeric_and_string_keywords() {
+    let s = schema(json!({"exclusiveMinimum": 0, "multipleOf": 0.1}));
+    assert!(s.is_valid(&json!(0.3)));
+    assert!(!s.is_valid(&json!(0)));
+    assert!(!s.is_valid(&json!(0.35)));
+    assert!(s.is_valid(&json!("not a number")));
+
+    let s = schema(json!({"minLength": 3}));
+    assert!(
+        s.is_valid(&json!("éàü")),
+        "lengths count characters, not bytes"
+    );
+    assert!(!s.is_valid(&json!("éà")));
+
+    let s = schema(json!({"maxLength": 3, "pattern": "^[a-z]+\\d?$"}));
+    assert!(s.is_valid(&json!("ab1")));
+    assert_eq!(
+        messages(&s, &json!("Ab")),
+        vec!["String does not match pattern '^[a-z]+\\d?$' at / (schema: #/pattern)"]
+    );
+    assert!(!s.is_valid(&json!("abcd")));
+}
+
+#[test]
+fn test_pattern_engine() {
+    let p = Pattern::new(r"^(?:\+\d{1,3} )?\(\d{3}\) \d{3}-\d{4}$").unwrap();
+    assert!(p.is_match("(555) 123-4567"));
+    assert!(p.is_match("+44 (555) 123-4567"));
+    assert!(!p.is_match("555-123-4567"));
+
+    let p = Pattern::new(r"\bcat|dog\b").unwrap();
+    assert!(p.is_match("the cat sat"));
+    assert!(p.is_match("hotdog"));
+    assert!(!p.is_match("concatenate"));
+
+    assert!(Pattern::new("a{2,}?b").unwrap().is_match("xaaab"));
+    assert!(
+        Pattern::new("[^\\s]+@[\\w.-]+")
+            .unwrap()
+            .is_match("me@x.io")
+    );
+    assert!(Pattern::new("x{,2}").unwrap().is_match("x{,2}"));
+    assert!(Pattern::new("(a").is_err());
+    assert!(Pattern::new("[b-a]").is_err());
+    assert!(Pattern::new("*a").is_err());
+}
+
+#[test]
+fn test_pattern_long_input() {
+    let text = "a".repeat(100_000);
+    assert!(Pattern::new("^a*$").unwrap().is_match(&text));
+    assert!(Pattern::new("^(?:a|aa)+?$").unwrap().is_match(&text));
+    assert!(!Pattern::new("^a*b$").unwrap().is_match(&text));
+    assert!(!Pattern::new("^(a*)*b").unwrap().is_match(&text));
+    assert!(Pattern::new("a{3}$").unwrap().is_match(&text));
+    assert!(
+        Pattern::new(r"^\w+\b")
+            .unwrap()
+            .is_match(&format!("{} end", text))
+    );
+}
+
+#[test]
+fn test_pattern_size_limit() {
+    assert!(Pattern::new("a{1,1000}").unwrap().is_match("a"));
+    for source in ["(a{1,1000}){1,1000}", "(){1000000000}", "a{1,999999999999}"] {
+        let err = Pattern::new(source).unwrap_err();
+        assert!(err.contains("more than 100000 instructions"), "{}", err);
+    }
+}
+
+#[test]
+fn test_formats() {
+    assert_eq!(check_format("date", "2024-02-29"), Some(true));
+    assert_eq!(check_format("date", "2023-02-29"), Some(false));
+    assert_eq!(
+        check_format("date-time", "2024-01-01T12:30:00.5+02:00"),
+        Some(true)
+    );
+    assert_eq!(
+        check_format("date-time", "2024-01-01 12:30:00Z"),
+        Some(false)
+    );
+    assert_eq!(check_format("time", "23:59:60Z"), Some(true));
+    assert_eq!(check_format("email", "first.last@example.com"), Some(true));
+    assert_eq!(
+        check_format("email", "first..last@example.com"),
+        Some(false)
+    );
+    assert_eq!(
+        check_format("uri", "https://example.com/a%20b?q=1"),
+        Some(true)
+    );
+    assert_eq!(check_format("uri", "/relative/path"), Some(false));
+    assert_eq!(
+        check_format("uuid", "123e4567-e89b-12d3-a456-426614174000"),
+        Some(true)
+    );
+    assert_eq!(check_format("hostname", "whatever"), None);
+
+    let s = schema(json!({"format": "email"}));
+    assert_eq!(
+        messages(&s, &json!("nope")),
+        vec!["String is not a valid email at / (schema: #/format)"]
+    );
+    assert!(s.is_valid(&json!(42)), "format only applies to strings");
+}
+
+#[test]
+fn test_refs_and_defs() {
+    let s = schema(json!({
+        "$defs": {
+            "node": {
+                "type": "object",
+                "properties": {
+                    "value": {"type": "number"},
+                    "children": {"type": "array", "items": {"$ref": "#/$defs/node"}}
+                },
+                "required": ["value"]
+            },
+            "named": {"$anchor": "name", "type": "string"}
+        },
+        "properties": {
+            "tree": {"$ref": "#/$defs/node"},
+            "label": {"$ref": "#name"}
+        }
+    }));
+    assert!(s.is_valid(&json!({
+        "tree": {"value": 1, "children": [{"value": 2, "children": []}]},
+        "label": "root"
+    })));
+
+    let errors = s
+        .validate(&json!({"tree": {"value": 1, "children": [{"value": "x"}]}, "label": 5}))
+        .unwrap_err();
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert_eq!(errors[0].instance_path(), "/label");
+    assert_eq!(errors[0].schema_path(), "/$defs/named/type");
+    assert_eq!(errors[1].instance_path(), "/tree/children/0/value");
+    assert_eq!(errors[1].schema_path(), "/$defs/node/properties/value/type");
+
+    let looping = schema(json!({"$defs": {"a": {"$ref": "#/$defs/a"}}, "$ref": "#/$defs/a"}));
+    assert!(looping.is_valid(&json!(1)));
+}
+
+#[test]
+fn test_combinators() {
+    let s = schema(json!({
+        "allOf": [{"type": "number"}, {"minimum": 10}],
+        "not": {"const": 13}
+    }));
+    assert!(s.is_valid(&json!(12)));
+    assert_eq!(
+        messages(&s, &json!(13)),
+        vec!["Value must not match the schema in not at / (schema: #/not)"]
+    );
+    assert_eq!(
+        messages(&s, &json!(5)),
+        vec!["5 is less than the minimum of 10 at / (schema: #/allOf/1/minimum)"]
+    );
+
+    let s = schema(json!({"anyOf": [{"type": "string"}, {"type": "boolean"}]}));
+    assert!(s.is_valid(&json!(true)));
+    assert_eq!(
+        messages(&s, &json!(1)),
+        vec!["Value does not match any schema in anyOf at / (schema: #/anyOf)"]
+    );
+
+    let s = schema(json!({"oneOf": [{"multipleOf": 3}, {"multipleOf": 5}]}));
+    assert!(s.is_valid(&json!(9)));
+    assert_eq!(
+        messages(&s, &json!(15)),
+        vec!["Value matches 2 schemas in oneOf, expected exactly one at / (schema: #/oneOf)"]
+    );
+}
+
+#[test]
+fn test_invalid_schemas_rejected_at_compile_time() {
+    let err = |value: JsonValue| JsonSchema::compile(&value).err().unwrap();
+    assert_eq!(
+        err(json!({"type": "text"})),
+        "Invalid schema at #/type: unknown type 'text'"
+    );
+    assert!(
+        err(json!({"properties": {"a": {"pattern": "(x"}}}))
+            .starts_with("Invalid schema at #/properties/a/pattern")
+    );
+    assert!(err(json!({"minLength": -1})).starts_with("Invalid schema at #/minLength"));
+    assert_eq!(
+        err(json!({"$ref": "#/$defs/missing"})),
+        "Invalid schema at #/$ref: cannot resolve '#/$defs/missing'"
+    );
+    assert!(err(json!({"items": 3})).starts_with("Invalid schema at #/items"));
+    assert!(err(json!("string")).starts_with("Invalid schema"));
+}
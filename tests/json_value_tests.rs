@@ -0,0 +1,119 @@
+use parse_light::core::json_value::JsonValue;
+use parse_light::{parse, stringify};
+use std::collections::HashMap;
+
+#[test]
+fn test_accessors_and_predicates() {
+    let value =
+        parse(r#"{"s": "text", "n": 42, "f": 1.5, "b": true, "z": null, "a": [1], "o": {}}"#)
+            .unwrap();
+
+    assert_eq!(value["s"].as_str(), Some("text"));
+    assert_eq!(value["n"].as_i64(), Some(42));
+    assert_eq!(value["f"].as_i64(), None);
+    assert_eq!(value["f"].as_f64(), Some(1.5));
+    assert_eq!(value["b"].as_bool(), Some(true));
+    assert!(value["z"].is_null());
+    assert!(value["a"].is_array());
+    assert!(value["o"].is_object());
+    assert!(value["s"].is_string() && value["n"].is_number() && value["b"].is_boolean());
+    assert_eq!(value["a"].as_array().map(|a| a.len()), Some(1));
+    assert_eq!(value["o"].as_object().map(|o| o.len()), Some(0));
+    assert_eq!(value["s"].as_f64(), None);
+}
+
+#[test]
+fn test_get_and_index() {
+    let value = parse(r#"{"flights": [{"origin": "JFK"}]}"#).unwrap();
+    assert_eq!(value["flights"][0]["origin"].as_str(), Some("JFK"));
+    assert_eq!(
+        value
+            .get("flights")
+            .and_then(|f| f.get(0))
+            .and_then(|f| f.get("origin")),
+        Some(&JsonValue::String("JFK".to_string()))
+    );
+
+    // Misses return a shared null instead of panicking
+    assert!(value["missing"]["deeper"][3].is_null());
+    assert!(value["flights"][9].is_null());
+    assert_eq!(value.get("missing"), None);
+    assert_eq!(
+        value.get(String::from("flights")).map(|f| f.is_array()),
+        Some(true)
+    );
+}
+
+#[test]
+fn test_index_mut_auto_vivifies() {
+    let mut value = JsonValue::Null;
+    value["server"]["port"] = JsonValue::from(8080);
+    value["server"]["hosts"] = JsonValue::from(vec!["a", "b"]);
+    value["server"]["hosts"][1] = JsonValue::from("c");
+    assert_eq!(
+        stringify(&value),
+        r#"{"server":{"hosts":["a","c"],"port":8080}}"#
+    );
+
+    if let Some(hosts) = value["server"]["hosts"].as_array_mut() {
+        hosts.push(JsonValue::Null);
+    }
+    if let Some(server) = value.get_mut("server").and_then(|s| s.as_object_mut()) {
+        server.remove("port");
+    }
+    assert_eq!(stringify(&value), r#"{"server":{"hosts":["a","c",null]}}"#);
+}
+
+#[test]
+#[should_panic]
+fn test_index_mut_out_of_bounds_panics() {
+    let mut value = parse("[1]").unwrap();
+    value[3] = JsonValue::Null;
+}
+
+#[test]
+fn test_from_conversions() {
+    assert_eq!(JsonValue::from(true), JsonValue::Boolean(true));
+    assert_eq!(JsonValue::from(7u8), JsonValue::Number(7.0));
+    assert_eq!(JsonValue::from(-3i64), JsonValue::Number(-3.0));
+    assert_eq!(JsonValue::from(0.5f32), JsonValue::Number(0.5));
+    assert_eq!(JsonValue::from("x"), JsonValue::String("x".to_string()));
+    assert_eq!(JsonValue::from(None::<i32>), JsonValue::Null);
+    assert_eq!(
+        JsonValue::from(Some("y")),
+        JsonValue::String("y".to_string())
+    );
+
+    let mut map = HashMap::new();
+    map.insert("k".to_string(), vec![1, 2]);
+    assert_eq!(stringify(&JsonValue::from(map)), r#"{"k":[1,2]}"#);
+}
+
+#[test]
+fn test_try_from_conversions() {
+    assert_eq!(i32::try_from(JsonValue::Number(12.0)), Ok(12));
+    assert!(u8::try_from(JsonValue::Number(300.0)).is_err());
+    assert!(i64::try_from(JsonValue::Number(1.5)).is_err());
+    assert_eq!(f64::try_from(JsonValue::Number(1.5)), Ok(1.5));
+    assert_eq!(bool::try_from(JsonValue::Boolean(false)), Ok(false));
+    assert_eq!(String::try_from(JsonValue::from("s")), Ok("s".to_string()));
+    assert!(String::try_from(JsonValue::Null).is_err());
+
+    let list: Vec<u32> = parse("[1, 2, 3]").unwrap().try_into().unwrap();
+    assert_eq!(list, vec![1, 2, 3]);
+    let bad: Result<Vec<u32>, String> = parse(r#"[1, "two"]"#).unwrap().try_into();
+    assert!(bad.is_err());
+
+    let map: HashMap<String, bool> = parse(r#"{"a": true}"#).unwrap().try_into().unwrap();
+    assert_eq!(map.get("a"), Some(&true));
+}
+
+#[test]
+fn test_try_from_option() {
+    assert_eq!(Option::<i32>::try_from(JsonValue::Null), Ok(None));
+    assert_eq!(Option::<i32>::try_from(JsonValue::Number(3.0)), Ok(Some(3)));
+    assert!(Option::<i32>::try_from(JsonValue::from("3")).is_err());
+
+    let list: Vec<Option<String>> = parse(r#"["a", null]"#).unwrap().try_into().unwrap();
+    assert_eq!(list, vec![Some("a".to_string()), None]);
+}
@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+
+use parse_light::core::parse_options::ParseOptions;
+use parse_light::core::token::Token;
+use parse_light::core::tokentype::TokenType;
+use parse_light::lexer::tokenizer::Tokenizer;
+use parse_light::{json, parse, parse_with};
+
+fn jsonc() -> ParseOptions {
+    ParseOptions::new().with_allow_comments(true)
+}
+
+#[test]
+fn test_comments_are_whitespace() {
+    let value = parse_with(
+        "/* header */ [1, /* two */ 2 // end of line\n, \"// not a comment\", {\"a\" /**/: true}] // done",
+        &jsonc(),
+    )
+    .unwrap();
+    assert_eq!(value, json!([1, 2, "// not a comment", {"a": true}]));
+}
+
+#[test]
+fn test_comments_rejected_by_default() {
+    assert!(parse("[1, /* two */ 2]").is_err());
+    assert!(parse_with("[1 // one\n]", &ParseOptions::default()).is_err());
+}
+
+#[test]
+fn test_comment_errors_are_positioned() {
+    assert_eq!(
+        parse_with("{\"a\": 1} /* never closed *", &jsonc()).unwrap_err(),
+        "Unterminated block comment starting at position 9"
+    );
+    assert_eq!(
+        parse_with("[1, /* a **", &jsonc()).unwrap_err(),
+        "Unterminated block comment starting at position 4"
+    );
+    assert_eq!(
+        parse_with("[true, / x]", &jsonc()).unwrap_err(),
+        "Invalid comment at position 7"
+    );
+}
+
+#[test]
+fn test_tokenizer_skips_comments() {
+    let tokens = Tokenizer::new(Some("{/**/\"k\"//\n:null}".to_string()), None)
+        .with_options(jsonc())
+        .collect::<Result<Vec<Token>, String>>()
+        .unwrap();
+    let types: Vec<TokenType> = tokens.iter().map(|t| t.token_type()).collect();
+    assert_eq!(
+        types,
+        vec![
+            TokenType::LeftBrace,
+            TokenType::Text,
+            TokenType::Colon,
+            TokenType::Null,
+            TokenType::RightBrace,
+            TokenType::EOF
+        ]
+    );
+}
+
+#[test]
+fn test_parse_jsonc_file() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join("tsconfig.jsonc");
+    let path = path.to_string_lossy();
+    let value = parse_with(&path, &jsonc()).unwrap();
+    assert_eq!(value["compilerOptions"]["target"], json!("es2020"));
+    assert_eq!(value["compilerOptions"]["maxNodeModuleJsDepth"], json!(2));
+    assert_eq!(value["compilerOptions"]["paths"]["@/*"], json!(["src/*"]));
+    assert_eq!(value["include"], json!(["src/**/*.ts"]));
+    assert!(parse(&path).is_err());
+}
@@ -0,0 +1,160 @@
+use parse_light::core::json_value::JsonValue;
+use parse_light::jsonpath::json_path::JsonPath;
+use parse_light::parse;
+use std::path::PathBuf;
+
+fn store() -> JsonValue {
+    parse(
+        r#"{
+            "store": {
+                "book": [
+                    {"category": "reference", "author": "Nigel Rees", "title": "Sayings", "price": 8.95},
+                    {"category": "fiction", "author": "Evelyn Waugh", "title": "Sword", "price": 12.99},
+                    {"category": "fiction", "author": "Herman Melville", "title": "Moby Dick", "isbn": "0-553", "price": 8.99},
+                    {"category": "fiction", "author": "J. R. R. Tolkien", "title": "The Lord", "isbn": "0-395", "price": 22.99}
+                ],
+                "bicycle": {"color": "red", "price": 399}
+            }
+        }"#,
+    )
+    .unwrap()
+}
+
+fn paths(value: &JsonValue, expression: &str) -> Vec<String> {
+    value
+        .query(expression)
+        .unwrap()
+        .iter()
+        .map(|m| m.path().to_string())
+        .collect()
+}
+
+#[test]
+fn test_root_and_child_segments() {
+    let value = store();
+    assert_eq!(paths(&value, "$"), vec!["$"]);
+    assert_eq!(
+        paths(&value, "$.store.bicycle.color"),
+        vec!["$['store']['bicycle']['color']"]
+    );
+    assert_eq!(
+        paths(&value, "$['store'][\"bicycle\"]"),
+        vec!["$['store']['bicycle']"]
+    );
+    assert!(paths(&value, "$.missing").is_empty());
+}
+
+#[test]
+fn test_wildcard_and_recursive_descent() {
+    let value = store();
+    let authors: Vec<&JsonValue> = JsonPath::parse("$..author").unwrap().query_values(&value);
+    assert_eq!(authors.len(), 4);
+    assert_eq!(authors[0], &JsonValue::String("Nigel Rees".to_string()));
+
+    assert_eq!(paths(&value, "$.store.book[*]").len(), 4);
+    // Wildcard over an object visits members in sorted key order
+    assert_eq!(
+        paths(&value, "$.store.bicycle.*"),
+        vec![
+            "$['store']['bicycle']['color']",
+            "$['store']['bicycle']['price']"
+        ]
+    );
+    assert_eq!(paths(&value, "$..price").len(), 5);
+}
+
+#[test]
+fn test_indices_slices_and_unions() {
+    let value = parse("[0, 1, 2, 3, 4, 5, 6]").unwrap();
+    assert_eq!(paths(&value, "$[-1]"), vec!["$[6]"]);
+    assert!(paths(&value, "$[7]").is_empty());
+    assert_eq!(paths(&value, "$[1:3]"), vec!["$[1]", "$[2]"]);
+    assert_eq!(paths(&value, "$[5:]"), vec!["$[5]", "$[6]"]);
+    assert_eq!(paths(&value, "$[::3]"), vec!["$[0]", "$[3]", "$[6]"]);
+    assert_eq!(paths(&value, "$[::-3]"), vec!["$[6]", "$[3]", "$[0]"]);
+    assert!(paths(&value, "$[::0]").is_empty());
+    assert_eq!(paths(&value, "$[0, -1, 0]"), vec!["$[0]", "$[6]", "$[0]"]);
+}
+
+#[test]
+fn test_filter_expressions() {
+    let value = store();
+    let titles = |expression: &str| -> Vec<JsonValue> {
+        value
+            .query(expression)
+            .unwrap()
+            .iter()
+            .filter_map(|m| {
+                if let JsonValue::Object(book) = m.value() {
+                    book.get("title").cloned()
+                } else {
+                    None
+                }
+            })
+            .collect()
+    };
+
+    assert_eq!(titles("$.store.book[?@.price < 10]").len(), 2);
+    assert_eq!(titles("$.store.book[?@.isbn]").len(), 2);
+    assert_eq!(titles("$.store.book[?!@.isbn]").len(), 2);
+    assert_eq!(
+        titles("$.store.book[?@.category == 'fiction' && @.price > 20]"),
+        vec![JsonValue::String("The Lord".to_string())]
+    );
+    assert_eq!(
+        titles("$.store.book[?(@.price == 8.95 || @.price == 22.99)]").len(),
+        2
+    );
+    assert_eq!(
+        titles("$.store.book[?@.price > $.store.bicycle.price]").len(),
+        0
+    );
+    // Comparisons against a missing member are false rather than errors
+    assert_eq!(titles("$.store.book[?@.isbn >= '0']").len(), 2);
+    assert_eq!(titles("$.store.book[?@.missing == null]").len(), 0);
+}
+
+#[test]
+fn test_normalized_path_escaping() {
+    let value = parse(r#"{"it's": {"a\\b": 1}}"#).unwrap();
+    assert_eq!(
+        paths(&value, "$..*"),
+        vec![r"$['it\'s']", r"$['it\'s']['a\\b']"]
+    );
+}
+
+#[test]
+fn test_invalid_expressions() {
+    for expression in [
+        "store",
+        "$.",
+        "$[",
+        "$[01]",
+        "$[-0]",
+        "$['unterminated]",
+        "$[?@.a == ]",
+        "$[?@..a == 1]",
+        "$[?1]",
+        "$.store extra",
+    ] {
+        assert!(
+            JsonPath::parse(expression).is_err(),
+            "Expected {} to be rejected",
+            expression
+        );
+    }
+}
+
+#[test]
+fn test_query_sample_file() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join("sample1.json");
+    let value = parse(&path.to_string_lossy()).unwrap();
+    let matches = value
+        .query("$.flights[?@.departure_city == 'New York'].flight_number")
+        .unwrap();
+    assert_eq!(matches[0].path(), "$['flights'][0]['flight_number']");
+    assert_eq!(matches[0].value(), &JsonValue::String("DL8509".to_string()));
+}
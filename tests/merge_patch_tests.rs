@@ -0,0 +1,61 @@
+use parse_light::patch::merge_patch::merge_patch_diff;
+use parse_light::{parse, stringify};
+
+fn merged(target: &str, patch: &str) -> String {
+    let mut value = parse(target).unwrap();
+    value.merge_patch(&parse(patch).unwrap());
+    stringify(&value)
+}
+
+#[test]
+fn test_merge_patch_rfc_example() {
+    let target = r#"{
+        "title": "Goodbye!",
+        "author": {"givenName": "John", "familyName": "Doe"},
+        "tags": ["example", "sample"],
+        "content": "This will be unchanged"
+    }"#;
+    let patch = r#"{
+        "title": "Hello!",
+        "phoneNumber": "+01-123-456-7890",
+        "author": {"familyName": null},
+        "tags": ["example"]
+    }"#;
+    assert_eq!(
+        merged(target, patch),
+        r#"{"author":{"givenName":"John"},"content":"This will be unchanged","phoneNumber":"+01-123-456-7890","tags":["example"],"title":"Hello!"}"#
+    );
+}
+
+#[test]
+fn test_merge_patch_replaces_non_objects() {
+    assert_eq!(
+        merged(r#"{"a": [1, 2]}"#, r#"{"a": {"b": 1}}"#),
+        r#"{"a":{"b":1}}"#
+    );
+    assert_eq!(
+        merged(r#"{"a": {"b": 1}}"#, r#"{"a": [null]}"#),
+        r#"{"a":[null]}"#
+    );
+    assert_eq!(merged(r#"[1]"#, r#"{"a": {"b": null}}"#), r#"{"a":{}}"#);
+    assert_eq!(merged(r#"{"a": 1}"#, r#"["replaced"]"#), r#"["replaced"]"#);
+}
+
+#[test]
+fn test_merge_patch_diff_roundtrip() {
+    let a = parse(r#"{"env": "base", "db": {"host": "localhost", "port": 5432}, "debug": true}"#)
+        .unwrap();
+    let b = parse(
+        r#"{"env": "prod", "db": {"host": "db.internal", "port": 5432}, "replicas": [1, 2]}"#,
+    )
+    .unwrap();
+    let patch = merge_patch_diff(&a, &b);
+    assert_eq!(
+        stringify(&patch),
+        r#"{"db":{"host":"db.internal"},"debug":null,"env":"prod","replicas":[1,2]}"#
+    );
+
+    let mut patched = a.clone();
+    patched.merge_patch(&patch);
+    assert_eq!(patched, b);
+}
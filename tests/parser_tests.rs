@@ -126,3 +126,17 @@ fn test_array_of_different_types() {
         panic!("Expected array");
     }
 }
+
+#[test]
+fn test_parse_surrogate_pair_escape() {
+    let result = parse_str(r#"["\ud83d\ude00", "caf\u00e9"]"#).unwrap();
+    assert_eq!(
+        result,
+        JsonValue::Array(vec![
+            JsonValue::String("😀".to_string()),
+            JsonValue::String("café".to_string()),
+        ])
+    );
+    assert!(parse_str(r#"["\ud83d"]"#).is_err());
+    assert!(parse_str(r#"["\ud83dA"]"#).is_err());
+}
@@ -0,0 +1,105 @@
+use parse_light::parser::partial::{Completion, PartialPolicy};
+use parse_light::{json, parse_partial};
+
+fn partial(input: &str) -> parse_light::parser::partial::PartialValue {
+    parse_partial(input, &PartialPolicy::default()).unwrap()
+}
+
+#[test]
+fn test_complete_input_is_unchanged() {
+    let result = partial("{\"a\": [1, 2], \"b\": \"x\"}");
+    assert!(result.is_complete());
+    assert_eq!(result.value(), &json!({"a": [1, 2], "b": "x"}));
+}
+
+#[test]
+fn test_closes_strings_and_containers() {
+    let result = partial("{\"items\": [{\"name\": \"wid");
+    assert_eq!(result.value(), &json!({"items": [{"name": "wid"}]}));
+    assert_eq!(
+        result.completions(),
+        &[
+            Completion::ClosedString,
+            Completion::ClosedObject,
+            Completion::ClosedArray,
+            Completion::ClosedObject,
+        ]
+    );
+
+    // a half-written escape is dropped
+    assert_eq!(partial("[\"tab\\").value(), &json!(["tab"]));
+    assert_eq!(partial("[\"a\\u00").value(), &json!(["a"]));
+    assert_eq!(partial("[\"a\\u0041").value(), &json!(["aA"]));
+    assert_eq!(partial("[\"q\\\"").value(), &json!(["q\""]));
+}
+
+#[test]
+fn test_literals_and_trailing_commas() {
+    let result = partial("[true, fa");
+    assert_eq!(result.value(), &json!([true, false]));
+    assert_eq!(
+        result.completions()[0],
+        Completion::CompletedLiteral("false".to_string())
+    );
+    assert_eq!(partial("{\"a\": n").value(), &json!({"a": null}));
+
+    let result = partial("[1, 2,  ");
+    assert_eq!(result.value(), &json!([1, 2]));
+    assert_eq!(result.completions()[0], Completion::DroppedTrailingComma);
+    assert_eq!(partial("{\"a\": 1,").value(), &json!({"a": 1}));
+}
+
+#[test]
+fn test_dangling_key_policy() {
+    for input in ["{\"a\": 1, \"b", "{\"a\": 1, \"b\"", "{\"a\": 1, \"b\":  "] {
+        let result = partial(input);
+        assert_eq!(result.value(), &json!({"a": 1}));
+        assert_eq!(
+            result.completions()[0],
+            Completion::DroppedKey("\"b\"".to_string())
+        );
+
+        let keep = PartialPolicy::new().with_keep_dangling_keys(true);
+        let result = parse_partial(input, &keep).unwrap();
+        assert_eq!(result.value(), &json!({"a": 1, "b": null}));
+        assert_eq!(
+            result.completions()[0],
+            Completion::NullValue("\"b\"".to_string())
+        );
+    }
+}
+
+#[test]
+fn test_partial_number_policy() {
+    assert_eq!(partial("[1, 25").value(), &json!([1, 25]));
+
+    let result = partial("{\"a\": 1, \"b\": 2.");
+    assert_eq!(result.value(), &json!({"a": 1}));
+    assert_eq!(
+        result.completions()[0],
+        Completion::DroppedNumber("2.".to_string())
+    );
+    assert_eq!(partial("[3e+").value(), &json!([]));
+
+    let keep = PartialPolicy::new().with_keep_partial_numbers(true);
+    let result = parse_partial("{\"a\": 1, \"b\": 2.", &keep).unwrap();
+    assert_eq!(result.value(), &json!({"a": 1, "b": 2}));
+    assert_eq!(
+        result.completions()[0],
+        Completion::TrimmedNumber("2".to_string())
+    );
+    assert_eq!(parse_partial("[3e+", &keep).unwrap().value(), &json!([3]));
+}
+
+#[test]
+fn test_progressive_prefixes() {
+    let full = "{\"id\": 7, \"tags\": [\"a\", \"bc\"], \"ok\": true, \"nested\": {\"x\": null}}";
+    for end in 1..=full.len() {
+        let result = parse_partial(&full[..end], &PartialPolicy::default());
+        assert!(result.is_ok(), "prefix {:?}: {:?}", &full[..end], result);
+    }
+    assert!(partial(full).is_complete());
+
+    // errors unrelated to truncation are still reported
+    assert!(parse_partial("{\"a\" 1", &PartialPolicy::default()).is_err());
+}
@@ -0,0 +1,120 @@
+use parse_light::core::parse_options::ParseOptions;
+use parse_light::core::raw_value::RawValue;
+use parse_light::core::stringify_options::StringifyOptions;
+use parse_light::{json, parse, parse_with_raw, stringify, stringify_with};
+
+const MESSAGE: &str = r#"{"type": "order", "payload": {"total": 10.50, "items": [1e3, 2.0]}}"#;
+
+#[test]
+fn test_raw_subtree_keeps_source_text() {
+    let message = parse_with_raw(MESSAGE, &ParseOptions::default(), &["/payload"]).unwrap();
+    assert_eq!(message["type"], json!("order"));
+
+    let payload = message["payload"].as_raw().unwrap();
+    assert_eq!(payload.get(), r#"{"total": 10.50, "items": [1e3, 2.0]}"#);
+    assert_eq!(
+        payload.parse().unwrap(),
+        json!({"total": 10.5, "items": [1000, 2]})
+    );
+}
+
+#[test]
+fn test_stringify_writes_raw_verbatim() {
+    let message = parse_with_raw(MESSAGE, &ParseOptions::default(), &["/payload"]).unwrap();
+    let forwarded = json!({"route": "billing", "payload": message["payload"].clone()});
+    assert_eq!(
+        stringify(&forwarded),
+        r#"{"payload":{"total": 10.50, "items": [1e3, 2.0]},"route":"billing"}"#
+    );
+}
+
+#[test]
+fn test_raw_pointers_into_arrays_and_root() {
+    let json = r#"{"batches": [[1, 2], {"a": "x"}], "n": 1.0}"#;
+    let value = parse_with_raw(json, &ParseOptions::default(), &["/batches/1", "/n"]).unwrap();
+    assert!(value["batches"][0].is_array());
+    assert_eq!(value["batches"][1].as_raw().unwrap().get(), r#"{"a": "x"}"#);
+    assert_eq!(value["n"].as_raw().unwrap().get(), "1.0");
+
+    let root = parse_with_raw(" [1,  2] ", &ParseOptions::default(), &[""]).unwrap();
+    assert_eq!(root.as_raw().unwrap().get(), "[1,  2]");
+
+    // pointers that match nothing leave the document fully parsed
+    let value = parse_with_raw(json, &ParseOptions::default(), &["/missing"]).unwrap();
+    assert_eq!(value["n"], json!(1));
+}
+
+#[test]
+fn test_raw_subtrees_are_validated() {
+    let options = ParseOptions::default();
+    for json in [
+        r#"{"payload": {"a": 1,}}"#,
+        r#"{"payload": {"a" 1}}"#,
+        r#"{"payload": {"a": }}"#,
+        r#"{"payload": [1, , 2]}"#,
+        r#"{"payload": {1: 2}}"#,
+        r#"{"payload": ["\q"]}"#,
+        r#"{"payload": [1}"#,
+    ] {
+        assert!(
+            parse_with_raw(json, &options, &["/payload"]).is_err(),
+            "{}",
+            json
+        );
+    }
+
+    let json5 = ParseOptions::default().with_json5(true);
+    let value = parse_with_raw("{payload: [1, 2,]}", &json5, &["/payload"]).unwrap();
+    let payload = value["payload"].as_raw().unwrap();
+    assert_eq!(payload.get(), "[1, 2,]");
+    assert!(payload.parse().is_err());
+    assert_eq!(payload.parse_with(&json5).unwrap(), json!([1, 2]));
+}
+
+#[test]
+fn test_raw_value_from_string() {
+    let raw = RawValue::from_string(r#"{"a": [true, null]}"#.to_string()).unwrap();
+    assert_eq!(raw.to_string(), r#"{"a": [true, null]}"#);
+    assert_eq!(stringify(&json!([raw])), r#"[{"a": [true, null]}]"#);
+
+    assert!(RawValue::from_string("".to_string()).is_err());
+    assert!(RawValue::from_string("[1] [2]".to_string()).is_err());
+    assert!(RawValue::from_string("{\"a\"}".to_string()).is_err());
+}
+
+#[test]
+fn test_raw_values_from_file() {
+    let path = format!("{}/tests/data/sample1.json", env!("CARGO_MANIFEST_DIR"));
+    let value = parse_with_raw(&path, &ParseOptions::default(), &["/flights/1"]).unwrap();
+    let raw = value["flights"][1].as_raw().unwrap();
+    assert!(std::fs::read_to_string(&path).unwrap().contains(raw.get()));
+    assert_eq!(raw.parse().unwrap(), parse(&path).unwrap()["flights"][1]);
+    assert!(value["flights"][0].is_object());
+}
+
+#[test]
+fn test_raw_values_follow_stringify_options() {
+    let json = r#"{"html": {"tag": "</script>", "name": "é"}}"#;
+    let value = parse_with_raw(json, &ParseOptions::default(), &["/html"]).unwrap();
+    assert_eq!(
+        stringify(&value),
+        r#"{"html":{"tag": "</script>", "name": "é"}}"#
+    );
+    let safe = StringifyOptions::new()
+        .with_html_safe(true)
+        .with_ensure_ascii(true);
+    assert_eq!(
+        stringify_with(&value, &safe),
+        r#"{"html":{"name":"\u00e9","tag":"\u003c/script\u003e"}}"#
+    );
+}
+
+#[test]
+fn test_json5_raw_values_are_written_as_json() {
+    let json5 = ParseOptions::default().with_json5(true);
+    let value = parse_with_raw("{a: {b: 'x', /* c */ n: 0x10,}}", &json5, &["/a"]).unwrap();
+    let raw = value["a"].as_raw().unwrap();
+    assert_eq!(raw.get(), "{b: 'x', /* c */ n: 0x10,}");
+    assert_eq!(raw.options(), &json5);
+    assert_eq!(stringify(&value), r#"{"a":{"b":"x","n":16}}"#);
+}
@@ -0,0 +1,124 @@
+use parse_light::parser::repair::{Fix, FixKind};
+use parse_light::{json, parse, repair};
+
+fn kinds(input: &str) -> Vec<FixKind> {
+    repair(input).1.iter().map(Fix::kind).cloned().collect()
+}
+
+#[test]
+fn test_valid_json_is_unchanged() {
+    let input = "{\n  \"a\": [1, 2.5, -3],\n  \"b\": {\"c\": null, \"d\": \"x\\\"y\"}\n}";
+    let (output, fixes) = repair(input);
+    assert_eq!(output, input);
+    assert!(fixes.is_empty());
+}
+
+#[test]
+fn test_commas() {
+    let (output, fixes) = repair("{\"a\": 1\n \"b\": 2,}");
+    assert_eq!(output, "{\"a\": 1,\n \"b\": 2}");
+    assert_eq!(fixes[0].kind(), &FixKind::MissingComma);
+    assert_eq!(fixes[0].position().line(), 2);
+    assert_eq!(fixes[1].kind(), &FixKind::TrailingComma);
+
+    assert_eq!(repair("[1 2 , ]").0, "[1, 2  ]");
+    assert_eq!(
+        kinds("[,1,,2]"),
+        vec![FixKind::ExtraComma, FixKind::ExtraComma]
+    );
+    assert_eq!(parse(&repair("[,1,,2]").0).unwrap(), json!([1, 2]));
+}
+
+#[test]
+fn test_quotes_and_keys() {
+    let (output, fixes) = repair("{name: 'it\\'s \"ok\"', \u{201C}smart\u{201D}: 1}");
+    assert_eq!(output, "{\"name\": \"it's \\\"ok\\\"\", \"smart\": 1}");
+    assert_eq!(
+        fixes.iter().map(Fix::kind).cloned().collect::<Vec<_>>(),
+        vec![
+            FixKind::UnquotedKey,
+            FixKind::SingleQuotedString,
+            FixKind::SmartQuotes
+        ]
+    );
+    assert_eq!(fixes[2].position().column(), 22);
+    assert_eq!(fixes[0].to_string(), "quoted key at 1:2");
+
+    assert_eq!(repair("{\"a\" 1}").0, "{\"a\": 1}");
+    assert_eq!(repair("[hello]").0, "[\"hello\"]");
+}
+
+#[test]
+fn test_python_literals_and_control_characters() {
+    let (output, fixes) = repair("[True, False, None, \"a\tb\nc\"]");
+    assert_eq!(output, "[true, false, null, \"a\\tb\\nc\"]");
+    assert_eq!(
+        fixes.iter().map(Fix::kind).cloned().collect::<Vec<_>>(),
+        vec![
+            FixKind::PythonLiteral("True".to_string()),
+            FixKind::PythonLiteral("False".to_string()),
+            FixKind::PythonLiteral("None".to_string()),
+            FixKind::ControlCharacter,
+            FixKind::ControlCharacter,
+        ]
+    );
+}
+
+#[test]
+fn test_missing_closing_brackets() {
+    let (output, fixes) = repair("{\"a\": [1, {\"b\": 2");
+    assert_eq!(output, "{\"a\": [1, {\"b\": 2}]}");
+    assert_eq!(
+        fixes.iter().map(Fix::kind).cloned().collect::<Vec<_>>(),
+        vec![
+            FixKind::MissingClosingBracket('}'),
+            FixKind::MissingClosingBracket(']'),
+            FixKind::MissingClosingBracket('}'),
+        ]
+    );
+
+    // an inner container left open is closed by its parent's bracket
+    assert_eq!(repair("[{\"a\": 1]").0, "[{\"a\": 1}]");
+    assert_eq!(repair("{\"a\": \"open").0, "{\"a\": \"open\"}");
+    assert_eq!(repair("{\"a\":}").0, "{\"a\":null}");
+    assert_eq!(repair("[1]]").0, "[1]");
+}
+
+#[test]
+fn test_repaired_output_parses() {
+    let input = "{\n  name: 'widget',\n  tags: ['a' 'b',],\n  active: True,\n  owner: None\n";
+    let (output, _) = repair(input);
+    assert_eq!(
+        parse(&output).unwrap(),
+        json!({"name": "widget", "tags": ["a", "b"], "active": true, "owner": null})
+    );
+}
+
+#[test]
+fn test_comments_are_removed() {
+    let (output, fixes) = repair("// c\n{\"a\":1}");
+    assert_eq!(output, "\n{\"a\":1}");
+    assert_eq!(fixes.len(), 1);
+    assert_eq!(fixes[0].kind(), &FixKind::Comment);
+    assert_eq!(fixes[0].to_string(), "removed comment at 1:1");
+
+    let input = "{\n  \"a\": 1, // one\n  /* gone */ \"b\": [1/**/2,] /* unclosed";
+    let (output, _) = repair(input);
+    assert_eq!(parse(&output).unwrap(), json!({"a": 1, "b": [1, 2]}));
+    assert_eq!(
+        kinds(input),
+        vec![
+            FixKind::Comment,
+            FixKind::Comment,
+            FixKind::Comment,
+            FixKind::MissingComma,
+            FixKind::TrailingComma,
+            FixKind::Comment,
+            FixKind::MissingClosingBracket('}'),
+        ]
+    );
+    assert_eq!(
+        repair("[1 / 2]").1[0].kind(),
+        &FixKind::UnexpectedCharacter('/')
+    );
+}
@@ -0,0 +1,118 @@
+use std::path::PathBuf;
+
+use parse_light::core::json_value::JsonValue;
+use parse_light::schema::infer::infer_schema;
+use parse_light::schema::json_schema::JsonSchema;
+use parse_light::{json, parse, stringify};
+
+fn data_path(file_name: &str) -> String {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join(file_name)
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[test]
+fn test_infer_required_optional_and_types() {
+    let samples = [
+        json!({"id": 1, "name": "a", "score": 1.5, "tags": ["x"], "extra": null}),
+        json!({"id": 2, "name": "b", "score": 2, "tags": []}),
+        json!({"id": 3, "name": null, "score": 3, "tags": ["y", 4]}),
+    ];
+    let schema = infer_schema(samples.iter());
+    assert_eq!(
+        stringify(&schema),
+        stringify(&json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "extra": {"type": "null"},
+                "id": {"type": "integer"},
+                "name": {"type": ["null", "string"]},
+                "score": {"type": "number"},
+                "tags": {"type": "array", "items": {"type": ["integer", "string"]}}
+            },
+            "required": ["id", "name", "score", "tags"]
+        }))
+    );
+}
+
+#[test]
+fn test_infer_enum_for_low_cardinality_strings() {
+    let samples: Vec<JsonValue> = ["open", "closed", "open", "open", "closed"]
+        .iter()
+        .enumerate()
+        .map(|(i, status)| json!({"status": *status, "id": format!("id-{}", i)}))
+        .collect();
+    let schema = infer_schema(samples.iter());
+    assert_eq!(
+        stringify(&schema["properties"]["status"]),
+        r#"{"enum":["closed","open"],"type":"string"}"#
+    );
+    assert_eq!(
+        schema["properties"]["id"]["enum"],
+        JsonValue::Null,
+        "too few repeats"
+    );
+
+    let nullable = [json!("a"), json!("a"), JsonValue::Null, json!("a")];
+    assert_eq!(
+        stringify(&infer_schema(nullable.iter())["enum"]),
+        r#"["a",null]"#
+    );
+
+    let unique: Vec<JsonValue> = (0..20).map(|i| json!(format!("v{}", i % 10))).collect();
+    assert_eq!(
+        infer_schema(unique.iter())["enum"],
+        JsonValue::Null,
+        "too many distinct values"
+    );
+}
+
+#[test]
+fn test_infer_from_sample_files() {
+    let document = parse(&data_path("sample1.json")).unwrap();
+    let flights = document["flights"].as_array().unwrap();
+    let schema = infer_schema(flights.iter());
+    assert_eq!(schema["type"], json!("object"));
+    assert_eq!(schema["required"].as_array().unwrap().len(), 9);
+    assert_eq!(
+        schema["properties"]["duration_hours"]["type"],
+        json!("number")
+    );
+    assert!(
+        schema["properties"]["arrival_city"]["enum"]
+            .as_array()
+            .is_some()
+    );
+    assert_eq!(
+        schema["properties"]["flight_number"]["enum"],
+        JsonValue::Null
+    );
+
+    let compiled = JsonSchema::compile(&schema).unwrap();
+    assert!(flights.iter().all(|flight| compiled.is_valid(flight)));
+
+    let documents = [
+        document.clone(),
+        parse(&data_path("sample2.json")).unwrap(),
+        parse(&data_path("nested.json")).unwrap(),
+    ];
+    let compiled = JsonSchema::compile(&infer_schema(documents.iter())).unwrap();
+    assert!(documents.iter().all(|doc| compiled.is_valid(doc)));
+}
+
+#[test]
+fn test_infer_empty_input() {
+    assert_eq!(
+        stringify(&infer_schema(std::iter::empty())),
+        r#"{"$schema":"https://json-schema.org/draft/2020-12/schema"}"#
+    );
+    let schema = infer_schema([json!({"list": []})].iter());
+    assert_eq!(
+        stringify(&schema["properties"]["list"]),
+        r#"{"type":"array"}"#
+    );
+}
@@ -0,0 +1,176 @@
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use parse_light::core::json_value::JsonValue;
+use parse_light::core::parse_options::ParseOptions;
+use parse_light::core::stringify_options::StringifyOptions;
+use parse_light::lexer::tokenizer::Tokenizer;
+use parse_light::serde::deserializer::{Deserializer, from_file, from_str};
+use parse_light::serde::serializer::{to_string, to_string_with};
+use parse_light::{json, parse, parse_with_raw, stringify};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Person {
+    name: String,
+    age: u8,
+    active: bool,
+    address: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Rect(u32, u32),
+    Named { label: String },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Drawing {
+    id: u64,
+    offset: i32,
+    shapes: Vec<Shape>,
+    #[serde(default)]
+    tags: BTreeMap<String, String>,
+}
+
+fn data_path(file_name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join(file_name)
+}
+
+#[test]
+fn test_serde_derive_roundtrip() {
+    let drawing = Drawing {
+        id: 9007199254740993,
+        offset: -4,
+        shapes: vec![
+            Shape::Empty,
+            Shape::Circle(1.5),
+            Shape::Rect(2, 3),
+            Shape::Named {
+                label: "a\"b".to_string(),
+            },
+        ],
+        tags: BTreeMap::from([("k".to_string(), "v".to_string())]),
+    };
+    let json = to_string(&drawing).unwrap();
+    assert_eq!(
+        json,
+        r#"{"id":9007199254740993,"offset":-4,"shapes":["Empty",{"Circle":1.5},{"Rect":[2,3]},{"Named":{"label":"a\"b"}}],"tags":{"k":"v"}}"#
+    );
+    assert_eq!(from_str::<Drawing>(&json).unwrap(), drawing);
+}
+
+#[test]
+fn test_serde_from_file() {
+    let person: Person = from_file(data_path("simple_object.json")).unwrap();
+    assert_eq!(
+        person,
+        Person {
+            name: "John Doe".to_string(),
+            age: 30,
+            active: true,
+            address: None,
+        }
+    );
+
+    let err = from_file::<Person>(data_path("missing.json")).unwrap_err();
+    assert!(err.message().starts_with("File not found"));
+}
+
+#[test]
+fn test_serde_errors() {
+    let err = from_str::<Person>(r#"{"name": "x", "age": 300, "active": true}"#).unwrap_err();
+    assert!(err.message().contains("300"), "{}", err);
+
+    let err = from_str::<Person>(r#"{"name": "x", "age": 3}"#).unwrap_err();
+    assert_eq!(err.message(), "missing field `active`");
+
+    let err = from_str::<BTreeMap<String, u8>>(r#"{"a": 1} {}"#).unwrap_err();
+    assert!(
+        err.message().starts_with("Expected end of input"),
+        "{}",
+        err
+    );
+
+    let err = from_str::<Vec<bool>>("[true null]").unwrap_err();
+    assert!(err.message().starts_with("Expected ',' or ']'"), "{}", err);
+
+    let err = from_str::<Vec<f64>>("[1.2.3]").unwrap_err();
+    assert_eq!(err.message(), "Invalid number '1.2.3' at position 1");
+}
+
+#[test]
+fn test_serde_json_value_roundtrip() {
+    let text = r#"{"a":[1,2.5,"é\n",null],"b":{"c":true},"d":-1}"#;
+    let value: JsonValue = from_str(text).unwrap();
+    assert_eq!(value["a"], parse(r#"[1,2.5,"é\n",null]"#).unwrap());
+    assert_eq!(value["b"]["c"], JsonValue::Boolean(true));
+    assert_eq!(value["d"].as_f64(), Some(-1.0));
+    assert_eq!(to_string(&value).unwrap(), stringify(&value));
+}
+
+#[test]
+fn test_serde_serializer_uses_escaper() {
+    let value = json!({"html": "<a href='/x'>é</a>"});
+    let options = StringifyOptions::new()
+        .with_ensure_ascii(true)
+        .with_html_safe(true)
+        .with_escape_slash(true);
+    assert_eq!(
+        to_string_with(&value, &options).unwrap(),
+        value.stringify_with(&options)
+    );
+    assert_eq!(
+        to_string(&f64::NAN).unwrap(),
+        "null",
+        "non-finite floats are written as null"
+    );
+    let json5 = StringifyOptions::new().with_json5(true);
+    assert_eq!(
+        to_string_with(&BTreeMap::from([("max", f64::INFINITY)]), &json5).unwrap(),
+        "{max:Infinity}"
+    );
+
+    let err = to_string(&BTreeMap::from([(vec![1], 2)])).unwrap_err();
+    assert_eq!(err.message(), "Object keys must be strings");
+    assert_eq!(
+        to_string(&BTreeMap::from([(7, 'x')])).unwrap(),
+        r#"{"7":"x"}"#
+    );
+}
+
+#[test]
+fn test_deserializer_releases_tokens() {
+    let items: Vec<String> = (0..50_000)
+        .map(|i| format!(r#"{{"name": "n{}", "active": true, "tags": [null]}}"#, i))
+        .collect();
+    let json = format!("[{}]", items.join(","));
+
+    #[derive(Deserialize)]
+    struct Item {
+        name: String,
+        active: bool,
+        tags: Vec<Option<String>>,
+    }
+    let mut deserializer = Deserializer::new(Tokenizer::new(Some(json), None));
+    let items = Vec::<Item>::deserialize(&mut deserializer).unwrap();
+    deserializer.end().unwrap();
+    assert_eq!(items.len(), 50_000);
+    assert_eq!(items[49_999].name, "n49999");
+    assert!(items.iter().all(|item| item.active && item.tags == [None]));
+    assert!(deserializer.tokenizer().fsm.all_tokens.len() <= 2);
+}
+
+#[test]
+fn test_serialize_raw_values() {
+    let json5 = ParseOptions::new().with_json5(true);
+    let value = parse_with_raw("{a: ['x', 0x10,]}", &json5, &["/a"]).unwrap();
+    assert_eq!(to_string(&value).unwrap(), r#"{"a":["x",16]}"#);
+}
@@ -1,4 +1,6 @@
-use parse_light::{parse, stringify};
+use parse_light::core::json_value::JsonValue;
+use parse_light::core::stringify_options::StringifyOptions;
+use parse_light::{parse, stringify, stringify_with};
 
 #[test]
 fn test_stringify_primitives() {
@@ -66,3 +68,37 @@ fn test_api_roundtrip() {
     let serialized = stringify(&parsed);
     assert_eq!(serialized, original);
 }
+
+#[test]
+fn test_stringify_ensure_ascii() {
+    let value = JsonValue::String("café 😀".to_string());
+    let options = StringifyOptions::new().with_ensure_ascii(true);
+    let serialized = stringify_with(&value, &options);
+    assert_eq!(serialized, r#""caf\u00e9 \ud83d\ude00""#);
+    assert!(serialized.is_ascii());
+
+    // Default output keeps unicode verbatim
+    assert_eq!(stringify(&value), "\"café 😀\"");
+
+    // Surrogate pairs decode back to the original string
+    let wrapped = format!("[{}]", serialized);
+    assert_eq!(parse(&wrapped).unwrap(), JsonValue::Array(vec![value]));
+}
+
+#[test]
+fn test_stringify_html_safe() {
+    let value = JsonValue::String("</script><b>&\u{2028}\u{2029}".to_string());
+    let options = StringifyOptions::new().with_html_safe(true);
+    assert_eq!(
+        stringify_with(&value, &options),
+        r#""\u003c/script\u003e\u003cb\u003e\u0026\u2028\u2029""#
+    );
+}
+
+#[test]
+fn test_stringify_escape_slash() {
+    let value = JsonValue::String("a/b".to_string());
+    assert_eq!(stringify(&value), r#""a/b""#);
+    let options = StringifyOptions::new().with_escape_slash(true);
+    assert_eq!(stringify_with(&value, &options), r#""a\/b""#);
+}
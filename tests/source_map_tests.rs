@@ -0,0 +1,94 @@
+use parse_light::core::parse_options::ParseOptions;
+use parse_light::json;
+use parse_light::parse_with_source_map;
+use parse_light::schema::json_schema::JsonSchema;
+
+const CONFIG: &str = "{\n  \"name\": \"api\",\n  \"servers\": [\n    {\"port\": 80},\n    {\"port\": \"443\"}\n  ]\n}";
+
+#[test]
+fn test_values_are_located_by_pointer() {
+    let (value, map) = parse_with_source_map(CONFIG, &ParseOptions::default()).unwrap();
+    assert_eq!(value["servers"][1]["port"], json!("443"));
+
+    let port = map.value_span("/servers/1/port").unwrap();
+    assert_eq!((port.start_line(), port.start_col()), (5, 14));
+    assert_eq!(&CONFIG[port.start()..port.end()], "\"443\"");
+
+    let name = map.get("/name").unwrap();
+    assert_eq!(name.key().unwrap().to_string(), "2:3-2:9");
+    assert_eq!(name.value().to_string(), "2:11-2:16");
+}
+
+#[test]
+fn test_containers_span_their_brackets() {
+    let (_, map) = parse_with_source_map(CONFIG, &ParseOptions::default()).unwrap();
+    let root = map.get("").unwrap();
+    assert!(root.key().is_none());
+    assert_eq!(
+        (root.value().start(), root.value().end()),
+        (0, CONFIG.len())
+    );
+
+    let servers = map.value_span("/servers").unwrap();
+    assert_eq!(servers.to_string(), "3:14-6:4");
+    assert!(map.key_span("/servers/0").is_none());
+    assert_eq!(map.len(), 7);
+}
+
+#[test]
+fn test_escaped_keys() {
+    let json = "{\"a/b\": {\"m~n\": [true, null]}, \"\\u0041\": 1}";
+    let (_, map) = parse_with_source_map(json, &ParseOptions::default()).unwrap();
+    let pointers: Vec<&str> = map.iter().map(|(pointer, _)| pointer).collect();
+    assert_eq!(
+        pointers,
+        vec![
+            "",
+            "/A",
+            "/a~1b",
+            "/a~1b/m~0n",
+            "/a~1b/m~0n/0",
+            "/a~1b/m~0n/1"
+        ]
+    );
+}
+
+#[test]
+fn test_schema_errors_point_into_source() {
+    let schema = JsonSchema::compile(&json!({
+        "properties": {
+            "servers": {"items": {"properties": {"port": {"type": "integer"}}}}
+        }
+    }))
+    .unwrap();
+    let (value, map) = parse_with_source_map(CONFIG, &ParseOptions::default()).unwrap();
+    let errors = schema.validate(&value).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    let span = map.value_span(errors[0].instance_path()).unwrap();
+    assert_eq!(span.to_string(), "5:14-5:19");
+}
+
+#[test]
+fn test_json5_source_map() {
+    let json = "// config\n{\n  port: 0x50,\n  'hosts': ['a', 'b',],\n}";
+    let options = ParseOptions::new().with_json5(true);
+    let (_, map) = parse_with_source_map(json, &options).unwrap();
+    assert_eq!(map.key_span("/port").unwrap().to_string(), "3:3-3:7");
+    assert_eq!(map.value_span("/port").unwrap().to_string(), "3:9-3:13");
+    assert_eq!(map.value_span("/hosts/1").unwrap().to_string(), "4:18-4:21");
+}
+
+#[test]
+fn test_negative_numbers_span_their_sign() {
+    let json = "{\"a\": -12, \"b\": {\"c\": -1.5}}";
+    let (_, map) = parse_with_source_map(json, &ParseOptions::default()).unwrap();
+    let a = map.value_span("/a").unwrap();
+    assert_eq!(&json[a.start()..a.end()], "-12");
+    assert_eq!(a.to_string(), "1:7-1:10");
+    let c = map.value_span("/b/c").unwrap();
+    assert_eq!(&json[c.start()..c.end()], "-1.5");
+
+    let options = ParseOptions::new().with_json5(true);
+    let (_, map) = parse_with_source_map("{a: -12}", &options).unwrap();
+    assert_eq!(map.value_span("/a").unwrap().to_string(), "1:5-1:8");
+}
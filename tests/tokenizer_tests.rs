@@ -1,5 +1,7 @@
 use parse_light::core::token::Token;
 use parse_light::core::tokentype::TokenType;
+use parse_light::lexer::buffered_string_reader::BufferedStringReader;
+use parse_light::lexer::byte_reader::ByteReader;
 use parse_light::lexer::tokenizer::Tokenizer;
 
 // Helper to collect tokens using the Iterator interface
@@ -167,3 +169,142 @@ fn test_complex_object() {
     assert_eq!(tokens[32].token_type(), TokenType::RightBrace);
     assert_eq!(tokens[33].token_type(), TokenType::EOF);
 }
+
+fn spans(json: &str) -> Vec<(usize, usize, usize, usize, usize, usize)> {
+    tokenize(json)
+        .iter()
+        .map(|t| {
+            let s = t.span();
+            (
+                s.start(),
+                s.end(),
+                s.start_line(),
+                s.start_col(),
+                s.end_line(),
+                s.end_col(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_token_spans_track_lines_and_columns() {
+    let json = "{\r\n  \"k\": 12,\n\t\"é\": true\r}";
+    assert_eq!(
+        spans(json),
+        vec![
+            (0, 1, 1, 1, 1, 2),     // {
+            (5, 8, 2, 3, 2, 6),     // "k"
+            (8, 9, 2, 6, 2, 7),     // :
+            (10, 12, 2, 8, 2, 10),  // 12, without the following comma
+            (12, 13, 2, 10, 2, 11), // ,
+            (15, 19, 3, 2, 3, 5),   // "é" is two bytes but one column
+            (19, 20, 3, 5, 3, 6),   // :
+            (21, 25, 3, 7, 3, 11),  // true
+            (26, 27, 4, 1, 4, 2),   // } after a lone \r
+            (27, 27, 4, 2, 4, 2),   // EOF
+        ]
+    );
+
+    let tokens = tokenize(json);
+    assert_eq!(tokens[3].end_pos(), Some(12));
+    assert_eq!(tokens[3].span().to_string(), "2:8-2:10");
+    assert_eq!(tokens[1].span().len(), 3);
+}
+
+#[test]
+fn test_number_span_excludes_trailing_whitespace() {
+    let tokens = tokenize("[1.5 \n ,2]");
+    assert_eq!(tokens[1].get_value_as_string().unwrap(), "1.5");
+    assert_eq!(tokens[1].span().end(), 4);
+    assert_eq!(tokens[2].span().start_line(), 2);
+    assert_eq!(tokens[2].span().start_col(), 2);
+}
+
+#[test]
+fn test_file_and_string_spans_match() {
+    let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join("nested.json");
+    let text = std::fs::read_to_string(&path).unwrap();
+    let from_file: Vec<_> = Tokenizer::new(None, Some(path.to_string_lossy().into_owned()))
+        .collect::<Result<Vec<Token>, String>>()
+        .unwrap()
+        .iter()
+        .map(|t| t.span())
+        .collect();
+    let from_string: Vec<_> = tokenize(&text).iter().map(|t| t.span()).collect();
+    assert_eq!(from_file, from_string);
+    assert!(from_file.last().unwrap().start_line() > 1);
+}
+
+#[test]
+fn test_release_tokens_keeps_memory_bounded() {
+    let json = format!("[{}1]", "1, ".repeat(1000));
+    let mut tokenizer = Tokenizer::new(Some(json), None);
+    loop {
+        let token = tokenizer.next_token().unwrap();
+        tokenizer.release_tokens();
+        assert!(tokenizer.fsm.all_tokens.len() <= 2);
+        if token.token_type() == TokenType::EOF {
+            break;
+        }
+    }
+}
+
+#[test]
+fn test_release_tokens_still_checks_sequence() {
+    let mut tokenizer = Tokenizer::new(Some("[{:}]".to_string()), None);
+    let mut result = Ok(());
+    for _ in 0..4 {
+        result = tokenizer.next_token().map(|_| ());
+        tokenizer.release_tokens();
+        if result.is_err() {
+            break;
+        }
+    }
+    assert!(result.is_err());
+}
+
+// A reader implemented outside the crate that relies on the default `position`
+struct OffsetOnlyReader(BufferedStringReader);
+
+impl ByteReader for OffsetOnlyReader {
+    fn next_byte(&mut self) -> Result<u8, String> {
+        self.0.next_byte()
+    }
+    fn peek_byte(&mut self) -> Result<u8, String> {
+        self.0.peek_byte()
+    }
+    fn next_chunk(&mut self) -> Result<Vec<u8>, String> {
+        self.0.next_chunk()
+    }
+    fn next_until(&mut self, byte: u8) -> Result<Vec<u8>, String> {
+        self.0.next_until(byte)
+    }
+    fn next_until_any(&mut self, bytes: &[u8]) -> Result<Vec<u8>, String> {
+        self.0.next_until_any(bytes)
+    }
+    fn skip_white_space(&mut self) {
+        self.0.skip_white_space()
+    }
+    fn offset(&mut self) -> usize {
+        self.0.offset()
+    }
+    fn throw_if_consumed(&mut self) -> Result<(), String> {
+        self.0.throw_if_consumed()
+    }
+}
+
+#[test]
+fn test_reader_without_line_tracking() {
+    let reader = OffsetOnlyReader(BufferedStringReader::new(b"{\n  \"a\": 1}".to_vec()));
+    let tokens: Vec<Token> = Tokenizer::from_reader(reader)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let key = &tokens[1];
+    assert_eq!(key.token_type(), TokenType::Text);
+    assert_eq!(key.span().start(), 4);
+    assert_eq!((key.span().start_line(), key.span().start_col()), (1, 5));
+}